@@ -0,0 +1,95 @@
+//! Approximate equality for points, behind the `approx` feature.
+//!
+//! Every field is compared with the same tolerance, in the field's own SBET
+//! units. Angles are compared as stored, without wrapping.
+
+use crate::{Field, Point};
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+
+impl AbsDiffEq for Point {
+    type Epsilon = f64;
+
+    fn default_epsilon() -> f64 {
+        f64::default_epsilon()
+    }
+
+    /// Returns true if every field differs by no more than `epsilon`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use approx::assert_abs_diff_eq;
+    /// use sbet::Point;
+    ///
+    /// let a = Point { altitude: 100., ..Default::default() };
+    /// let b = Point { altitude: 100.001, ..Default::default() };
+    /// assert_abs_diff_eq!(a, b, epsilon = 0.01);
+    /// ```
+    fn abs_diff_eq(&self, other: &Point, epsilon: f64) -> bool {
+        Field::ALL
+            .into_iter()
+            .all(|field| self.get(field).abs_diff_eq(&other.get(field), epsilon))
+    }
+}
+
+impl RelativeEq for Point {
+    fn default_max_relative() -> f64 {
+        f64::default_max_relative()
+    }
+
+    /// Returns true if every field is within `epsilon`, or within `max_relative` of the larger value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use sbet::Point;
+    ///
+    /// let a = Point { time: 396000., ..Default::default() };
+    /// let b = Point { time: 396000.0001, ..Default::default() };
+    /// assert_relative_eq!(a, b, max_relative = 1e-9);
+    /// ```
+    fn relative_eq(&self, other: &Point, epsilon: f64, max_relative: f64) -> bool {
+        Field::ALL.into_iter().all(|field| {
+            self.get(field)
+                .relative_eq(&other.get(field), epsilon, max_relative)
+        })
+    }
+}
+
+impl UlpsEq for Point {
+    fn default_max_ulps() -> u32 {
+        f64::default_max_ulps()
+    }
+
+    fn ulps_eq(&self, other: &Point, epsilon: f64, max_ulps: u32) -> bool {
+        Field::ALL.into_iter().all(|field| {
+            self.get(field)
+                .ulps_eq(&other.get(field), epsilon, max_ulps)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Point;
+    use approx::{abs_diff_eq, relative_eq, ulps_eq};
+
+    #[test]
+    fn every_field() {
+        let a = Point {
+            time: 1.,
+            z_angular_rate: 0.5,
+            ..Default::default()
+        };
+        let b = Point {
+            z_angular_rate: 0.5 + 1e-6,
+            ..a
+        };
+        assert!(abs_diff_eq!(a, b, epsilon = 1e-5));
+        assert!(!abs_diff_eq!(a, b));
+        assert!(relative_eq!(a, b, max_relative = 1e-5));
+        assert!(!ulps_eq!(a, b));
+        assert!(ulps_eq!(a, a));
+    }
+}
@@ -0,0 +1,118 @@
+use crate::{Point, Trajectory};
+
+/// The minimum and maximum latitude, longitude, and altitude of a trajectory.
+///
+/// Latitudes and longitudes are in radians unless converted with
+/// [Bounds::to_degrees]. Bounds that cross the antimeridian span almost
+/// all longitudes.
+///
+/// Created by [Trajectory::bounds] and [Dataset::bounds](crate::Dataset::bounds).
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[allow(missing_docs)]
+pub struct Bounds {
+    pub min_latitude: f64,
+    pub max_latitude: f64,
+    pub min_longitude: f64,
+    pub max_longitude: f64,
+    pub min_altitude: f64,
+    pub max_altitude: f64,
+}
+
+impl Bounds {
+    /// Returns bounds containing only a point.
+    pub(crate) fn from_point(point: &Point) -> Bounds {
+        Bounds {
+            min_latitude: point.latitude,
+            max_latitude: point.latitude,
+            min_longitude: point.longitude,
+            max_longitude: point.longitude,
+            min_altitude: point.altitude,
+            max_altitude: point.altitude,
+        }
+    }
+
+    /// Returns these bounds grown to contain a point.
+    pub(crate) fn extend(self, point: &Point) -> Bounds {
+        Bounds {
+            min_latitude: self.min_latitude.min(point.latitude),
+            max_latitude: self.max_latitude.max(point.latitude),
+            min_longitude: self.min_longitude.min(point.longitude),
+            max_longitude: self.max_longitude.max(point.longitude),
+            min_altitude: self.min_altitude.min(point.altitude),
+            max_altitude: self.max_altitude.max(point.altitude),
+        }
+    }
+
+    /// Returns these bounds with latitudes and longitudes in degrees.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Point, Trajectory};
+    ///
+    /// let point = Point::builder().latitude_degrees(45.).build();
+    /// let bounds = Trajectory::from(vec![point]).bounds().unwrap().to_degrees();
+    /// assert!((bounds.max_latitude - 45.).abs() < 1e-12);
+    /// ```
+    pub fn to_degrees(&self) -> Bounds {
+        Bounds {
+            min_latitude: self.min_latitude.to_degrees(),
+            max_latitude: self.max_latitude.to_degrees(),
+            min_longitude: self.min_longitude.to_degrees(),
+            max_longitude: self.max_longitude.to_degrees(),
+            ..*self
+        }
+    }
+}
+
+impl Trajectory {
+    /// Returns the bounds of this trajectory's points, or `None` if it's empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Trajectory;
+    ///
+    /// let trajectory = Trajectory::from_path("data/2-points.sbet").unwrap();
+    /// let bounds = trajectory.bounds().unwrap();
+    /// assert!(bounds.min_altitude <= bounds.max_altitude);
+    /// ```
+    pub fn bounds(&self) -> Option<Bounds> {
+        let (first, rest) = self.points().split_first()?;
+        Some(
+            rest.iter()
+                .fold(Bounds::from_point(first), |bounds, point| {
+                    bounds.extend(point)
+                }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Point, Trajectory};
+
+    #[test]
+    fn bounds() {
+        let trajectory = Trajectory::from(vec![
+            Point {
+                latitude: 0.1,
+                longitude: -0.2,
+                altitude: 30.,
+                ..Default::default()
+            },
+            Point {
+                latitude: -0.1,
+                longitude: 0.2,
+                altitude: 10.,
+                ..Default::default()
+            },
+        ]);
+        let bounds = trajectory.bounds().unwrap();
+        assert_eq!(bounds.min_latitude, -0.1);
+        assert_eq!(bounds.max_longitude, 0.2);
+        assert_eq!(bounds.min_altitude, 10.);
+        assert_eq!(bounds.max_altitude, 30.);
+        assert!(Trajectory::default().bounds().is_none());
+    }
+}
@@ -0,0 +1,143 @@
+use crate::Point;
+
+macro_rules! degrees_accessors {
+    ($($field:ident, $getter:ident, $setter:ident;)*) => {
+        impl Point {
+            $(
+                #[doc = concat!("Returns the ", stringify!($field), " in degrees.")]
+                pub fn $getter(&self) -> f64 {
+                    self.$field.to_degrees()
+                }
+
+                #[doc = concat!("Sets the ", stringify!($field), " from a value in degrees.")]
+                pub fn $setter(&mut self, degrees: f64) {
+                    self.$field = degrees.to_radians();
+                }
+            )*
+        }
+
+        impl PointBuilder {
+            $(
+                #[doc = concat!("Sets the ", stringify!($field), " from a value in degrees.")]
+                pub fn $getter(mut self, degrees: f64) -> PointBuilder {
+                    self.point.$setter(degrees);
+                    self
+                }
+            )*
+        }
+    };
+}
+
+macro_rules! builder_setters {
+    ($($field:ident,)*) => {
+        impl PointBuilder {
+            $(
+                #[doc = concat!("Sets the ", stringify!($field), " in SBET units (radians for angles).")]
+                pub fn $field(mut self, $field: f64) -> PointBuilder {
+                    self.point.$field = $field;
+                    self
+                }
+            )*
+        }
+    };
+}
+
+/// Builds a [Point], with helpers for setting angles in degrees.
+///
+/// Created by [Point::builder]. Unset fields are zero.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::Point;
+///
+/// let point = Point::builder()
+///     .time(151631.)
+///     .latitude_degrees(32.5)
+///     .longitude_degrees(-117.)
+///     .altitude(100.)
+///     .yaw_degrees(90.)
+///     .build();
+/// assert_eq!(point.latitude, 32.5f64.to_radians());
+/// assert_eq!(point.yaw_degrees(), 90.);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PointBuilder {
+    point: Point,
+}
+
+impl Point {
+    /// Returns a builder for a point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Point;
+    ///
+    /// let point = Point::builder().altitude(42.).build();
+    /// assert_eq!(point.altitude, 42.);
+    /// ```
+    pub fn builder() -> PointBuilder {
+        PointBuilder::default()
+    }
+}
+
+impl PointBuilder {
+    /// Builds the point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Point;
+    ///
+    /// let point = Point::builder().build();
+    /// assert_eq!(point, Point::default());
+    /// ```
+    pub fn build(self) -> Point {
+        self.point
+    }
+}
+
+builder_setters!(
+    time,
+    latitude,
+    longitude,
+    altitude,
+    x_velocity,
+    y_velocity,
+    z_velocity,
+    roll,
+    pitch,
+    yaw,
+    wander_angle,
+    x_acceleration,
+    y_acceleration,
+    z_acceleration,
+    x_angular_rate,
+    y_angular_rate,
+    z_angular_rate,
+);
+
+degrees_accessors!(
+    latitude, latitude_degrees, set_latitude_degrees;
+    longitude, longitude_degrees, set_longitude_degrees;
+    roll, roll_degrees, set_roll_degrees;
+    pitch, pitch_degrees, set_pitch_degrees;
+    yaw, yaw_degrees, set_yaw_degrees;
+    wander_angle, wander_angle_degrees, set_wander_angle_degrees;
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::Point;
+
+    #[test]
+    fn degrees_round_trip() {
+        let mut point = Point::default();
+        point.set_roll_degrees(-12.5);
+        point.set_wander_angle_degrees(180.);
+        assert_eq!(point.roll, (-12.5f64).to_radians());
+        assert!((point.roll_degrees() + 12.5).abs() < 1e-12);
+        assert_eq!(point.wander_angle, std::f64::consts::PI);
+    }
+}
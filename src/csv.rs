@@ -0,0 +1,604 @@
+//! Reading and writing points as delimited text.
+//!
+//! Each [Column] maps a named CSV column to a [Field] of a [Point], or a
+//! [Derived](crate::Derived) value, with units and a scale factor for
+//! converting between the CSV value and the SBET value.
+//!
+//! # Examples
+//!
+//! ```
+//! use sbet::csv::{Reader, Writer};
+//! use sbet::Point;
+//!
+//! let point = Point { time: 1., latitude: 0.5, ..Default::default() };
+//! let mut writer = Writer::new(Vec::new());
+//! writer.write_one(&point).unwrap();
+//! let csv = writer.finish().unwrap();
+//!
+//! let points = Reader::new(&csv[..]).collect::<sbet::Result<Vec<_>>>().unwrap();
+//! assert_eq!(points[0].latitude, 0.5);
+//! ```
+
+use crate::{Error, Field, Point, Quantity, Result};
+use std::{
+    fmt::Write as _,
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::Path,
+};
+
+/// How a column's values relate to its field's SBET values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Units {
+    /// The same units as the SBET record: meters, seconds, and radians.
+    Native,
+
+    /// Degrees, for angles and angular rates.
+    Degrees,
+
+    /// An ISO 8601 UTC datetime, for the time field, with times in seconds-of-week of this GPS week.
+    #[cfg(feature = "chrono")]
+    Utc {
+        /// The GPS week.
+        week: u32,
+    },
+}
+
+/// A CSV column holding one field, or derived value, of each point.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::csv::{Column, Units};
+/// use sbet::Field;
+///
+/// let column = Column {
+///     name: "HeightFt".to_string(),
+///     scale: 0.3048,
+///     ..Column::new(Field::Altitude)
+/// };
+/// assert_eq!(column.units, Units::Native);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Column {
+    /// The name of the column in the header.
+    pub name: String,
+
+    /// The point field or derived value.
+    ///
+    /// Derived values are written, but ignored when reading.
+    pub quantity: Quantity,
+
+    /// The units of the column's values.
+    pub units: Units,
+
+    /// Multiplying a column value by this factor converts it into `units`.
+    pub scale: f64,
+}
+
+impl Column {
+    /// Creates a column named after a field or derived value.
+    ///
+    /// Latitude and longitude are in degrees; other values are in SBET units.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::csv::{Column, Units};
+    /// use sbet::{Derived, Field};
+    ///
+    /// let column = Column::new(Field::Latitude);
+    /// assert_eq!(column.name, "latitude");
+    /// assert_eq!(column.units, Units::Degrees);
+    /// assert_eq!(Column::new(Derived::TrueHeading).name, "true_heading");
+    /// ```
+    pub fn new<Q: Into<Quantity>>(quantity: Q) -> Column {
+        let quantity = quantity.into();
+        Column {
+            name: quantity.name().to_string(),
+            quantity,
+            units: match quantity {
+                Quantity::Field(Field::Latitude | Field::Longitude) => Units::Degrees,
+                _ => Units::Native,
+            },
+            scale: 1.,
+        }
+    }
+
+    /// Writes a point's value for this column.
+    fn format(&self, point: &Point, precision: Option<usize>, line: &mut String) -> Result<()> {
+        let value = point.quantity(self.quantity);
+        let value = match self.units {
+            Units::Native => value / self.scale,
+            Units::Degrees => value.to_degrees() / self.scale,
+            #[cfg(feature = "chrono")]
+            Units::Utc { week } => {
+                let utc = crate::time::to_utc(week, value).ok_or(Error::TimeOutOfRange(value))?;
+                line.push_str(&utc.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true));
+                return Ok(());
+            }
+        };
+        match precision {
+            Some(precision) => write!(line, "{:.*}", precision, value),
+            None => write!(line, "{}", value),
+        }
+        .expect("writing to a string can't fail");
+        Ok(())
+    }
+
+    /// Parses a CSV value into this column's field value.
+    fn parse(&self, value: &str) -> Option<f64> {
+        match self.units {
+            Units::Native => value.parse::<f64>().ok().map(|value| value * self.scale),
+            Units::Degrees => value
+                .parse::<f64>()
+                .ok()
+                .map(|value| (value * self.scale).to_radians()),
+            #[cfg(feature = "chrono")]
+            Units::Utc { week } => value
+                .parse()
+                .ok()
+                .map(|datetime| crate::time::seconds_of_week(datetime, week)),
+        }
+    }
+}
+
+/// Reads points from delimited text with a header line.
+///
+/// Fields without a column are set to zero. Blank lines are skipped.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::csv::Reader;
+///
+/// let csv = "time,latitude,longitude\n1.5,45,-120\n";
+/// let points = Reader::new(csv.as_bytes())
+///     .collect::<sbet::Result<Vec<_>>>()
+///     .unwrap();
+/// assert_eq!(points[0].time, 1.5);
+/// assert_eq!(points[0].latitude_degrees(), 45.);
+/// ```
+#[derive(Debug)]
+pub struct Reader<R: BufRead> {
+    read: R,
+    delimiter: char,
+    columns: Option<Vec<Column>>,
+    indices: Option<Vec<(usize, Column)>>,
+    line: String,
+    line_number: u64,
+}
+
+impl<R: BufRead> Reader<R> {
+    /// Creates a reader that matches columns to fields by header name.
+    ///
+    /// Header names that are [Field] names, as written by [Writer], are
+    /// used, with latitude and longitude in degrees and other fields in
+    /// SBET units. Other columns are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::csv::Reader;
+    ///
+    /// let reader = Reader::new(std::io::stdin().lock());
+    /// ```
+    pub fn new(read: R) -> Reader<R> {
+        Reader {
+            read,
+            delimiter: ',',
+            columns: None,
+            indices: None,
+            line: String::new(),
+            line_number: 0,
+        }
+    }
+
+    /// Creates a reader for these columns.
+    ///
+    /// Columns are found by name in the header line, and other columns are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::csv::{Column, Reader};
+    /// use sbet::Field;
+    ///
+    /// let columns = vec![Column {
+    ///     name: "GPSTime".to_string(),
+    ///     ..Column::new(Field::Time)
+    /// }];
+    /// let mut reader = Reader::with_columns("Lat,GPSTime\n45,1.5\n".as_bytes(), columns);
+    /// assert_eq!(reader.next().unwrap().unwrap().time, 1.5);
+    /// ```
+    pub fn with_columns(read: R, columns: Vec<Column>) -> Reader<R> {
+        let mut reader = Reader::new(read);
+        reader.columns = Some(columns);
+        reader
+    }
+
+    /// Sets the delimiter between values, which is a comma by default.
+    ///
+    /// Set the delimiter before reading any points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::csv::Reader;
+    ///
+    /// let mut reader = Reader::new("time;altitude\n1;2\n".as_bytes());
+    /// reader.set_delimiter(';');
+    /// assert_eq!(reader.next().unwrap().unwrap().altitude, 2.);
+    /// ```
+    pub fn set_delimiter(&mut self, delimiter: char) {
+        self.delimiter = delimiter;
+    }
+
+    /// Reads one point.
+    ///
+    /// Returns `None` at the end of the input.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is empty, a column is missing from the
+    /// header, or a value can't be parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::csv::Reader;
+    ///
+    /// let mut reader = Reader::new("time\n1.5\n".as_bytes());
+    /// assert_eq!(reader.read_one().unwrap().unwrap().time, 1.5);
+    /// assert!(reader.read_one().unwrap().is_none());
+    /// ```
+    pub fn read_one(&mut self) -> Result<Option<Point>> {
+        if self.indices.is_none() {
+            if !self.read_line()? {
+                return Err(Error::EmptyCsv);
+            }
+            self.indices = Some(self.match_header()?);
+        }
+        loop {
+            if !self.read_line()? {
+                return Ok(None);
+            }
+            if !self.line.trim().is_empty() {
+                break;
+            }
+        }
+        let values: Vec<&str> = self.line.split(self.delimiter).map(str::trim).collect();
+        let mut point = Point::default();
+        for (index, column) in self.indices.iter().flatten() {
+            let value = values
+                .get(*index)
+                .and_then(|value| column.parse(value))
+                .ok_or_else(|| Error::InvalidCsvValue {
+                    line: self.line_number,
+                    column: column.name.clone(),
+                })?;
+            if let Quantity::Field(field) = column.quantity {
+                point.set(field, value);
+            }
+        }
+        Ok(Some(point))
+    }
+
+    fn read_line(&mut self) -> Result<bool> {
+        self.line.clear();
+        let n = self.read.read_line(&mut self.line)?;
+        self.line_number += 1;
+        Ok(n > 0)
+    }
+
+    /// Returns the index of each column in the header.
+    fn match_header(&self) -> Result<Vec<(usize, Column)>> {
+        let header: Vec<&str> = self.line.split(self.delimiter).map(str::trim).collect();
+        if let Some(columns) = &self.columns {
+            columns
+                .iter()
+                .map(|column| {
+                    header
+                        .iter()
+                        .position(|name| *name == column.name)
+                        .map(|index| (index, column.clone()))
+                        .ok_or_else(|| Error::MissingColumn(column.name.clone()))
+                })
+                .collect()
+        } else {
+            Ok(header
+                .iter()
+                .enumerate()
+                .filter_map(|(index, name)| Some((index, Column::new(name.parse::<Field>().ok()?))))
+                .collect())
+        }
+    }
+}
+
+impl Reader<BufReader<File>> {
+    /// Creates a reader for the CSV file at the path.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use sbet::csv::Reader;
+    ///
+    /// let reader = Reader::from_path("infile.csv").unwrap();
+    /// ```
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Reader<BufReader<File>>> {
+        Ok(Reader::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+impl<R: BufRead> Iterator for Reader<R> {
+    type Item = Result<Point>;
+
+    fn next(&mut self) -> Option<Result<Point>> {
+        self.read_one().transpose()
+    }
+}
+
+/// Writes points as delimited text with a header line.
+///
+/// The header is written before the first point, or by [Writer::finish]
+/// if no points were written.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::csv::{Column, Writer};
+/// use sbet::{Field, Point};
+///
+/// let mut writer = Writer::with_columns(Vec::new(), vec![Column::new(Field::Time)]);
+/// writer.write_one(&Point { time: 1.5, ..Default::default() }).unwrap();
+/// assert_eq!(writer.finish().unwrap(), b"time\n1.5\n");
+/// ```
+#[derive(Debug)]
+pub struct Writer<W: Write> {
+    write: W,
+    columns: Vec<Column>,
+    delimiter: char,
+    precision: Option<usize>,
+    has_header: bool,
+    line: String,
+}
+
+impl<W: Write> Writer<W> {
+    /// Creates a writer for latitude and longitude in degrees, and altitude.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::csv::Writer;
+    ///
+    /// let writer = Writer::new(std::io::stdout());
+    /// ```
+    pub fn new(write: W) -> Writer<W> {
+        Writer::with_columns(
+            write,
+            vec![
+                Column::new(Field::Latitude),
+                Column::new(Field::Longitude),
+                Column::new(Field::Altitude),
+            ],
+        )
+    }
+
+    /// Creates a writer for these columns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::csv::{Column, Writer};
+    /// use sbet::Field;
+    ///
+    /// let columns = Field::ALL.into_iter().map(Column::new).collect();
+    /// let writer = Writer::with_columns(std::io::stdout(), columns);
+    /// ```
+    pub fn with_columns(write: W, columns: Vec<Column>) -> Writer<W> {
+        Writer {
+            write,
+            columns,
+            delimiter: ',',
+            precision: None,
+            has_header: false,
+            line: String::new(),
+        }
+    }
+
+    /// Returns this writer's columns.
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    /// Sets the delimiter between values, which is a comma by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::csv::Writer;
+    ///
+    /// let mut writer = Writer::new(Vec::new());
+    /// writer.set_delimiter('\t');
+    /// ```
+    pub fn set_delimiter(&mut self, delimiter: char) {
+        self.delimiter = delimiter;
+    }
+
+    /// Sets the number of digits written after the decimal point.
+    ///
+    /// By default, values are written with as many digits as are needed to
+    /// read them back exactly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::csv::{Column, Writer};
+    /// use sbet::{Field, Point};
+    ///
+    /// let mut writer = Writer::with_columns(Vec::new(), vec![Column::new(Field::Time)]);
+    /// writer.set_precision(Some(2));
+    /// writer.write_one(&Point { time: 1., ..Default::default() }).unwrap();
+    /// assert_eq!(writer.finish().unwrap(), b"time\n1.00\n");
+    /// ```
+    pub fn set_precision(&mut self, precision: Option<usize>) {
+        self.precision = precision;
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.write
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.write
+    }
+
+    /// Writes one point as a line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::csv::Writer;
+    /// use sbet::Point;
+    ///
+    /// let mut writer = Writer::new(std::io::stdout());
+    /// writer.write_one(&Point::default()).unwrap();
+    /// ```
+    pub fn write_one(&mut self, point: &Point) -> Result<()> {
+        self.write_header()?;
+        self.line.clear();
+        for (i, column) in self.columns.iter().enumerate() {
+            if i > 0 {
+                self.line.push(self.delimiter);
+            }
+            column.format(point, self.precision, &mut self.line)?;
+        }
+        self.line.push('\n');
+        self.write.write_all(self.line.as_bytes())?;
+        Ok(())
+    }
+
+    /// Writes many points, one line each.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::csv::Writer;
+    /// use sbet::Point;
+    ///
+    /// let mut writer = Writer::new(Vec::new());
+    /// writer.write_all(&[Point::default(); 2]).unwrap();
+    /// assert_eq!(writer.finish().unwrap().split(|&b| b == b'\n').count(), 4);
+    /// ```
+    pub fn write_all(&mut self, points: &[Point]) -> Result<()> {
+        for point in points {
+            self.write_one(point)?;
+        }
+        Ok(())
+    }
+
+    /// Flushes the underlying writer.
+    pub fn flush(&mut self) -> Result<()> {
+        self.write.flush()?;
+        Ok(())
+    }
+
+    /// Writes the header if it hasn't been written, then flushes and consumes this writer.
+    ///
+    /// Unlike dropping a [BufWriter], this reports errors from the final flush.
+    pub fn finish(mut self) -> Result<W> {
+        self.write_header()?;
+        self.flush()?;
+        Ok(self.write)
+    }
+
+    fn write_header(&mut self) -> Result<()> {
+        if !self.has_header {
+            let names: Vec<&str> = self
+                .columns
+                .iter()
+                .map(|column| column.name.as_str())
+                .collect();
+            writeln!(self.write, "{}", names.join(&self.delimiter.to_string()))?;
+            self.has_header = true;
+        }
+        Ok(())
+    }
+}
+
+impl Writer<BufWriter<File>> {
+    /// Creates a writer for the CSV file at the path.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use sbet::csv::Writer;
+    ///
+    /// let writer = Writer::from_path("outfile.csv").unwrap();
+    /// ```
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Writer<BufWriter<File>>> {
+        Ok(Writer::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Column, Reader, Units, Writer};
+    use crate::{Error, Field, Point};
+
+    #[test]
+    fn roundtrip() {
+        let point = Point {
+            time: 151631.25,
+            latitude: 0.5,
+            longitude: -2.,
+            altitude: 100.,
+            roll: 0.01,
+            ..Default::default()
+        };
+        let columns: Vec<Column> = Field::ALL.into_iter().map(Column::new).collect();
+        let mut writer = Writer::with_columns(Vec::new(), columns.clone());
+        writer.set_delimiter(';');
+        writer.write_one(&point).unwrap();
+        let csv = writer.finish().unwrap();
+        let mut reader = Reader::with_columns(&csv[..], columns);
+        reader.set_delimiter(';');
+        let read = reader.next().unwrap().unwrap();
+        assert!((read.latitude - point.latitude).abs() < 1e-15);
+        assert_eq!(read.roll, point.roll);
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn scale() {
+        let column = Column {
+            name: "HeightFt".to_string(),
+            scale: 0.3048,
+            ..Column::new(Field::Altitude)
+        };
+        let mut reader = Reader::with_columns("HeightFt\n100\n".as_bytes(), vec![column]);
+        assert_eq!(reader.next().unwrap().unwrap().altitude, 30.48);
+    }
+
+    #[test]
+    fn errors() {
+        assert!(matches!(
+            Reader::new(&b""[..]).read_one(),
+            Err(Error::EmptyCsv)
+        ));
+        let column = Column {
+            units: Units::Native,
+            ..Column::new(Field::Latitude)
+        };
+        assert!(matches!(
+            Reader::with_columns("time\n1\n".as_bytes(), vec![column]).read_one(),
+            Err(Error::MissingColumn(_))
+        ));
+        assert!(matches!(
+            Reader::new("time\n\nnope\n".as_bytes()).read_one(),
+            Err(Error::InvalidCsvValue { line: 3, .. })
+        ));
+    }
+}
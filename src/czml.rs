@@ -0,0 +1,287 @@
+//! CZML output for replaying trajectories in CesiumJS, behind the `chrono` feature.
+//!
+//! The document has a clock spanning the trajectory and one entity whose
+//! position and orientation are sampled at each point. Positions are
+//! longitude and latitude in degrees and ellipsoidal height. Orientations
+//! rotate a model's body axes (x forward, y left, z up) into the
+//! earth-fixed frame, from roll, pitch, and true heading.
+//!
+//! # Examples
+//!
+//! ```
+//! use sbet::czml::Builder;
+//! use sbet::Point;
+//!
+//! let points = [
+//!     Point { time: 396018., ..Default::default() },
+//!     Point { time: 396019., ..Default::default() },
+//! ];
+//! let czml = Builder::new(2264).to_string(&points).unwrap();
+//! assert!(czml.contains(r#""epoch":"2023-06-01T14:00:00Z""#));
+//! ```
+
+use crate::{
+    json::{write_number, write_string},
+    Error, Point, Result,
+};
+use chrono::SecondsFormat;
+use std::io::Write;
+
+/// Builds a CZML document from a slice of points.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Builder {
+    week: u32,
+    name: Option<String>,
+    model: Option<String>,
+    decimate: usize,
+}
+
+impl Builder {
+    /// Creates a builder for points with times in seconds-of-week of this GPS week.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::czml::Builder;
+    ///
+    /// let builder = Builder::new(2264);
+    /// ```
+    pub fn new(week: u32) -> Builder {
+        Builder {
+            week,
+            name: None,
+            model: None,
+            decimate: 1,
+        }
+    }
+
+    /// Sets the document and entity name.
+    pub fn name(mut self, name: &str) -> Builder {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Draws the entity with the glTF model at this URI.
+    ///
+    /// Without a model, the entity is drawn as a point.
+    pub fn model(mut self, uri: &str) -> Builder {
+        self.model = Some(uri.to_string());
+        self
+    }
+
+    /// Only uses every `n`th point, starting with the first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    pub fn decimate(mut self, n: usize) -> Builder {
+        assert!(n > 0, "decimation must be positive");
+        self.decimate = n;
+        self
+    }
+
+    /// Writes the CZML document for the points.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are no points, or a time can't be converted to UTC.
+    pub fn write<W: Write>(&self, points: &[Point], mut write: W) -> Result<()> {
+        let points: Vec<&Point> = points.iter().step_by(self.decimate).collect();
+        let (first, last) = match (points.first(), points.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Err(Error::NoPoints),
+        };
+        let utc = |time: f64| {
+            crate::time::to_utc(self.week, time)
+                .map(|utc| utc.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+                .ok_or(Error::TimeOutOfRange(time))
+        };
+        let (start, stop) = (utc(first.time)?, utc(last.time)?);
+        write!(write, r#"[{{"id":"document","#)?;
+        if let Some(name) = &self.name {
+            write!(write, r#""name":"#)?;
+            write_string(&mut write, name)?;
+            write!(write, ",")?;
+        }
+        write!(
+            write,
+            r#""version":"1.0","clock":{{"interval":"{start}/{stop}","currentTime":"{start}","multiplier":1}}}},"#
+        )?;
+        write!(
+            write,
+            r#"{{"id":"trajectory","availability":"{start}/{stop}","#
+        )?;
+        if let Some(name) = &self.name {
+            write!(write, r#""name":"#)?;
+            write_string(&mut write, name)?;
+            write!(write, ",")?;
+        }
+        write!(
+            write,
+            r#""position":{{"epoch":"{start}","cartographicDegrees":["#
+        )?;
+        for (i, point) in points.iter().enumerate() {
+            if i > 0 {
+                write!(write, ",")?;
+            }
+            for (j, value) in [
+                point.time - first.time,
+                point.longitude.to_degrees(),
+                point.latitude.to_degrees(),
+                point.altitude,
+            ]
+            .into_iter()
+            .enumerate()
+            {
+                if j > 0 {
+                    write!(write, ",")?;
+                }
+                write_number(&mut write, value)?;
+            }
+        }
+        write!(
+            write,
+            r#"]}},"orientation":{{"epoch":"{start}","unitQuaternion":["#
+        )?;
+        for (i, point) in points.iter().enumerate() {
+            if i > 0 {
+                write!(write, ",")?;
+            }
+            write_number(&mut write, point.time - first.time)?;
+            for value in orientation(point) {
+                write!(write, ",")?;
+                write_number(&mut write, value)?;
+            }
+        }
+        write!(write, "]}},")?;
+        match &self.model {
+            Some(uri) => {
+                write!(write, r#""model":{{"gltf":"#)?;
+                write_string(&mut write, uri)?;
+                write!(write, r#","minimumPixelSize":64}},"#)?;
+            }
+            None => write!(write, r#""point":{{"pixelSize":8}},"#)?,
+        }
+        writeln!(write, r#""path":{{"width":2,"leadTime":0}}}}]"#)?;
+        Ok(())
+    }
+
+    /// Returns the CZML document for the points as a string.
+    pub fn to_string(&self, points: &[Point]) -> Result<String> {
+        let mut bytes = Vec::new();
+        self.write(points, &mut bytes)?;
+        Ok(String::from_utf8(bytes).expect("CZML is valid UTF-8"))
+    }
+}
+
+/// Returns the unit quaternion, as `[x, y, z, w]`, that rotates a model's body axes into the earth-fixed frame.
+///
+/// A model's y and z axes point left and up, the opposite of the SBET body frame.
+fn orientation(point: &Point) -> [f64; 4] {
+    let mut rotation = point.rotation_body_to_ecef();
+    for row in &mut rotation {
+        row[1] = -row[1];
+        row[2] = -row[2];
+    }
+    to_quaternion(&rotation)
+}
+
+/// Converts a rotation matrix to a unit quaternion, as `[x, y, z, w]`.
+fn to_quaternion(m: &[[f64; 3]; 3]) -> [f64; 4] {
+    let trace = m[0][0] + m[1][1] + m[2][2];
+    if trace > 0. {
+        let s = 0.5 / (trace + 1.).sqrt();
+        [
+            (m[2][1] - m[1][2]) * s,
+            (m[0][2] - m[2][0]) * s,
+            (m[1][0] - m[0][1]) * s,
+            0.25 / s,
+        ]
+    } else if m[0][0] > m[1][1] && m[0][0] > m[2][2] {
+        let s = 2. * (1. + m[0][0] - m[1][1] - m[2][2]).sqrt();
+        [
+            0.25 * s,
+            (m[0][1] + m[1][0]) / s,
+            (m[0][2] + m[2][0]) / s,
+            (m[2][1] - m[1][2]) / s,
+        ]
+    } else if m[1][1] > m[2][2] {
+        let s = 2. * (1. + m[1][1] - m[0][0] - m[2][2]).sqrt();
+        [
+            (m[0][1] + m[1][0]) / s,
+            0.25 * s,
+            (m[1][2] + m[2][1]) / s,
+            (m[0][2] - m[2][0]) / s,
+        ]
+    } else {
+        let s = 2. * (1. + m[2][2] - m[0][0] - m[1][1]).sqrt();
+        [
+            (m[0][2] + m[2][0]) / s,
+            (m[1][2] + m[2][1]) / s,
+            0.25 * s,
+            (m[1][0] - m[0][1]) / s,
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{orientation, Builder};
+    use crate::Point;
+    use std::f64::consts::FRAC_PI_2;
+
+    /// Rotates a vector by a unit quaternion.
+    fn rotate(q: [f64; 4], v: [f64; 3]) -> [f64; 3] {
+        let [x, y, z, w] = q;
+        let t = [
+            2. * (y * v[2] - z * v[1]),
+            2. * (z * v[0] - x * v[2]),
+            2. * (x * v[1] - y * v[0]),
+        ];
+        [
+            v[0] + w * t[0] + y * t[2] - z * t[1],
+            v[1] + w * t[1] + z * t[0] - x * t[2],
+            v[2] + w * t[2] + x * t[1] - y * t[0],
+        ]
+    }
+
+    fn assert_close(a: [f64; 3], b: [f64; 3]) {
+        for (a, b) in a.into_iter().zip(b) {
+            assert!((a - b).abs() < 1e-12, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn level_heading_north() {
+        // At latitude and longitude zero, east is +y, north is +z, and up is +x.
+        let q = orientation(&Point::default());
+        assert_close(rotate(q, [1., 0., 0.]), [0., 0., 1.]);
+        assert_close(rotate(q, [0., 0., 1.]), [1., 0., 0.]);
+    }
+
+    #[test]
+    fn heading_east_pitched_up() {
+        let point = Point {
+            yaw: FRAC_PI_2,
+            pitch: 0.1,
+            ..Default::default()
+        };
+        let forward = rotate(orientation(&point), [1., 0., 0.]);
+        assert_close(forward, [0.1f64.sin(), 0.1f64.cos(), 0.]);
+    }
+
+    #[test]
+    fn roll_right_wing_down() {
+        let point = Point {
+            roll: 0.1,
+            ..Default::default()
+        };
+        let left = rotate(orientation(&point), [0., 1., 0.]);
+        assert_close(left, [0.1f64.sin(), -(0.1f64.cos()), 0.]);
+    }
+
+    #[test]
+    fn no_points() {
+        assert!(Builder::new(2264).to_string(&[]).is_err());
+    }
+}
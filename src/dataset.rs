@@ -0,0 +1,308 @@
+use crate::{Bounds, Error, Input, Point, Reader, Result, TimeWindow};
+use std::path::{Path, PathBuf};
+
+/// Several SBET files treated as one trajectory, e.g. a project delivered with one file per lift.
+///
+/// The files are ordered by time and must not overlap. They must be
+/// uncompressed, little-endian records, each sorted by time. Only the first
+/// and last records of each file are read when the dataset is created.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::Dataset;
+///
+/// let dataset = Dataset::from_paths(&["data/2-points.sbet"]).unwrap();
+/// assert_eq!(dataset.files().len(), 1);
+/// let points = dataset.points().collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(points.len(), 2);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Dataset {
+    files: Vec<DatasetFile>,
+}
+
+/// One file of a [Dataset], with the times of its first and last records.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DatasetFile {
+    /// The file path.
+    pub path: PathBuf,
+
+    /// The time of the first record.
+    pub start_time: f64,
+
+    /// The time of the last record.
+    pub end_time: f64,
+}
+
+/// Two files whose time ranges overlap, found by [overlaps].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Overlap {
+    /// The file that starts first.
+    pub first: PathBuf,
+
+    /// The file that starts second.
+    pub second: PathBuf,
+
+    /// The times covered by both files.
+    pub window: TimeWindow,
+}
+
+/// An iterator over the points of every file in a [Dataset], created by [Dataset::points].
+#[derive(Debug)]
+pub struct DatasetPoints<'a> {
+    files: std::slice::Iter<'a, DatasetFile>,
+    reader: Option<Reader<Input>>,
+}
+
+impl Dataset {
+    /// Creates a dataset from SBET files, in any order.
+    ///
+    /// Files without any whole records are left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns [Error::OverlappingFiles] if two files' time ranges overlap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Dataset;
+    ///
+    /// let paths = ["data/2-points.sbet", "data/2-points.sbet"];
+    /// assert!(Dataset::from_paths(&paths).is_err());
+    /// ```
+    pub fn from_paths<P: AsRef<Path>>(paths: &[P]) -> Result<Dataset> {
+        let files = read_files(paths)?;
+        if let Some(overlap) = find_overlaps(&files).next() {
+            return Err(Error::OverlappingFiles {
+                first: overlap.first,
+                second: overlap.second,
+            });
+        }
+        Ok(Dataset { files })
+    }
+
+    /// Returns the files, ordered by time.
+    pub fn files(&self) -> &[DatasetFile] {
+        &self.files
+    }
+
+    /// Returns the time from the first record of the first file to the last record of the last, or `None` if there are no files.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Dataset;
+    ///
+    /// let dataset = Dataset::from_paths(&["data/2-points.sbet"]).unwrap();
+    /// let window = dataset.time_window().unwrap();
+    /// assert!(window.duration() > 0.);
+    /// ```
+    pub fn time_window(&self) -> Option<TimeWindow> {
+        let first = self.files.first()?;
+        let last = self.files.last()?;
+        Some(TimeWindow::new(first.start_time, last.end_time))
+    }
+
+    /// Returns an iterator over the points of every file, in time order.
+    ///
+    /// Each file is opened when the iterator reaches it.
+    pub fn points(&self) -> DatasetPoints<'_> {
+        DatasetPoints {
+            files: self.files.iter(),
+            reader: None,
+        }
+    }
+
+    /// Reads every point to find the bounds, or `None` if there are no points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Dataset;
+    ///
+    /// let dataset = Dataset::from_paths(&["data/2-points.sbet"]).unwrap();
+    /// let bounds = dataset.bounds().unwrap().unwrap();
+    /// assert!(bounds.min_altitude <= bounds.max_altitude);
+    /// ```
+    pub fn bounds(&self) -> Result<Option<Bounds>> {
+        let mut bounds: Option<Bounds> = None;
+        for result in self.points() {
+            let point = result?;
+            bounds = Some(match bounds {
+                Some(bounds) => bounds.extend(&point),
+                None => Bounds::from_point(&point),
+            });
+        }
+        Ok(bounds)
+    }
+
+    /// Interpolates a point at a time, reading only a few records of the file that covers it.
+    ///
+    /// See [query_time](crate::query_time).
+    ///
+    /// # Errors
+    ///
+    /// Returns [Error::Extrapolation] if no file covers the time, including
+    /// when it falls in a gap between files.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Dataset;
+    ///
+    /// let dataset = Dataset::from_paths(&["data/2-points.sbet"]).unwrap();
+    /// let point = dataset.interpolate(151631.004).unwrap();
+    /// assert!(dataset.interpolate(0.).is_err());
+    /// ```
+    pub fn interpolate(&self, time: f64) -> Result<Point> {
+        let index = self
+            .files
+            .partition_point(|file| file.start_time <= time)
+            .saturating_sub(1);
+        let file = self.files.get(index).ok_or(Error::NoPoints)?;
+        if time < file.start_time || time > file.end_time {
+            return Err(Error::Extrapolation {
+                time,
+                start_time: file.start_time,
+                end_time: file.end_time,
+            });
+        }
+        crate::query_time(&file.path, time)
+    }
+}
+
+/// Returns every pair of SBET files whose time ranges overlap, and the times they share.
+///
+/// Use this to catch duplicated or mis-split deliveries before merging
+/// them or creating a [Dataset]. Only the first and last records of each
+/// file are read, so the files must be uncompressed, little-endian
+/// records, each sorted by time. Files without any whole records are left
+/// out. Pairs are ordered by the start times of their files.
+///
+/// # Examples
+///
+/// ```
+/// let paths = ["data/2-points.sbet", "data/2-points.sbet"];
+/// let overlaps = sbet::overlaps(&paths).unwrap();
+/// assert_eq!(overlaps.len(), 1);
+/// assert!(overlaps[0].window.duration() > 0.);
+/// ```
+pub fn overlaps<P: AsRef<Path>>(paths: &[P]) -> Result<Vec<Overlap>> {
+    let files = read_files(paths)?;
+    Ok(find_overlaps(&files).collect())
+}
+
+/// Reads the time ranges of the files, ordered by start time.
+fn read_files<P: AsRef<Path>>(paths: &[P]) -> Result<Vec<DatasetFile>> {
+    let mut files = Vec::with_capacity(paths.len());
+    for path in paths {
+        let path = path.as_ref();
+        match crate::time_bounds(path) {
+            Ok((start_time, end_time)) => files.push(DatasetFile {
+                path: path.to_path_buf(),
+                start_time,
+                end_time,
+            }),
+            Err(Error::NoPoints) => {}
+            Err(err) => return Err(err),
+        }
+    }
+    files.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+    Ok(files)
+}
+
+fn find_overlaps(files: &[DatasetFile]) -> impl Iterator<Item = Overlap> + '_ {
+    files.iter().enumerate().flat_map(move |(i, first)| {
+        files[i + 1..]
+            .iter()
+            .take_while(move |second| second.start_time <= first.end_time)
+            .map(move |second| Overlap {
+                first: first.path.clone(),
+                second: second.path.clone(),
+                window: TimeWindow::new(second.start_time, first.end_time.min(second.end_time)),
+            })
+    })
+}
+
+impl Iterator for DatasetPoints<'_> {
+    type Item = Result<Point>;
+
+    fn next(&mut self) -> Option<Result<Point>> {
+        loop {
+            if let Some(reader) = self.reader.as_mut() {
+                match reader.next() {
+                    Some(result) => return Some(result),
+                    None => self.reader = None,
+                }
+            }
+            let file = self.files.next()?;
+            match Reader::from_path(&file.path) {
+                Ok(reader) => self.reader = Some(reader),
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Dataset, TimeWindow};
+    use crate::{Error, Point, Writer};
+
+    #[test]
+    fn split() {
+        let directory = std::env::temp_dir().join(format!("sbet-dataset-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let write = |name: &str, times: &[f64]| {
+            let path = directory.join(name);
+            let mut writer = Writer::from_path(&path).unwrap();
+            for &time in times {
+                writer
+                    .write_one(Point {
+                        time,
+                        altitude: time,
+                        ..Default::default()
+                    })
+                    .unwrap();
+            }
+            writer.finish().unwrap();
+            path
+        };
+        let second = write("second.sbet", &[10., 11., 12.]);
+        let first = write("first.sbet", &[1., 2.]);
+        let empty = write("empty.sbet", &[]);
+        let dataset = Dataset::from_paths(&[second, first.clone(), empty]).unwrap();
+        assert_eq!(dataset.files().len(), 2);
+        assert_eq!(dataset.files()[0].path, first);
+
+        let times: Vec<f64> = dataset.points().map(|point| point.unwrap().time).collect();
+        assert_eq!(times, [1., 2., 10., 11., 12.]);
+        assert_eq!(dataset.bounds().unwrap().unwrap().max_altitude, 12.);
+        assert_eq!(dataset.interpolate(11.5).unwrap().altitude, 11.5);
+        assert!(matches!(
+            dataset.interpolate(5.).unwrap_err(),
+            Error::Extrapolation {
+                start_time: 1.,
+                end_time: 2.,
+                ..
+            }
+        ));
+
+        let overlapping = write("overlapping.sbet", &[2., 3.]);
+        assert!(matches!(
+            Dataset::from_paths(&[&first, &overlapping]).unwrap_err(),
+            Error::OverlappingFiles { .. }
+        ));
+        let inside = write("inside.sbet", &[1.5, 1.75]);
+        let overlaps = crate::overlaps(&[&first, &overlapping, &inside]).unwrap();
+        let windows: Vec<_> = overlaps.iter().map(|overlap| overlap.window).collect();
+        assert_eq!(
+            windows,
+            [TimeWindow::new(1.5, 1.75), TimeWindow::new(2., 2.)]
+        );
+        assert_eq!(overlaps[1].second, overlapping);
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,119 @@
+use crate::{Error, Field, Point, Result, Trajectory};
+use std::str::FromStr;
+
+/// What to do with consecutive points that have the same time.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::DuplicatePolicy;
+///
+/// let policy: DuplicatePolicy = "keep-last".parse().unwrap();
+/// assert_eq!(policy, DuplicatePolicy::KeepLast);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DuplicatePolicy {
+    /// Return [Error::DuplicateTime].
+    #[default]
+    Error,
+
+    /// Keep the first point with each time.
+    KeepFirst,
+
+    /// Keep the last point with each time.
+    KeepLast,
+
+    /// Replace the points with each time by the mean of each of their fields.
+    Average,
+}
+
+impl FromStr for DuplicatePolicy {
+    type Err = Error;
+
+    /// Parses "error", "keep-first", "keep-last", or "average".
+    fn from_str(s: &str) -> Result<DuplicatePolicy> {
+        match s {
+            "error" => Ok(DuplicatePolicy::Error),
+            "keep-first" => Ok(DuplicatePolicy::KeepFirst),
+            "keep-last" => Ok(DuplicatePolicy::KeepLast),
+            "average" => Ok(DuplicatePolicy::Average),
+            _ => Err(Error::UnknownDuplicatePolicy(s.to_string())),
+        }
+    }
+}
+
+impl Trajectory {
+    /// Returns a new trajectory where no two consecutive points have the same time.
+    ///
+    /// Only consecutive duplicates are found, so sort the points by time first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [Error::DuplicateTime] if there are duplicates and the policy is [DuplicatePolicy::Error].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{DuplicatePolicy, Point, Trajectory};
+    ///
+    /// let trajectory = Trajectory::from(vec![
+    ///     Point { time: 1., altitude: 10., ..Default::default() },
+    ///     Point { time: 1., altitude: 20., ..Default::default() },
+    ///     Point { time: 2., ..Default::default() },
+    /// ]);
+    /// assert!(trajectory.dedup(DuplicatePolicy::Error).is_err());
+    /// let deduped = trajectory.dedup(DuplicatePolicy::Average).unwrap();
+    /// assert_eq!(deduped.len(), 2);
+    /// assert_eq!(deduped.points()[0].altitude, 15.);
+    /// ```
+    pub fn dedup(&self, policy: DuplicatePolicy) -> Result<Trajectory> {
+        let mut points = Vec::with_capacity(self.len());
+        for run in self.points().chunk_by(|a, b| a.time == b.time) {
+            let point = match (run, policy) {
+                ([point], _) | ([point, ..], DuplicatePolicy::KeepFirst) => *point,
+                ([.., point], DuplicatePolicy::KeepLast) => *point,
+                (_, DuplicatePolicy::Average) => {
+                    let mut mean = Point::default();
+                    for field in Field::ALL {
+                        let sum: f64 = run.iter().map(|point| point.get(field)).sum();
+                        mean.set(field, sum / run.len() as f64);
+                    }
+                    mean
+                }
+                _ => return Err(Error::DuplicateTime(run[0].time)),
+            };
+            points.push(point);
+        }
+        Ok(points.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DuplicatePolicy, Point, Trajectory};
+
+    #[test]
+    fn keep_first_and_last() {
+        let trajectory: Trajectory = [(0., 1.), (1., 2.), (1., 3.), (1., 4.), (2., 5.)]
+            .into_iter()
+            .map(|(time, altitude)| Point {
+                time,
+                altitude,
+                ..Default::default()
+            })
+            .collect::<Vec<_>>()
+            .into();
+        let altitudes = |policy| -> Vec<f64> {
+            trajectory
+                .dedup(policy)
+                .unwrap()
+                .points()
+                .iter()
+                .map(|point| point.altitude)
+                .collect()
+        };
+        assert_eq!(altitudes(DuplicatePolicy::KeepFirst), [1., 2., 5.]);
+        assert_eq!(altitudes(DuplicatePolicy::KeepLast), [1., 4., 5.]);
+        assert_eq!(altitudes(DuplicatePolicy::Average), [1., 3., 5.]);
+    }
+}
@@ -0,0 +1,272 @@
+use crate::{Error, Point, Result};
+use std::{
+    fs::File,
+    io::{BufReader, Read, Seek},
+    path::Path,
+};
+use tiff::{
+    decoder::{Decoder, DecodingResult},
+    tags::Tag,
+    ColorType,
+};
+
+/// GeoTIFF key for the model type.
+const GT_MODEL_TYPE: u16 = 1024;
+
+/// GeoTIFF key for whether a pixel is an area or a point.
+const GT_RASTER_TYPE: u16 = 1025;
+
+/// Model type value for geographic (latitude and longitude) rasters.
+const MODEL_TYPE_GEOGRAPHIC: u16 = 2;
+
+/// Raster type value for pixels whose coordinates are their centers.
+const RASTER_PIXEL_IS_POINT: u16 = 2;
+
+/// A digital elevation model, for computing heights above ground.
+///
+/// Models are read from single-band GeoTIFFs in geographic coordinates,
+/// i.e. longitude and latitude in degrees. Cells equal to the GDAL nodata
+/// value are treated as missing. The model's heights must be in the same
+/// vertical datum as the altitudes they're compared against; most DEMs are
+/// orthometric, so use a [Geoid](crate::Geoid) to convert the trajectory first.
+///
+/// # Examples
+///
+/// ```no_run
+/// use sbet::{Dem, Point};
+///
+/// let dem = Dem::from_path("dem.tif").unwrap();
+/// let agl = dem.height_above_ground(&Point::default()).unwrap();
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Dem {
+    north: f64,
+    west: f64,
+    latitude_spacing: f64,
+    longitude_spacing: f64,
+    rows: usize,
+    columns: usize,
+    heights: Vec<f32>,
+    nodata: Option<f32>,
+}
+
+impl Dem {
+    /// Reads a GeoTIFF elevation model from a path.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use sbet::Dem;
+    ///
+    /// let dem = Dem::from_path("dem.tif").unwrap();
+    /// ```
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Dem> {
+        Dem::read(BufReader::new(File::open(path)?))
+    }
+
+    /// Reads a GeoTIFF elevation model.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use sbet::Dem;
+    /// use std::{fs::File, io::BufReader};
+    ///
+    /// let dem = Dem::read(BufReader::new(File::open("dem.tif").unwrap())).unwrap();
+    /// ```
+    pub fn read<R: Read + Seek>(read: R) -> Result<Dem> {
+        let mut decoder = Decoder::new(read)?;
+        if let ColorType::RGB(_) | ColorType::RGBA(_) | ColorType::CMYK(_) = decoder.colortype()? {
+            return Err(Error::InvalidDem("more than one band".to_string()));
+        }
+        let (columns, rows) = decoder.dimensions()?;
+        let (columns, rows) = (columns as usize, rows as usize);
+        if rows < 2 || columns < 2 {
+            return Err(Error::InvalidDem(format!(
+                "too small: {} rows, {} columns",
+                rows, columns
+            )));
+        }
+        let mut pixel_is_point = false;
+        if let Some(keys) = decoder.find_tag(Tag::GeoKeyDirectoryTag)? {
+            let keys = keys.into_u16_vec()?;
+            for key in keys.get(4..).unwrap_or_default().chunks_exact(4) {
+                match (key[0], key[3]) {
+                    (GT_MODEL_TYPE, model_type) if model_type != MODEL_TYPE_GEOGRAPHIC => {
+                        return Err(Error::InvalidDem(format!(
+                            "model type {} is not geographic",
+                            model_type
+                        )))
+                    }
+                    (GT_RASTER_TYPE, raster_type) => {
+                        pixel_is_point = raster_type == RASTER_PIXEL_IS_POINT
+                    }
+                    _ => {}
+                }
+            }
+        }
+        let scale = decoder
+            .find_tag(Tag::ModelPixelScaleTag)?
+            .map(|value| value.into_f64_vec())
+            .transpose()?
+            .filter(|scale| scale.len() >= 2 && scale[0] > 0. && scale[1] > 0.)
+            .ok_or_else(|| Error::InvalidDem("missing or invalid pixel scale".to_string()))?;
+        let tiepoint = decoder
+            .find_tag(Tag::ModelTiepointTag)?
+            .map(|value| value.into_f64_vec())
+            .transpose()?
+            .filter(|tiepoint| tiepoint.len() >= 6)
+            .ok_or_else(|| Error::InvalidDem("missing or invalid tiepoint".to_string()))?;
+        let nodata = decoder
+            .find_tag(Tag::GdalNodata)?
+            .map(|value| value.into_string())
+            .transpose()?
+            .and_then(|nodata| nodata.trim_end_matches('\0').trim().parse().ok());
+        // Heights are sampled at pixel centers, which are half a pixel in
+        // from the corner unless the raster says otherwise.
+        let center = if pixel_is_point { 0. } else { 0.5 };
+        let west = tiepoint[3] + (center - tiepoint[0]) * scale[0];
+        let north = tiepoint[4] - (center - tiepoint[1]) * scale[1];
+        let heights: Vec<f32> = match decoder.read_image()? {
+            DecodingResult::U8(values) => values.into_iter().map(f32::from).collect(),
+            DecodingResult::U16(values) => values.into_iter().map(f32::from).collect(),
+            DecodingResult::I8(values) => values.into_iter().map(f32::from).collect(),
+            DecodingResult::I16(values) => values.into_iter().map(f32::from).collect(),
+            DecodingResult::I32(values) => values.into_iter().map(|value| value as f32).collect(),
+            DecodingResult::F32(values) => values,
+            DecodingResult::F64(values) => values.into_iter().map(|value| value as f32).collect(),
+            _ => return Err(Error::InvalidDem("unsupported sample format".to_string())),
+        };
+        if heights.len() != rows * columns {
+            return Err(Error::InvalidDem("more than one band".to_string()));
+        }
+        Ok(Dem {
+            north,
+            west,
+            latitude_spacing: scale[1],
+            longitude_spacing: scale[0],
+            rows,
+            columns,
+            heights,
+            nodata,
+        })
+    }
+
+    /// Returns the ground height, in meters, at a latitude and longitude in radians.
+    ///
+    /// Heights are bilinearly interpolated between cell centers. Returns
+    /// `None` if the location is outside of the model or next to a cell
+    /// without data.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use sbet::Dem;
+    ///
+    /// let dem = Dem::from_path("dem.tif").unwrap();
+    /// let height = dem.height(0.7, -1.8).unwrap();
+    /// ```
+    pub fn height(&self, latitude: f64, longitude: f64) -> Option<f64> {
+        let row = (self.north - latitude.to_degrees()) / self.latitude_spacing;
+        let column = (longitude.to_degrees() - self.west) / self.longitude_spacing;
+        if !(0. ..=(self.rows - 1) as f64).contains(&row)
+            || !(0. ..=(self.columns - 1) as f64).contains(&column)
+        {
+            return None;
+        }
+        let (row0, column0) = (
+            (row.floor() as usize).min(self.rows - 2),
+            (column.floor() as usize).min(self.columns - 2),
+        );
+        let (row_factor, column_factor) = (row - row0 as f64, column - column0 as f64);
+        let value = |row: usize, column: usize| {
+            let value = self.heights[row * self.columns + column];
+            if value.is_nan() || Some(value) == self.nodata {
+                None
+            } else {
+                Some(f64::from(value))
+            }
+        };
+        let north = value(row0, column0)? * (1. - column_factor)
+            + value(row0, column0 + 1)? * column_factor;
+        let south = value(row0 + 1, column0)? * (1. - column_factor)
+            + value(row0 + 1, column0 + 1)? * column_factor;
+        Some(north * (1. - row_factor) + south * row_factor)
+    }
+
+    /// Returns a point's height above ground, i.e. its altitude minus the ground height.
+    ///
+    /// Returns `None` if the point is outside of the model.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use sbet::{Dem, Point};
+    ///
+    /// let dem = Dem::from_path("dem.tif").unwrap();
+    /// let agl = dem.height_above_ground(&Point::default()).unwrap();
+    /// ```
+    pub fn height_above_ground(&self, point: &Point) -> Option<f64> {
+        self.height(point.latitude, point.longitude)
+            .map(|height| point.altitude - height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Dem;
+    use crate::Point;
+    use std::io::Cursor;
+    use tiff::{
+        encoder::{colortype::Gray32Float, TiffEncoder},
+        tags::Tag,
+    };
+
+    fn dem(heights: &[f32]) -> Dem {
+        let mut bytes = Cursor::new(Vec::new());
+        let mut encoder = TiffEncoder::new(&mut bytes).unwrap();
+        let mut image = encoder.new_image::<Gray32Float>(3, 2).unwrap();
+        let directory = image.encoder();
+        directory
+            .write_tag(Tag::ModelPixelScaleTag, &[1f64, 1., 0.][..])
+            .unwrap();
+        directory
+            .write_tag(Tag::ModelTiepointTag, &[0f64, 0., 0., -10.5, 11.5, 0.][..])
+            .unwrap();
+        directory
+            .write_tag(Tag::GeoKeyDirectoryTag, &[1u16, 1, 0, 1, 1024, 0, 1, 2][..])
+            .unwrap();
+        directory.write_tag(Tag::GdalNodata, "-9999").unwrap();
+        image.write_data(heights).unwrap();
+        bytes.set_position(0);
+        Dem::read(bytes).unwrap()
+    }
+
+    #[test]
+    fn bilinear() {
+        let dem = dem(&[10., 12., 14., 0., 2., 4.]);
+        let height = dem
+            .height(10.5f64.to_radians(), (-9.5f64).to_radians())
+            .unwrap();
+        assert!((height - 6.).abs() < 1e-6);
+        let point = Point {
+            latitude: 11f64.to_radians(),
+            longitude: (-8f64).to_radians(),
+            altitude: 20.,
+            ..Default::default()
+        };
+        assert!((dem.height_above_ground(&point).unwrap() - 6.).abs() < 1e-6);
+    }
+
+    #[test]
+    fn outside_and_nodata() {
+        let dem = dem(&[10., 12., -9999., 0., 2., 4.]);
+        assert!(dem.height(0., 0.).is_none());
+        assert!(dem
+            .height(10.5f64.to_radians(), (-10f64).to_radians())
+            .is_some());
+        assert!(dem
+            .height(10.5f64.to_radians(), (-8.5f64).to_radians())
+            .is_none());
+    }
+}
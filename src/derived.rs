@@ -0,0 +1,363 @@
+use crate::{Error, Field, Point};
+use std::{f64::consts::TAU, str::FromStr};
+
+/// A value computed from the fields of a [Point].
+///
+/// # Examples
+///
+/// ```
+/// use sbet::{Derived, Point};
+///
+/// let point = Point { yaw: 0.5, wander_angle: 0.25, ..Default::default() };
+/// assert_eq!(point.derive(Derived::TrueHeading), 0.25);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Derived {
+    /// Yaw minus wander angle, in radians clockwise from true north. See [Point::true_heading].
+    TrueHeading,
+
+    /// Three-dimensional speed, in meters per second. See [Point::speed].
+    Speed,
+
+    /// Horizontal speed, in meters per second. See [Point::horizontal_speed].
+    HorizontalSpeed,
+
+    /// Course over ground, in radians clockwise from true north. See [Point::course].
+    Course,
+
+    /// Magnitude of the acceleration, in meters per second squared. See [Point::acceleration_magnitude].
+    AccelerationMagnitude,
+
+    /// Magnitude of the angular rate, in radians per second. See [Point::angular_rate_magnitude].
+    AngularRateMagnitude,
+}
+
+impl Derived {
+    /// All derived values.
+    pub const ALL: [Derived; 6] = [
+        Derived::TrueHeading,
+        Derived::Speed,
+        Derived::HorizontalSpeed,
+        Derived::Course,
+        Derived::AccelerationMagnitude,
+        Derived::AngularRateMagnitude,
+    ];
+
+    /// Returns this value's name, which matches the [Point] method name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Derived;
+    ///
+    /// assert_eq!(Derived::TrueHeading.name(), "true_heading");
+    /// ```
+    pub fn name(&self) -> &'static str {
+        match self {
+            Derived::TrueHeading => "true_heading",
+            Derived::Speed => "speed",
+            Derived::HorizontalSpeed => "horizontal_speed",
+            Derived::Course => "course",
+            Derived::AccelerationMagnitude => "acceleration_magnitude",
+            Derived::AngularRateMagnitude => "angular_rate_magnitude",
+        }
+    }
+
+    /// Returns true if this value is an angle in radians or an angular rate in radians per second.
+    pub fn is_angular(&self) -> bool {
+        matches!(
+            self,
+            Derived::TrueHeading | Derived::Course | Derived::AngularRateMagnitude
+        )
+    }
+}
+
+impl FromStr for Derived {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Derived, Error> {
+        Derived::ALL
+            .into_iter()
+            .find(|derived| derived.name() == s)
+            .ok_or_else(|| Error::UnknownField(s.to_string()))
+    }
+}
+
+/// A field of a [Point], or a value derived from its fields.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::{Point, Quantity};
+///
+/// let quantity: Quantity = "true_heading".parse().unwrap();
+/// assert_eq!(Point::default().quantity(quantity), 0.);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Quantity {
+    /// A field stored in the SBET record.
+    Field(Field),
+
+    /// A value computed from the fields.
+    Derived(Derived),
+}
+
+impl Quantity {
+    /// Returns the field or derived value's name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Field, Quantity};
+    ///
+    /// assert_eq!(Quantity::from(Field::Roll).name(), "roll");
+    /// ```
+    pub fn name(&self) -> &'static str {
+        match self {
+            Quantity::Field(field) => field.name(),
+            Quantity::Derived(derived) => derived.name(),
+        }
+    }
+
+    /// Returns true if this quantity is an angle in radians or an angular rate in radians per second.
+    pub fn is_angular(&self) -> bool {
+        match self {
+            Quantity::Field(field) => field.is_angular(),
+            Quantity::Derived(derived) => derived.is_angular(),
+        }
+    }
+
+    /// Returns true if this quantity is a velocity or speed in meters per second.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Derived, Field, Quantity};
+    ///
+    /// assert!(Quantity::from(Derived::Speed).is_speed());
+    /// assert!(Quantity::from(Field::XVelocity).is_speed());
+    /// assert!(!Quantity::from(Field::Altitude).is_speed());
+    /// ```
+    pub fn is_speed(&self) -> bool {
+        matches!(
+            self,
+            Quantity::Field(Field::XVelocity | Field::YVelocity | Field::ZVelocity)
+                | Quantity::Derived(Derived::Speed | Derived::HorizontalSpeed)
+        )
+    }
+}
+
+impl From<Field> for Quantity {
+    fn from(field: Field) -> Quantity {
+        Quantity::Field(field)
+    }
+}
+
+impl From<Derived> for Quantity {
+    fn from(derived: Derived) -> Quantity {
+        Quantity::Derived(derived)
+    }
+}
+
+impl FromStr for Quantity {
+    type Err = Error;
+
+    /// Parses a field name or a derived value name.
+    fn from_str(s: &str) -> Result<Quantity, Error> {
+        s.parse()
+            .map(Quantity::Field)
+            .or_else(|_| s.parse().map(Quantity::Derived))
+    }
+}
+
+impl Point {
+    /// Returns the heading from true north, in radians in `[0, 2π)`.
+    ///
+    /// SBET yaw is the platform heading in the wander-azimuth frame, not
+    /// from true north. Using yaw as the heading rotates results by the
+    /// wander angle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Point;
+    /// use std::f64::consts::PI;
+    ///
+    /// let point = Point { yaw: 0.5 * PI, wander_angle: PI, ..Default::default() };
+    /// assert_eq!(point.true_heading(), 1.5 * PI);
+    /// ```
+    pub fn true_heading(&self) -> f64 {
+        normalize(self.yaw - self.wander_angle)
+    }
+
+    /// Returns the magnitude of the velocity, in meters per second.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Point;
+    ///
+    /// let point = Point { x_velocity: 3., z_velocity: 4., ..Default::default() };
+    /// assert_eq!(point.speed(), 5.);
+    /// ```
+    pub fn speed(&self) -> f64 {
+        self.horizontal_speed().hypot(self.z_velocity)
+    }
+
+    /// Returns the magnitude of the horizontal velocity, or ground speed, in meters per second.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Point;
+    ///
+    /// let point = Point { x_velocity: 3., y_velocity: 4., z_velocity: 1., ..Default::default() };
+    /// assert_eq!(point.horizontal_speed(), 5.);
+    /// ```
+    pub fn horizontal_speed(&self) -> f64 {
+        self.x_velocity.hypot(self.y_velocity)
+    }
+
+    /// Returns the direction of the horizontal velocity from true north, in radians in `[0, 2π)`.
+    ///
+    /// Unlike [Point::true_heading], which is where the platform points,
+    /// this is where it moves. The course is zero when the point isn't moving.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Point;
+    /// use std::f64::consts::FRAC_PI_2;
+    ///
+    /// let point = Point { y_velocity: 10., ..Default::default() };
+    /// assert_eq!(point.course(), FRAC_PI_2);
+    /// ```
+    pub fn course(&self) -> f64 {
+        let [north, east, _] = self.velocity_ned();
+        normalize(east.atan2(north))
+    }
+
+    /// Returns the magnitude of the acceleration, in meters per second squared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Point;
+    ///
+    /// let point = Point { x_acceleration: 3., z_acceleration: 4., ..Default::default() };
+    /// assert_eq!(point.acceleration_magnitude(), 5.);
+    /// ```
+    pub fn acceleration_magnitude(&self) -> f64 {
+        self.x_acceleration
+            .hypot(self.y_acceleration)
+            .hypot(self.z_acceleration)
+    }
+
+    /// Returns the magnitude of the angular rate, in radians per second.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Point;
+    ///
+    /// let point = Point { y_angular_rate: 3., z_angular_rate: 4., ..Default::default() };
+    /// assert_eq!(point.angular_rate_magnitude(), 5.);
+    /// ```
+    pub fn angular_rate_magnitude(&self) -> f64 {
+        self.x_angular_rate
+            .hypot(self.y_angular_rate)
+            .hypot(self.z_angular_rate)
+    }
+
+    /// Returns a derived value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Derived, Point};
+    ///
+    /// let point = Point { yaw: 1., ..Default::default() };
+    /// assert_eq!(point.derive(Derived::TrueHeading), point.true_heading());
+    /// ```
+    pub fn derive(&self, derived: Derived) -> f64 {
+        match derived {
+            Derived::TrueHeading => self.true_heading(),
+            Derived::Speed => self.speed(),
+            Derived::HorizontalSpeed => self.horizontal_speed(),
+            Derived::Course => self.course(),
+            Derived::AccelerationMagnitude => self.acceleration_magnitude(),
+            Derived::AngularRateMagnitude => self.angular_rate_magnitude(),
+        }
+    }
+
+    /// Returns the value of a field or derived value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Field, Point};
+    ///
+    /// let point = Point { roll: 0.1, ..Default::default() };
+    /// assert_eq!(point.quantity(Field::Roll.into()), 0.1);
+    /// ```
+    pub fn quantity(&self, quantity: Quantity) -> f64 {
+        match quantity {
+            Quantity::Field(field) => self.get(field),
+            Quantity::Derived(derived) => self.derive(derived),
+        }
+    }
+}
+
+/// Wraps an angle into `[0, 2π)`.
+fn normalize(angle: f64) -> f64 {
+    let angle = angle % TAU;
+    if angle < 0. {
+        angle + TAU
+    } else {
+        angle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Derived, Field, Point, Quantity};
+    use std::f64::consts::TAU;
+
+    #[test]
+    fn true_heading_wraps() {
+        let point = Point {
+            yaw: 0.1,
+            wander_angle: 0.3,
+            ..Default::default()
+        };
+        assert!((point.true_heading() - (TAU - 0.2)).abs() < 1e-12);
+        let point = Point {
+            yaw: 3. * TAU + 0.1,
+            ..Default::default()
+        };
+        assert!((point.true_heading() - 0.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn course_removes_wander() {
+        let point = Point {
+            x_velocity: 1.,
+            wander_angle: 0.5,
+            ..Default::default()
+        };
+        assert!((point.course() - (TAU - 0.5)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn parse_quantity() {
+        assert_eq!(
+            "yaw".parse::<Quantity>().unwrap(),
+            Quantity::Field(Field::Yaw)
+        );
+        assert_eq!(
+            "true_heading".parse::<Quantity>().unwrap(),
+            Quantity::Derived(Derived::TrueHeading)
+        );
+        assert!("heading".parse::<Quantity>().is_err());
+    }
+}
@@ -0,0 +1,350 @@
+use crate::{
+    smooth::{wrap, wraps},
+    Field, Interpolator, Point,
+};
+
+/// The differences between two trajectories, matched record-by-record on time.
+///
+/// Created by [diff].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diff {
+    /// The number of records that were matched by time.
+    pub matched: usize,
+
+    /// The number of records in the first trajectory without a match in the second.
+    pub only_in_first: usize,
+
+    /// The number of records in the second trajectory without a match in the first.
+    pub only_in_second: usize,
+
+    /// Per-field differences over the matched records, in [Field::ALL] order.
+    pub fields: Vec<FieldDifference>,
+}
+
+/// Summary of the differences in one field across all matched records.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FieldDifference {
+    /// The field.
+    pub field: Field,
+
+    /// The maximum absolute difference.
+    ///
+    /// A NaN compared to anything but NaN is an infinite difference.
+    pub max: f64,
+
+    /// The root-mean-square difference.
+    pub rms: f64,
+}
+
+/// Compares two time-sorted trajectories record-by-record.
+///
+/// Records are matched when their times are within `tolerance` of each other.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::Reader;
+///
+/// let points = Reader::from_path("data/2-points.sbet")
+///     .unwrap()
+///     .into_points()
+///     .unwrap();
+/// let diff = sbet::diff(&points, &points, 1e-9);
+/// assert_eq!(diff.matched, 2);
+/// assert!(diff.is_within(1e-9));
+/// ```
+pub fn diff(first: &[Point], second: &[Point], tolerance: f64) -> Diff {
+    let mut sum_of_squares = [0.; 17];
+    let mut max = [0f64; 17];
+    let mut matched = 0;
+    let mut i = 0;
+    let mut j = 0;
+    while i < first.len() && j < second.len() {
+        let (a, b) = (&first[i], &second[j]);
+        if (a.time - b.time).abs() <= tolerance {
+            for (k, field) in Field::ALL.into_iter().enumerate() {
+                let difference = difference(a.get(field), b.get(field), false);
+                max[k] = max[k].max(difference);
+                sum_of_squares[k] += difference * difference;
+            }
+            matched += 1;
+            i += 1;
+            j += 1;
+        } else if a.time < b.time {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    let fields = Field::ALL
+        .into_iter()
+        .enumerate()
+        .map(|(k, field)| FieldDifference {
+            field,
+            max: max[k],
+            rms: if matched == 0 {
+                0.
+            } else {
+                (sum_of_squares[k] / matched as f64).sqrt()
+            },
+        })
+        .collect();
+    Diff {
+        matched,
+        only_in_first: first.len() - matched,
+        only_in_second: second.len() - matched,
+        fields,
+    }
+}
+
+/// The differences between two trajectories over their overlapping time range.
+///
+/// Created by [compare].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComparisonReport {
+    /// The number of points in the first trajectory that were compared.
+    pub compared: usize,
+
+    /// The start of the overlapping time range, or `None` if the trajectories don't overlap.
+    pub start_time: Option<f64>,
+
+    /// The end of the overlapping time range, or `None` if the trajectories don't overlap.
+    pub end_time: Option<f64>,
+
+    /// The maximum horizontal distance between positions, in meters.
+    pub horizontal_max: f64,
+
+    /// The root-mean-square horizontal distance between positions, in meters.
+    pub horizontal_rms: f64,
+
+    /// Per-field differences, in [Field::ALL] order, except time.
+    pub fields: Vec<FieldDifference>,
+}
+
+/// Compares two time-sorted trajectories over the time range they share.
+///
+/// Unlike [diff], points don't need to have the same times: the second
+/// trajectory is interpolated at the time of each point of the first
+/// trajectory in the overlap. Angle differences are wrapped, so a yaw of
+/// just under π compared to just over -π is a small difference. This
+/// validates reprocessing runs, which often have different sample times.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::{Field, Point};
+///
+/// let first = [
+///     Point { time: 0., altitude: 10., ..Default::default() },
+///     Point { time: 1., altitude: 10., ..Default::default() },
+/// ];
+/// let second = [
+///     Point { time: 0.5, altitude: 11., ..Default::default() },
+///     Point { time: 1.5, altitude: 13., ..Default::default() },
+/// ];
+/// let report = sbet::compare(&first, &second);
+/// assert_eq!(report.compared, 1);
+/// let altitude = report.fields.iter().find(|field| field.field == Field::Altitude).unwrap();
+/// assert_eq!(altitude.max, 2.);
+/// ```
+pub fn compare(first: &[Point], second: &[Point]) -> ComparisonReport {
+    let fields: Vec<Field> = Field::ALL.into_iter().skip(1).collect();
+    let mut sum_of_squares = vec![0.; fields.len()];
+    let mut max = vec![0f64; fields.len()];
+    let (mut horizontal_sum_of_squares, mut horizontal_max) = (0., 0f64);
+    let mut compared = 0;
+    let mut range: Option<(f64, f64)> = None;
+    if let Ok(interpolator) = Interpolator::new(second) {
+        for a in first {
+            let Ok(b) = interpolator.interpolate(a.time) else {
+                continue;
+            };
+            for (k, &field) in fields.iter().enumerate() {
+                let difference = difference(a.get(field), b.get(field), wraps(field));
+                max[k] = max[k].max(difference);
+                sum_of_squares[k] += difference * difference;
+            }
+            let ned = a.to_ned(&b);
+            let horizontal = ned.north.hypot(ned.east);
+            let horizontal = if horizontal.is_nan() {
+                f64::INFINITY
+            } else {
+                horizontal
+            };
+            horizontal_max = horizontal_max.max(horizontal);
+            horizontal_sum_of_squares += horizontal * horizontal;
+            range = Some(range.map_or((a.time, a.time), |(start, _)| (start, a.time)));
+            compared += 1;
+        }
+    }
+    let rms = |sum_of_squares: f64| {
+        if compared == 0 {
+            0.
+        } else {
+            (sum_of_squares / compared as f64).sqrt()
+        }
+    };
+    ComparisonReport {
+        compared,
+        start_time: range.map(|(start, _)| start),
+        end_time: range.map(|(_, end)| end),
+        horizontal_max,
+        horizontal_rms: rms(horizontal_sum_of_squares),
+        fields: fields
+            .into_iter()
+            .enumerate()
+            .map(|(k, field)| FieldDifference {
+                field,
+                max: max[k],
+                rms: rms(sum_of_squares[k]),
+            })
+            .collect(),
+    }
+}
+
+/// Returns the absolute difference between two values, optionally wrapped to `[-π, π)`.
+///
+/// Equal values, including two NaNs, don't differ, and a NaN compared to
+/// anything else is infinitely different, so it can't pass a tolerance check.
+fn difference(a: f64, b: f64, wrapped: bool) -> f64 {
+    if a == b || (a.is_nan() && b.is_nan()) {
+        return 0.;
+    }
+    let difference = if wrapped { wrap(a - b) } else { a - b }.abs();
+    if difference.is_nan() {
+        f64::INFINITY
+    } else {
+        difference
+    }
+}
+
+impl Diff {
+    /// Returns true if every record was matched and no field differs by more than `tolerance`.
+    ///
+    /// A field whose root-mean-square difference isn't finite is never within tolerance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Point;
+    ///
+    /// let first = [Point::default()];
+    /// let second = [Point { altitude: 1., ..Default::default() }];
+    /// assert!(!sbet::diff(&first, &second, 1e-9).is_within(1e-9));
+    /// ```
+    pub fn is_within(&self, tolerance: f64) -> bool {
+        self.only_in_first == 0
+            && self.only_in_second == 0
+            && self
+                .fields
+                .iter()
+                .all(|field| field.max <= tolerance && field.rms.is_finite())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Field, Point};
+
+    #[test]
+    fn unmatched() {
+        let first = [
+            Point {
+                time: 1.,
+                ..Default::default()
+            },
+            Point {
+                time: 2.,
+                altitude: 1.,
+                ..Default::default()
+            },
+        ];
+        let second = [
+            Point {
+                time: 2.,
+                altitude: 4.,
+                ..Default::default()
+            },
+            Point {
+                time: 3.,
+                ..Default::default()
+            },
+        ];
+        let diff = super::diff(&first, &second, 1e-9);
+        assert_eq!(diff.matched, 1);
+        assert_eq!(diff.only_in_first, 1);
+        assert_eq!(diff.only_in_second, 1);
+        let altitude = diff
+            .fields
+            .iter()
+            .find(|field| field.field == Field::Altitude)
+            .unwrap();
+        assert_eq!(altitude.max, 3.);
+        assert_eq!(altitude.rms, 3.);
+    }
+
+    #[test]
+    fn compare_horizontal() {
+        let first: Vec<Point> = (0..3)
+            .map(|i| Point {
+                time: f64::from(i),
+                latitude: 1e-6,
+                ..Default::default()
+            })
+            .collect();
+        let second = [
+            Point {
+                time: 0.5,
+                ..Default::default()
+            },
+            Point {
+                time: 5.,
+                ..Default::default()
+            },
+        ];
+        let report = super::compare(&first, &second);
+        assert_eq!(report.compared, 2);
+        assert_eq!(report.start_time, Some(1.));
+        assert_eq!(report.end_time, Some(2.));
+        assert!((report.horizontal_max - 6.3).abs() < 0.1);
+        assert_eq!(super::compare(&first, &[]).compared, 0);
+    }
+
+    #[test]
+    fn nan_differs() {
+        let first = [Point {
+            altitude: f64::NAN,
+            roll: f64::NAN,
+            ..Default::default()
+        }];
+        let second = [Point {
+            roll: f64::NAN,
+            ..Default::default()
+        }];
+        let diff = super::diff(&first, &second, 1e-9);
+        assert!(!diff.is_within(1e-9));
+        let max = |fields: &[super::FieldDifference], field| {
+            fields.iter().find(|f| f.field == field).unwrap().max
+        };
+        assert_eq!(max(&diff.fields, Field::Altitude), f64::INFINITY);
+        assert_eq!(max(&diff.fields, Field::Roll), 0.);
+
+        let first = [
+            first[0],
+            Point {
+                time: 1.,
+                altitude: f64::NAN,
+                ..Default::default()
+            },
+        ];
+        let second = [
+            second[0],
+            Point {
+                time: 1.,
+                ..Default::default()
+            },
+        ];
+        let report = super::compare(&first, &second);
+        assert_eq!(max(&report.fields, Field::Altitude), f64::INFINITY);
+    }
+}
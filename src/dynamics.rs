@@ -0,0 +1,101 @@
+use crate::{Point, TimeWindow, Trajectory};
+
+/// Thresholds above which a point is high-dynamics, like a turn or turbulence.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::DynamicsLimits;
+///
+/// let limits = DynamicsLimits { padding: 5., ..Default::default() };
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DynamicsLimits {
+    /// The largest absolute roll, in radians.
+    pub roll: f64,
+
+    /// The largest [angular rate magnitude](crate::Point::angular_rate_magnitude), in radians per second.
+    pub angular_rate: f64,
+
+    /// Seconds added before and after each window, so the settling around a turn is included.
+    pub padding: f64,
+}
+
+impl Default for DynamicsLimits {
+    /// Ten degrees of roll, ten degrees per second, and no padding.
+    fn default() -> DynamicsLimits {
+        DynamicsLimits {
+            roll: 10f64.to_radians(),
+            angular_rate: 10f64.to_radians(),
+            padding: 0.,
+        }
+    }
+}
+
+impl Trajectory {
+    /// Returns the time windows where the roll or angular rate exceeds the limits.
+    ///
+    /// Each window spans a run of consecutive high-dynamics points, widened
+    /// by the padding on each side. Overlapping windows are merged. Use the
+    /// windows to exclude turns and turbulence from calibration datasets.
+    /// The points must be sorted by time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{DynamicsLimits, Point, TimeWindow, Trajectory};
+    ///
+    /// let trajectory: Trajectory = (0..10)
+    ///     .map(|i| Point {
+    ///         time: f64::from(i),
+    ///         roll: if (3..5).contains(&i) { 0.5 } else { 0. },
+    ///         ..Default::default()
+    ///     })
+    ///     .collect::<Vec<_>>()
+    ///     .into();
+    /// let windows = trajectory.high_dynamics(DynamicsLimits { padding: 1., ..Default::default() });
+    /// assert_eq!(windows, [TimeWindow::new(2., 5.)]);
+    /// ```
+    pub fn high_dynamics(&self, limits: DynamicsLimits) -> Vec<TimeWindow> {
+        let exceeds = |point: &Point| {
+            point.roll.abs() > limits.roll || point.angular_rate_magnitude() > limits.angular_rate
+        };
+        let mut windows: Vec<TimeWindow> = Vec::new();
+        for run in self.points().chunk_by(|a, b| exceeds(a) == exceeds(b)) {
+            let (first, last) = (&run[0], &run[run.len() - 1]);
+            if !exceeds(first) {
+                continue;
+            }
+            let window = TimeWindow::new(first.time - limits.padding, last.time + limits.padding);
+            match windows.last_mut() {
+                Some(previous) if previous.stop >= window.start => previous.stop = window.stop,
+                _ => windows.push(window),
+            }
+        }
+        windows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DynamicsLimits, Point, TimeWindow, Trajectory};
+
+    #[test]
+    fn merges_padded_windows() {
+        let trajectory: Trajectory = (0..10)
+            .map(|i| Point {
+                time: f64::from(i),
+                z_angular_rate: if i == 2 || i == 5 { 1. } else { 0. },
+                ..Default::default()
+            })
+            .collect::<Vec<_>>()
+            .into();
+        let windows = trajectory.high_dynamics(DynamicsLimits::default());
+        assert_eq!(windows, [TimeWindow::new(2., 2.), TimeWindow::new(5., 5.)]);
+        let windows = trajectory.high_dynamics(DynamicsLimits {
+            padding: 1.5,
+            ..Default::default()
+        });
+        assert_eq!(windows, [TimeWindow::new(0.5, 6.5)]);
+    }
+}
@@ -0,0 +1,208 @@
+use crate::{Error, Field, Point, RECORD_SIZE};
+use alloc::string::ToString;
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+use core::{fmt, str::FromStr};
+
+/// The byte order of SBET records.
+///
+/// SBET files are little-endian, but some legacy toolchains write big-endian records.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::{Endianness, Point};
+///
+/// let point = Point { time: 1., ..Default::default() };
+/// let bytes = Endianness::Big.encode(&point);
+/// assert_eq!(bytes[..8], 1f64.to_be_bytes());
+/// assert_eq!(Endianness::Big.decode(&bytes), point);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Endianness {
+    /// Little-endian, the standard SBET byte order.
+    #[default]
+    Little,
+
+    /// Big-endian.
+    Big,
+}
+
+/// The largest plausible absolute value of each field, in [Field::ALL] order.
+const PLAUSIBLE_MAXIMUMS: [f64; 17] = [
+    1e10,
+    core::f64::consts::FRAC_PI_2 + 1e-9,
+    core::f64::consts::TAU + 1e-9,
+    1e6,
+    1e4,
+    1e4,
+    1e4,
+    core::f64::consts::TAU + 1e-9,
+    core::f64::consts::TAU + 1e-9,
+    core::f64::consts::TAU + 1e-9,
+    core::f64::consts::TAU + 1e-9,
+    1e4,
+    1e4,
+    1e4,
+    1e3,
+    1e3,
+    1e3,
+];
+
+/// Nonzero values smaller than this are treated as byte-swapped noise.
+const PLAUSIBLE_MINIMUM: f64 = 1e-30;
+
+impl Endianness {
+    /// Guesses the byte order of the whole records in a buffer.
+    ///
+    /// Each record is decoded both ways and each field is checked against a
+    /// plausible range, e.g. latitude within ±90° and finite, non-negligible values.
+    /// Values decoded in the wrong byte order are usually wildly out of
+    /// range or vanishingly small. Returns `None` if the buffer holds no
+    /// whole records or neither order is more plausible, e.g. if every
+    /// value is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Endianness;
+    ///
+    /// let bytes = std::fs::read("data/2-points.sbet").unwrap();
+    /// assert_eq!(Endianness::detect(&bytes), Some(Endianness::Little));
+    /// assert_eq!(Endianness::detect(&[]), None);
+    /// ```
+    pub fn detect(bytes: &[u8]) -> Option<Endianness> {
+        let mut little = 0;
+        let mut big = 0;
+        for chunk in bytes.chunks_exact(RECORD_SIZE) {
+            let chunk = chunk.try_into().expect("chunks are record sized");
+            little += Endianness::Little.plausibility(chunk);
+            big += Endianness::Big.plausibility(chunk);
+        }
+        match little.cmp(&big) {
+            core::cmp::Ordering::Greater => Some(Endianness::Little),
+            core::cmp::Ordering::Less => Some(Endianness::Big),
+            core::cmp::Ordering::Equal => None,
+        }
+    }
+
+    fn plausibility(self, bytes: &[u8; RECORD_SIZE]) -> usize {
+        plausible_fields(&self.decode(bytes))
+    }
+
+    /// Decodes a point from one record in this byte order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Endianness, Point};
+    ///
+    /// let point = Endianness::Little.decode(&[0; sbet::RECORD_SIZE]);
+    /// assert_eq!(point, Point::default());
+    /// ```
+    pub fn decode(self, bytes: &[u8; RECORD_SIZE]) -> Point {
+        let mut point = Point::default();
+        for (field, chunk) in Field::ALL.into_iter().zip(bytes.chunks_exact(8)) {
+            let value = match self {
+                Endianness::Little => LittleEndian::read_f64(chunk),
+                Endianness::Big => BigEndian::read_f64(chunk),
+            };
+            point.set(field, value);
+        }
+        point
+    }
+
+    /// Encodes a point as one record in this byte order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Endianness, Point};
+    ///
+    /// let bytes = Endianness::Little.encode(&Point::default());
+    /// assert_eq!(bytes, [0; sbet::RECORD_SIZE]);
+    /// ```
+    pub fn encode(self, point: &Point) -> [u8; RECORD_SIZE] {
+        let mut bytes = [0; RECORD_SIZE];
+        for (field, chunk) in Field::ALL.into_iter().zip(bytes.chunks_exact_mut(8)) {
+            match self {
+                Endianness::Little => LittleEndian::write_f64(chunk, point.get(field)),
+                Endianness::Big => BigEndian::write_f64(chunk, point.get(field)),
+            }
+        }
+        bytes
+    }
+}
+
+impl FromStr for Endianness {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Endianness, Error> {
+        match s {
+            "little" => Ok(Endianness::Little),
+            "big" => Ok(Endianness::Big),
+            _ => Err(Error::UnknownEndianness(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Endianness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Endianness::Little => f.write_str("little"),
+            Endianness::Big => f.write_str("big"),
+        }
+    }
+}
+
+/// Returns true if every field of a point is within its plausible range.
+#[cfg(feature = "std")]
+pub(crate) fn is_plausible(point: &Point) -> bool {
+    plausible_fields(point) == Field::ALL.len()
+}
+
+fn plausible_fields(point: &Point) -> usize {
+    Field::ALL
+        .into_iter()
+        .zip(PLAUSIBLE_MAXIMUMS)
+        .filter(|&(field, maximum)| {
+            let value = point.get(field);
+            let magnitude = if value < 0. { -value } else { value };
+            value.is_finite()
+                && magnitude <= maximum
+                && (value == 0. || magnitude >= PLAUSIBLE_MINIMUM)
+        })
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Endianness;
+    use crate::Point;
+
+    #[test]
+    fn swap() {
+        let point = Point {
+            time: 151631.,
+            latitude: 0.5,
+            ..Default::default()
+        };
+        let mut bytes = Endianness::Little.encode(&point);
+        for chunk in bytes.chunks_exact_mut(8) {
+            chunk.reverse();
+        }
+        assert_eq!(Endianness::Big.decode(&bytes), point);
+        assert_eq!("big".parse::<Endianness>().unwrap(), Endianness::Big);
+    }
+
+    #[test]
+    fn detect() {
+        let bytes = std::fs::read("data/2-points.sbet").unwrap();
+        let mut swapped = bytes.clone();
+        for chunk in swapped.chunks_exact_mut(8) {
+            chunk.reverse();
+        }
+        assert_eq!(Endianness::detect(&bytes), Some(Endianness::Little));
+        assert_eq!(Endianness::detect(&swapped), Some(Endianness::Big));
+        assert_eq!(Endianness::detect(&[0; 136]), None);
+    }
+}
@@ -0,0 +1,285 @@
+use crate::{Error, Point};
+use alloc::string::ToString;
+use core::{fmt, str::FromStr};
+
+/// The names of the fields of an SBET [Point], in record order.
+///
+/// Each name matches the [Point] member name and [Field::name].
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(sbet::FIELD_NAMES[3], "altitude");
+/// ```
+pub const FIELD_NAMES: &[&str] = &[
+    "time",
+    "latitude",
+    "longitude",
+    "altitude",
+    "x_velocity",
+    "y_velocity",
+    "z_velocity",
+    "roll",
+    "pitch",
+    "yaw",
+    "wander_angle",
+    "x_acceleration",
+    "y_acceleration",
+    "z_acceleration",
+    "x_angular_rate",
+    "y_angular_rate",
+    "z_angular_rate",
+];
+
+/// One of the seventeen fields of an SBET [Point], in record order.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::{Field, Point};
+///
+/// let point = Point { altitude: 42., ..Default::default() };
+/// assert_eq!(point.get(Field::Altitude), 42.);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[allow(missing_docs)]
+pub enum Field {
+    Time,
+    Latitude,
+    Longitude,
+    Altitude,
+    XVelocity,
+    YVelocity,
+    ZVelocity,
+    Roll,
+    Pitch,
+    Yaw,
+    WanderAngle,
+    XAcceleration,
+    YAcceleration,
+    ZAcceleration,
+    XAngularRate,
+    YAngularRate,
+    ZAngularRate,
+}
+
+impl Field {
+    /// All fields, in the order they are stored in an SBET record.
+    pub const ALL: [Field; 17] = [
+        Field::Time,
+        Field::Latitude,
+        Field::Longitude,
+        Field::Altitude,
+        Field::XVelocity,
+        Field::YVelocity,
+        Field::ZVelocity,
+        Field::Roll,
+        Field::Pitch,
+        Field::Yaw,
+        Field::WanderAngle,
+        Field::XAcceleration,
+        Field::YAcceleration,
+        Field::ZAcceleration,
+        Field::XAngularRate,
+        Field::YAngularRate,
+        Field::ZAngularRate,
+    ];
+
+    /// Returns this field's name, which matches the [Point] member name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Field;
+    ///
+    /// assert_eq!(Field::XVelocity.name(), "x_velocity");
+    /// ```
+    pub fn name(&self) -> &'static str {
+        FIELD_NAMES[*self as usize]
+    }
+
+    /// Returns true if this field is an angle in radians or an angular rate in radians per second.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Field;
+    ///
+    /// assert!(Field::Roll.is_angular());
+    /// assert!(Field::XAngularRate.is_angular());
+    /// assert!(!Field::Altitude.is_angular());
+    /// ```
+    pub fn is_angular(&self) -> bool {
+        matches!(
+            self,
+            Field::Latitude
+                | Field::Longitude
+                | Field::Roll
+                | Field::Pitch
+                | Field::Yaw
+                | Field::WanderAngle
+                | Field::XAngularRate
+                | Field::YAngularRate
+                | Field::ZAngularRate
+        )
+    }
+}
+
+impl FromStr for Field {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Field, Error> {
+        Field::ALL
+            .into_iter()
+            .find(|field| field.name() == s)
+            .ok_or_else(|| Error::UnknownField(s.to_string()))
+    }
+}
+
+impl Point {
+    /// Returns the value of a field by name, or `None` if there's no field with that name.
+    ///
+    /// Names are those in [FIELD_NAMES].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Point;
+    ///
+    /// let point = Point { roll: 0.1, ..Default::default() };
+    /// assert_eq!(point.field("roll"), Some(0.1));
+    /// assert_eq!(point.field("heading"), None);
+    /// ```
+    pub fn field(&self, name: &str) -> Option<f64> {
+        name.parse().ok().map(|field| self.get(field))
+    }
+
+    /// Returns the value of a field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Field, Point};
+    ///
+    /// let point = Point { roll: 0.1, ..Default::default() };
+    /// assert_eq!(point.get(Field::Roll), 0.1);
+    /// ```
+    pub fn get(&self, field: Field) -> f64 {
+        match field {
+            Field::Time => self.time,
+            Field::Latitude => self.latitude,
+            Field::Longitude => self.longitude,
+            Field::Altitude => self.altitude,
+            Field::XVelocity => self.x_velocity,
+            Field::YVelocity => self.y_velocity,
+            Field::ZVelocity => self.z_velocity,
+            Field::Roll => self.roll,
+            Field::Pitch => self.pitch,
+            Field::Yaw => self.yaw,
+            Field::WanderAngle => self.wander_angle,
+            Field::XAcceleration => self.x_acceleration,
+            Field::YAcceleration => self.y_acceleration,
+            Field::ZAcceleration => self.z_acceleration,
+            Field::XAngularRate => self.x_angular_rate,
+            Field::YAngularRate => self.y_angular_rate,
+            Field::ZAngularRate => self.z_angular_rate,
+        }
+    }
+
+    /// Sets the value of a field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Field, Point};
+    ///
+    /// let mut point = Point::default();
+    /// point.set(Field::Pitch, 0.2);
+    /// assert_eq!(point.pitch, 0.2);
+    /// ```
+    pub fn set(&mut self, field: Field, value: f64) {
+        let member = match field {
+            Field::Time => &mut self.time,
+            Field::Latitude => &mut self.latitude,
+            Field::Longitude => &mut self.longitude,
+            Field::Altitude => &mut self.altitude,
+            Field::XVelocity => &mut self.x_velocity,
+            Field::YVelocity => &mut self.y_velocity,
+            Field::ZVelocity => &mut self.z_velocity,
+            Field::Roll => &mut self.roll,
+            Field::Pitch => &mut self.pitch,
+            Field::Yaw => &mut self.yaw,
+            Field::WanderAngle => &mut self.wander_angle,
+            Field::XAcceleration => &mut self.x_acceleration,
+            Field::YAcceleration => &mut self.y_acceleration,
+            Field::ZAcceleration => &mut self.z_acceleration,
+            Field::XAngularRate => &mut self.x_angular_rate,
+            Field::YAngularRate => &mut self.y_angular_rate,
+            Field::ZAngularRate => &mut self.z_angular_rate,
+        };
+        *member = value;
+    }
+}
+
+impl fmt::Display for Point {
+    /// Writes one labeled line per field, with units.
+    ///
+    /// Angles and angular rates are in degrees; use the alternate flag
+    /// (`{:#}`) for radians. A precision sets the number of decimal places.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Point;
+    ///
+    /// let point = Point::builder().time(151631.).latitude_degrees(45.).build();
+    /// let text = format!("{:.3}", point);
+    /// assert!(text.starts_with("          time: 151631.000 s\n      latitude: 45.000 °\n"));
+    /// assert!(format!("{:#.3}", point).contains("latitude: 0.785 rad\n"));
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let degrees = !f.alternate();
+        for field in Field::ALL {
+            let mut value = self.get(field);
+            if degrees && field.is_angular() {
+                value = value.to_degrees();
+            }
+            let unit = match field {
+                Field::Time => "s",
+                Field::Altitude => "m",
+                Field::XVelocity | Field::YVelocity | Field::ZVelocity => "m/s",
+                Field::XAcceleration | Field::YAcceleration | Field::ZAcceleration => "m/s²",
+                Field::XAngularRate | Field::YAngularRate | Field::ZAngularRate if degrees => "°/s",
+                Field::XAngularRate | Field::YAngularRate | Field::ZAngularRate => "rad/s",
+                _ if degrees => "°",
+                _ => "rad",
+            };
+            write!(f, "{:>14}: ", field.name())?;
+            match f.precision() {
+                Some(precision) => write!(f, "{:.*}", precision, value)?,
+                None => write!(f, "{}", value)?,
+            }
+            writeln!(f, " {}", unit)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Field, FIELD_NAMES};
+    use crate::Point;
+
+    #[test]
+    fn names_in_record_order() {
+        let names: Vec<&str> = Field::ALL.iter().map(|field| field.name()).collect();
+        assert_eq!(names, FIELD_NAMES);
+        let mut point = Point::default();
+        for (i, field) in Field::ALL.into_iter().enumerate() {
+            point.set(field, i as f64);
+        }
+        for (i, name) in FIELD_NAMES.iter().enumerate() {
+            assert_eq!(point.field(name), Some(i as f64));
+        }
+    }
+}
@@ -0,0 +1,81 @@
+//! Conversions to [geo_types] geometries, behind the `geo` feature.
+//!
+//! Coordinates are longitude (x) and latitude (y) in degrees. Altitude is dropped.
+
+use crate::Point;
+#[cfg(feature = "std")]
+use crate::Trajectory;
+use geo_types::Coord;
+#[cfg(feature = "std")]
+use geo_types::LineString;
+
+impl From<&Point> for Coord<f64> {
+    /// Converts a point's position to a coordinate in degrees.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types::Coord;
+    /// use sbet::Point;
+    ///
+    /// let point = Point::builder().latitude_degrees(45.).longitude_degrees(-120.).build();
+    /// let coord = Coord::from(&point);
+    /// assert!((coord.x + 120.).abs() < 1e-12);
+    /// assert!((coord.y - 45.).abs() < 1e-12);
+    /// ```
+    fn from(point: &Point) -> Coord<f64> {
+        Coord {
+            x: point.longitude.to_degrees(),
+            y: point.latitude.to_degrees(),
+        }
+    }
+}
+
+impl From<&Point> for geo_types::Point<f64> {
+    /// Converts a point's position to a geo-types point in degrees.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Point;
+    ///
+    /// let point = Point::builder().latitude_degrees(45.).build();
+    /// let point = geo_types::Point::from(&point);
+    /// assert!((point.y() - 45.).abs() < 1e-12);
+    /// ```
+    fn from(point: &Point) -> geo_types::Point<f64> {
+        geo_types::Point(point.into())
+    }
+}
+
+impl From<Point> for geo_types::Point<f64> {
+    fn from(point: Point) -> geo_types::Point<f64> {
+        (&point).into()
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<&Trajectory> for LineString<f64> {
+    /// Converts a trajectory to a line string through each point, in degrees.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types::LineString;
+    /// use sbet::Trajectory;
+    ///
+    /// let trajectory = Trajectory::from_path("data/2-points.sbet").unwrap();
+    /// let line_string = LineString::from(&trajectory);
+    /// assert_eq!(line_string.0.len(), 2);
+    /// ```
+    fn from(trajectory: &Trajectory) -> LineString<f64> {
+        trajectory.points().iter().map(Coord::from).collect()
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<Trajectory> for LineString<f64> {
+    fn from(trajectory: Trajectory) -> LineString<f64> {
+        (&trajectory).into()
+    }
+}
@@ -0,0 +1,418 @@
+use crate::Point;
+
+/// The WGS84 semi-major axis, in meters.
+pub(crate) const WGS84_A: f64 = 6378137.;
+
+/// The WGS84 flattening.
+pub(crate) const WGS84_F: f64 = 1. / 298.257223563;
+
+/// The WGS84 first eccentricity squared.
+pub(crate) const WGS84_E2: f64 = WGS84_F * (2. - WGS84_F);
+
+/// A 3x3 rotation matrix, in row-major order.
+pub(crate) type Matrix = [[f64; 3]; 3];
+
+/// An earth-centered, earth-fixed (ECEF) position on the WGS84 ellipsoid, in meters.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct Ecef {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+/// A position and velocity in a local east-north-up (ENU) tangent frame.
+///
+/// Created by [Point::to_enu] or [crate::Trajectory::to_enu].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct Enu {
+    pub time: f64,
+    pub east: f64,
+    pub north: f64,
+    pub up: f64,
+    pub east_velocity: f64,
+    pub north_velocity: f64,
+    pub up_velocity: f64,
+}
+
+/// A position and velocity in a local north-east-down (NED) tangent frame.
+///
+/// Created by [Point::to_ned] or [crate::Trajectory::to_ned].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct Ned {
+    pub time: f64,
+    pub north: f64,
+    pub east: f64,
+    pub down: f64,
+    pub north_velocity: f64,
+    pub east_velocity: f64,
+    pub down_velocity: f64,
+}
+
+impl Point {
+    /// Converts this point's position to earth-centered, earth-fixed coordinates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Point;
+    ///
+    /// let ecef = Point::default().to_ecef();
+    /// assert_eq!(ecef.x, 6378137.);
+    /// ```
+    pub fn to_ecef(&self) -> Ecef {
+        let (sin_latitude, cos_latitude) = self.latitude.sin_cos();
+        let (sin_longitude, cos_longitude) = self.longitude.sin_cos();
+        let n = WGS84_A / (1. - WGS84_E2 * sin_latitude * sin_latitude).sqrt();
+        Ecef {
+            x: (n + self.altitude) * cos_latitude * cos_longitude,
+            y: (n + self.altitude) * cos_latitude * sin_longitude,
+            z: (n * (1. - WGS84_E2) + self.altitude) * sin_latitude,
+        }
+    }
+
+    /// Returns this point's velocity as north, east, and down components.
+    ///
+    /// SBET velocities are stored in the wander-azimuth frame, whose x axis
+    /// is rotated from true north by the wander angle (so that true heading
+    /// is yaw minus wander angle), with z pointing down.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Point;
+    ///
+    /// let point = Point { x_velocity: 1., ..Default::default() };
+    /// assert_eq!(point.velocity_ned(), [1., 0., 0.]);
+    /// ```
+    pub fn velocity_ned(&self) -> [f64; 3] {
+        let (sin_wander, cos_wander) = self.wander_angle.sin_cos();
+        [
+            self.x_velocity * cos_wander + self.y_velocity * sin_wander,
+            -self.x_velocity * sin_wander + self.y_velocity * cos_wander,
+            self.z_velocity,
+        ]
+    }
+
+    /// Returns the geodesic distance to another point on the WGS84 ellipsoid, in meters.
+    ///
+    /// Uses Vincenty's inverse formula, which is accurate to well under a
+    /// millimeter. Altitudes are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Point;
+    ///
+    /// let a = Point::default();
+    /// let b = Point { longitude: 1f64.to_radians(), ..Default::default() };
+    /// assert!((a.geodesic_distance(&b) - 111319.491).abs() < 1e-3);
+    /// ```
+    pub fn geodesic_distance(&self, other: &Point) -> f64 {
+        let b = WGS84_A * (1. - WGS84_F);
+        let l = other.longitude - self.longitude;
+        let (sin_u1, cos_u1) = ((1. - WGS84_F) * self.latitude.tan()).atan().sin_cos();
+        let (sin_u2, cos_u2) = ((1. - WGS84_F) * other.latitude.tan()).atan().sin_cos();
+        let mut lambda = l;
+        let (mut sin_sigma, mut cos_sigma, mut sigma, mut cos2_alpha, mut cos_2sigma_m);
+        let mut iterations = 0;
+        loop {
+            let (sin_lambda, cos_lambda) = lambda.sin_cos();
+            sin_sigma = (cos_u2 * sin_lambda).hypot(cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda);
+            if sin_sigma == 0. {
+                return 0.;
+            }
+            cos_sigma = sin_u1 * sin_u2 + cos_u1 * cos_u2 * cos_lambda;
+            sigma = sin_sigma.atan2(cos_sigma);
+            let sin_alpha = cos_u1 * cos_u2 * sin_lambda / sin_sigma;
+            cos2_alpha = 1. - sin_alpha * sin_alpha;
+            cos_2sigma_m = if cos2_alpha == 0. {
+                // Both points are on the equator.
+                0.
+            } else {
+                cos_sigma - 2. * sin_u1 * sin_u2 / cos2_alpha
+            };
+            let c = WGS84_F / 16. * cos2_alpha * (4. + WGS84_F * (4. - 3. * cos2_alpha));
+            let previous = lambda;
+            lambda = l
+                + (1. - c)
+                    * WGS84_F
+                    * sin_alpha
+                    * (sigma
+                        + c * sin_sigma
+                            * (cos_2sigma_m
+                                + c * cos_sigma * (-1. + 2. * cos_2sigma_m * cos_2sigma_m)));
+            iterations += 1;
+            // Nearly antipodal points may not converge; the last estimate is still close.
+            if (lambda - previous).abs() < 1e-12 || iterations >= 200 {
+                break;
+            }
+        }
+        let u2 = cos2_alpha * (WGS84_A * WGS84_A - b * b) / (b * b);
+        let a = 1. + u2 / 16384. * (4096. + u2 * (-768. + u2 * (320. - 175. * u2)));
+        let b_coefficient = u2 / 1024. * (256. + u2 * (-128. + u2 * (74. - 47. * u2)));
+        let delta_sigma = b_coefficient
+            * sin_sigma
+            * (cos_2sigma_m
+                + b_coefficient / 4.
+                    * (cos_sigma * (-1. + 2. * cos_2sigma_m * cos_2sigma_m)
+                        - b_coefficient / 6.
+                            * cos_2sigma_m
+                            * (-3. + 4. * sin_sigma * sin_sigma)
+                            * (-3. + 4. * cos_2sigma_m * cos_2sigma_m)));
+        b * a * (sigma - delta_sigma)
+    }
+
+    /// Returns the rotation matrix from the body frame to north-east-down.
+    ///
+    /// The body frame has x forward, y right, and z down. The rotation is
+    /// heading, then pitch, then roll, where heading is the yaw minus the
+    /// wander angle. Multiply a body-frame column vector on the left to get
+    /// its north, east, and down components.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Point;
+    /// use std::f64::consts::FRAC_PI_2;
+    ///
+    /// // A quarter-turn yaw in a wander-azimuth frame that's also rotated a
+    /// // quarter turn is a heading of zero, so the body's x axis points north.
+    /// let point = Point { yaw: FRAC_PI_2, wander_angle: FRAC_PI_2, ..Default::default() };
+    /// let rotation = point.rotation_body_to_ned();
+    /// assert!((rotation[0][0] - 1.).abs() < 1e-12);
+    /// ```
+    pub fn rotation_body_to_ned(&self) -> [[f64; 3]; 3] {
+        let (sin_roll, cos_roll) = self.roll.sin_cos();
+        let (sin_pitch, cos_pitch) = self.pitch.sin_cos();
+        let (sin_heading, cos_heading) = (self.yaw - self.wander_angle).sin_cos();
+        [
+            [
+                cos_pitch * cos_heading,
+                sin_roll * sin_pitch * cos_heading - cos_roll * sin_heading,
+                cos_roll * sin_pitch * cos_heading + sin_roll * sin_heading,
+            ],
+            [
+                cos_pitch * sin_heading,
+                sin_roll * sin_pitch * sin_heading + cos_roll * cos_heading,
+                cos_roll * sin_pitch * sin_heading - sin_roll * cos_heading,
+            ],
+            [-sin_pitch, sin_roll * cos_pitch, cos_roll * cos_pitch],
+        ]
+    }
+
+    /// Returns the rotation matrix from the body frame to earth-centered, earth-fixed.
+    ///
+    /// This is [Point::rotation_body_to_ned] followed by the rotation from
+    /// the north-east-down frame at this point's latitude and longitude.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Point;
+    ///
+    /// // At latitude and longitude zero, forward (north) is ECEF +z.
+    /// let rotation = Point::default().rotation_body_to_ecef();
+    /// assert!((rotation[2][0] - 1.).abs() < 1e-12);
+    /// ```
+    pub fn rotation_body_to_ecef(&self) -> [[f64; 3]; 3] {
+        let (sin_latitude, cos_latitude) = self.latitude.sin_cos();
+        let (sin_longitude, cos_longitude) = self.longitude.sin_cos();
+        let ned_to_ecef = [
+            [
+                -sin_latitude * cos_longitude,
+                -sin_longitude,
+                -cos_latitude * cos_longitude,
+            ],
+            [
+                -sin_latitude * sin_longitude,
+                cos_longitude,
+                -cos_latitude * sin_longitude,
+            ],
+            [cos_latitude, 0., -sin_latitude],
+        ];
+        multiply(&ned_to_ecef, &self.rotation_body_to_ned())
+    }
+
+    /// Expresses this point's position and velocity in an east-north-up frame
+    /// tangent to the ellipsoid at the origin.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Point;
+    ///
+    /// let origin = Point::default();
+    /// let point = Point { altitude: 10., ..Default::default() };
+    /// let enu = point.to_enu(&origin);
+    /// assert!((enu.up - 10.).abs() < 1e-9);
+    /// ```
+    pub fn to_enu(&self, origin: &Point) -> Enu {
+        let origin_ecef = origin.to_ecef();
+        let ecef = self.to_ecef();
+        let [east, north, up] = ecef_to_enu(
+            [
+                ecef.x - origin_ecef.x,
+                ecef.y - origin_ecef.y,
+                ecef.z - origin_ecef.z,
+            ],
+            origin,
+        );
+        let [north_velocity, east_velocity, down_velocity] = self.velocity_ned();
+        let velocity = enu_to_ecef([east_velocity, north_velocity, -down_velocity], self);
+        let [east_velocity, north_velocity, up_velocity] = ecef_to_enu(velocity, origin);
+        Enu {
+            time: self.time,
+            east,
+            north,
+            up,
+            east_velocity,
+            north_velocity,
+            up_velocity,
+        }
+    }
+
+    /// Expresses this point's position and velocity in a north-east-down frame
+    /// tangent to the ellipsoid at the origin.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Point;
+    ///
+    /// let origin = Point::default();
+    /// let point = Point { altitude: 10., ..Default::default() };
+    /// let ned = point.to_ned(&origin);
+    /// assert!((ned.down + 10.).abs() < 1e-9);
+    /// ```
+    pub fn to_ned(&self, origin: &Point) -> Ned {
+        let enu = self.to_enu(origin);
+        Ned {
+            time: enu.time,
+            north: enu.north,
+            east: enu.east,
+            down: -enu.up,
+            north_velocity: enu.north_velocity,
+            east_velocity: enu.east_velocity,
+            down_velocity: -enu.up_velocity,
+        }
+    }
+}
+
+/// Multiplies two matrices.
+pub(crate) fn multiply(a: &Matrix, b: &Matrix) -> Matrix {
+    let mut product = [[0.; 3]; 3];
+    for (i, row) in product.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = (0..3).map(|k| a[i][k] * b[k][j]).sum();
+        }
+    }
+    product
+}
+
+/// Rotates an ECEF vector into the ENU frame at a point.
+pub(crate) fn ecef_to_enu([x, y, z]: [f64; 3], at: &Point) -> [f64; 3] {
+    let (sin_latitude, cos_latitude) = at.latitude.sin_cos();
+    let (sin_longitude, cos_longitude) = at.longitude.sin_cos();
+    [
+        -sin_longitude * x + cos_longitude * y,
+        -sin_latitude * cos_longitude * x - sin_latitude * sin_longitude * y + cos_latitude * z,
+        cos_latitude * cos_longitude * x + cos_latitude * sin_longitude * y + sin_latitude * z,
+    ]
+}
+
+/// Rotates an ENU vector at a point into the ECEF frame.
+pub(crate) fn enu_to_ecef([east, north, up]: [f64; 3], at: &Point) -> [f64; 3] {
+    let (sin_latitude, cos_latitude) = at.latitude.sin_cos();
+    let (sin_longitude, cos_longitude) = at.longitude.sin_cos();
+    [
+        -sin_longitude * east - sin_latitude * cos_longitude * north
+            + cos_latitude * cos_longitude * up,
+        cos_longitude * east - sin_latitude * sin_longitude * north
+            + cos_latitude * sin_longitude * up,
+        cos_latitude * north + sin_latitude * up,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Point;
+
+    #[test]
+    fn enu_offsets() {
+        let origin = Point {
+            latitude: 45f64.to_radians(),
+            longitude: (-105f64).to_radians(),
+            ..Default::default()
+        };
+        let north = Point {
+            latitude: origin.latitude + 1e-6,
+            ..origin
+        };
+        let enu = north.to_enu(&origin);
+        assert!(enu.north > 6. && enu.north < 7.);
+        assert!(enu.east.abs() < 1e-6);
+    }
+
+    #[test]
+    fn geodesic_distance() {
+        // Flinders Peak to Buninyong, from Vincenty's paper.
+        let dms = |degrees: f64, minutes: f64, seconds: f64| {
+            (degrees.signum() * (degrees.abs() + minutes / 60. + seconds / 3600.)).to_radians()
+        };
+        let flinders_peak = Point {
+            latitude: dms(-37., 57., 3.7203),
+            longitude: dms(144., 25., 29.5244),
+            ..Default::default()
+        };
+        let buninyong = Point {
+            latitude: dms(-37., 39., 10.1561),
+            longitude: dms(143., 55., 35.3839),
+            ..Default::default()
+        };
+        let distance = flinders_peak.geodesic_distance(&buninyong);
+        assert!((distance - 54972.271).abs() < 1e-3, "{}", distance);
+        assert_eq!(flinders_peak.geodesic_distance(&flinders_peak), 0.);
+    }
+
+    #[test]
+    fn body_to_ned_is_orthonormal() {
+        let point = Point {
+            roll: 0.1,
+            pitch: -0.2,
+            yaw: 2.,
+            wander_angle: 0.5,
+            ..Default::default()
+        };
+        let r = point.rotation_body_to_ned();
+        for i in 0..3 {
+            for j in 0..3 {
+                let dot: f64 = (0..3).map(|k| r[k][i] * r[k][j]).sum();
+                let expected = if i == j { 1. } else { 0. };
+                assert!((dot - expected).abs() < 1e-12);
+            }
+        }
+        // Forward points along the true heading, tilted up by the pitch.
+        assert!((r[0][0] - 0.2f64.cos() * 1.5f64.cos()).abs() < 1e-12);
+        assert!((r[1][0] - 0.2f64.cos() * 1.5f64.sin()).abs() < 1e-12);
+        assert!((r[2][0] - 0.2f64.sin()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn velocity_at_origin() {
+        let point = Point {
+            latitude: 0.5,
+            longitude: 0.5,
+            x_velocity: 1.,
+            y_velocity: 2.,
+            z_velocity: 3.,
+            ..Default::default()
+        };
+        let ned = point.to_ned(&point);
+        assert!((ned.north_velocity - 1.).abs() < 1e-12);
+        assert!((ned.east_velocity - 2.).abs() < 1e-12);
+        assert!((ned.down_velocity - 3.).abs() < 1e-12);
+    }
+}
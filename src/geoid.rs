@@ -0,0 +1,219 @@
+use crate::{Error, Point, Result};
+use byteorder::{BigEndian, ReadBytesExt};
+use std::{
+    fs::File,
+    io::{BufReader, Read},
+    path::Path,
+};
+
+/// Value used by GTX grids for cells without data.
+const GTX_NODATA: f32 = -88.8888;
+
+/// A geoid undulation grid, for converting ellipsoidal altitudes to orthometric heights.
+///
+/// Grids are read from the GTX format, which is used by PROJ and NOAA for
+/// models such as EGM96 and EGM2008.
+///
+/// # Examples
+///
+/// ```no_run
+/// use sbet::{Geoid, Point};
+///
+/// let geoid = Geoid::from_path("egm96_15.gtx").unwrap();
+/// let height = geoid.orthometric_height(&Point::default()).unwrap();
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Geoid {
+    south: f64,
+    west: f64,
+    latitude_spacing: f64,
+    longitude_spacing: f64,
+    rows: usize,
+    columns: usize,
+    undulations: Vec<f32>,
+}
+
+impl Geoid {
+    /// Reads a GTX geoid grid from a path.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use sbet::Geoid;
+    ///
+    /// let geoid = Geoid::from_path("egm96_15.gtx").unwrap();
+    /// ```
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Geoid> {
+        Geoid::read(BufReader::new(File::open(path)?))
+    }
+
+    /// Reads a GTX geoid grid.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use sbet::Geoid;
+    /// use std::fs::File;
+    ///
+    /// let geoid = Geoid::read(File::open("egm96_15.gtx").unwrap()).unwrap();
+    /// ```
+    pub fn read<R: Read>(mut read: R) -> Result<Geoid> {
+        let south = read.read_f64::<BigEndian>()?;
+        let west = read.read_f64::<BigEndian>()?;
+        let latitude_spacing = read.read_f64::<BigEndian>()?;
+        let longitude_spacing = read.read_f64::<BigEndian>()?;
+        let rows = read.read_i32::<BigEndian>()?;
+        let columns = read.read_i32::<BigEndian>()?;
+        if rows < 2 || columns < 2 || latitude_spacing <= 0. || longitude_spacing <= 0. {
+            return Err(Error::InvalidGeoid(format!(
+                "invalid grid header: {} rows, {} columns, spacing {} by {}",
+                rows, columns, latitude_spacing, longitude_spacing
+            )));
+        }
+        let (rows, columns) = (rows as usize, columns as usize);
+        // Read the grid incrementally instead of trusting the header's size,
+        // so a corrupt header can't allocate more than the stream holds.
+        let len = rows
+            .checked_mul(columns)
+            .and_then(|cells| cells.checked_mul(4))
+            .ok_or_else(|| {
+                Error::InvalidGeoid(format!("{} by {} grid is too large", rows, columns))
+            })?;
+        let mut bytes = Vec::new();
+        let _ = read.take(len as u64).read_to_end(&mut bytes)?;
+        if bytes.len() != len {
+            return Err(Error::InvalidGeoid(format!(
+                "{} by {} grid needs {} bytes, but only {} were read",
+                rows,
+                columns,
+                len,
+                bytes.len()
+            )));
+        }
+        let mut undulations = vec![0.; rows * columns];
+        (&bytes[..]).read_f32_into::<BigEndian>(&mut undulations)?;
+        Ok(Geoid {
+            south,
+            west,
+            latitude_spacing,
+            longitude_spacing,
+            rows,
+            columns,
+            undulations,
+        })
+    }
+
+    /// Returns the geoid undulation, in meters, at a latitude and longitude in radians.
+    ///
+    /// Returns `None` if the location is outside of the grid or in a cell without data.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use sbet::Geoid;
+    ///
+    /// let geoid = Geoid::from_path("egm96_15.gtx").unwrap();
+    /// let undulation = geoid.undulation(0.7, -1.8).unwrap();
+    /// ```
+    pub fn undulation(&self, latitude: f64, longitude: f64) -> Option<f64> {
+        let row = (latitude.to_degrees() - self.south) / self.latitude_spacing;
+        let column = (longitude.to_degrees() - self.west).rem_euclid(360.) / self.longitude_spacing;
+        let max_column = (self.columns - 1) as f64;
+        if !(0. ..=(self.rows - 1) as f64).contains(&row) || !(0. ..=max_column).contains(&column) {
+            return None;
+        }
+        let (row0, column0) = (
+            (row.floor() as usize).min(self.rows - 2),
+            (column.floor() as usize).min(self.columns - 2),
+        );
+        let (row_factor, column_factor) = (row - row0 as f64, column - column0 as f64);
+        let value = |row: usize, column: usize| {
+            let value = self.undulations[row * self.columns + column];
+            if (value - GTX_NODATA).abs() < 1e-4 {
+                None
+            } else {
+                Some(f64::from(value))
+            }
+        };
+        let south = value(row0, column0)? * (1. - column_factor)
+            + value(row0, column0 + 1)? * column_factor;
+        let north = value(row0 + 1, column0)? * (1. - column_factor)
+            + value(row0 + 1, column0 + 1)? * column_factor;
+        Some(south * (1. - row_factor) + north * row_factor)
+    }
+
+    /// Returns a point's orthometric height, i.e. its ellipsoidal altitude minus the geoid undulation.
+    ///
+    /// Returns `None` if the point is outside of the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use sbet::{Geoid, Point};
+    ///
+    /// let geoid = Geoid::from_path("egm96_15.gtx").unwrap();
+    /// let height = geoid.orthometric_height(&Point::default()).unwrap();
+    /// ```
+    pub fn orthometric_height(&self, point: &Point) -> Option<f64> {
+        self.undulation(point.latitude, point.longitude)
+            .map(|undulation| point.altitude - undulation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Geoid;
+    use crate::{Error, Point};
+    use byteorder::{BigEndian, WriteBytesExt};
+
+    fn geoid() -> Geoid {
+        let mut bytes = Vec::new();
+        for value in [10., 350., 1., 1.] {
+            bytes.write_f64::<BigEndian>(value).unwrap();
+        }
+        bytes.write_i32::<BigEndian>(2).unwrap();
+        bytes.write_i32::<BigEndian>(3).unwrap();
+        for value in [0., 2., 4., 10., 12., 14.] {
+            bytes.write_f32::<BigEndian>(value).unwrap();
+        }
+        Geoid::read(bytes.as_slice()).unwrap()
+    }
+
+    #[test]
+    fn bilinear() {
+        let geoid = geoid();
+        let undulation = geoid
+            .undulation(10.5f64.to_radians(), (-9.5f64).to_radians())
+            .unwrap();
+        assert!((undulation - 6.).abs() < 1e-9);
+        let point = Point {
+            latitude: 11f64.to_radians(),
+            longitude: (-8f64).to_radians(),
+            altitude: 20.,
+            ..Default::default()
+        };
+        assert!((geoid.orthometric_height(&point).unwrap() - 6.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn outside() {
+        let geoid = geoid();
+        assert!(geoid.undulation(0., 0.).is_none());
+        assert!(geoid.undulation(10.5f64.to_radians(), 0.).is_none());
+    }
+
+    #[test]
+    fn huge_header() {
+        let mut bytes = Vec::new();
+        for value in [10., 350., 1., 1.] {
+            bytes.write_f64::<BigEndian>(value).unwrap();
+        }
+        bytes.write_i32::<BigEndian>(i32::MAX).unwrap();
+        bytes.write_i32::<BigEndian>(i32::MAX).unwrap();
+        bytes.write_f32::<BigEndian>(0.).unwrap();
+        assert!(matches!(
+            Geoid::read(bytes.as_slice()).unwrap_err(),
+            Error::InvalidGeoid(_)
+        ));
+    }
+}
@@ -0,0 +1,501 @@
+//! GeoJSON output for trajectories.
+//!
+//! Positions are written as `[longitude, latitude, altitude]`, with
+//! longitude and latitude in degrees, or as `[x, y, altitude]` for
+//! [projected](Builder::projected) points.
+//!
+//! # Examples
+//!
+//! ```
+//! use sbet::geojson::Builder;
+//! use sbet::{Field, Point};
+//!
+//! let points = [Point::default(); 3];
+//! let geojson = Builder::points()
+//!     .properties([Field::Time])
+//!     .to_string(&points)
+//!     .unwrap();
+//! assert!(geojson.starts_with(r#"{"type":"FeatureCollection""#));
+//! ```
+
+use crate::{
+    hull::convex_hull,
+    json::{write_number, write_string},
+    Error, Point, Quantity, Result, Segment, SegmentKind, SpeedUnit,
+};
+use std::io::Write;
+
+/// The shape of the GeoJSON output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Geometry {
+    /// A single `Feature` with a `LineString` geometry through every point.
+    #[default]
+    LineString,
+
+    /// A `FeatureCollection` with one `Point` feature per point.
+    Points,
+
+    /// A single `Feature` with a two-dimensional `Polygon` geometry around the points' convex hull.
+    ///
+    /// Properties are ignored. See [Trajectory::convex_hull](crate::Trajectory::convex_hull).
+    ConvexHull,
+}
+
+/// Builds GeoJSON from a slice of points.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::geojson::Builder;
+/// use sbet::Point;
+///
+/// let points = [Point::default(); 10];
+/// let geojson = Builder::line_string().decimate(5).to_string(&points).unwrap();
+/// assert_eq!(geojson.matches("[0,0,0]").count(), 2);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Builder {
+    geometry: Geometry,
+    decimate: usize,
+    properties: Vec<Quantity>,
+    speed_units: SpeedUnit,
+    projected: bool,
+}
+
+impl Builder {
+    /// Creates a builder for a `LineString` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::geojson::Builder;
+    ///
+    /// let builder = Builder::line_string();
+    /// ```
+    pub fn line_string() -> Builder {
+        Builder::new(Geometry::LineString)
+    }
+
+    /// Creates a builder for a `FeatureCollection` of `Point` features.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::geojson::Builder;
+    ///
+    /// let builder = Builder::points();
+    /// ```
+    pub fn points() -> Builder {
+        Builder::new(Geometry::Points)
+    }
+
+    /// Creates a builder for a `Polygon` feature around the points' convex hull.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::geojson::Builder;
+    /// use sbet::Point;
+    ///
+    /// let points: Vec<Point> = [(0., 0.), (1., 0.), (0., 1.)]
+    ///     .into_iter()
+    ///     .map(|(longitude, latitude)| Point { longitude, latitude, ..Default::default() })
+    ///     .collect();
+    /// let geojson = Builder::convex_hull().to_string(&points).unwrap();
+    /// assert!(geojson.contains(r#""type":"Polygon""#));
+    /// assert!(Builder::convex_hull().to_string(&[Point::default()]).is_err());
+    /// ```
+    pub fn convex_hull() -> Builder {
+        Builder::new(Geometry::ConvexHull)
+    }
+
+    /// Creates a builder for a geometry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::geojson::{Builder, Geometry};
+    ///
+    /// let builder = Builder::new(Geometry::Points);
+    /// ```
+    pub fn new(geometry: Geometry) -> Builder {
+        Builder {
+            geometry,
+            decimate: 1,
+            properties: Vec::new(),
+            speed_units: SpeedUnit::MetersPerSecond,
+            projected: false,
+        }
+    }
+
+    /// Only uses every `n`th point, starting with the first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    pub fn decimate(mut self, n: usize) -> Builder {
+        assert!(n > 0, "decimation must be positive");
+        self.decimate = n;
+        self
+    }
+
+    /// Sets the fields or derived values to include as properties, in SBET units.
+    ///
+    /// Velocities and speeds are in the [speed units](Builder::speed_units).
+    ///
+    /// For points, each feature has one value per field. For a line
+    /// string, each property is an array with one value per vertex.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::geojson::Builder;
+    /// use sbet::{Derived, Field, Quantity};
+    ///
+    /// let builder = Builder::points().properties([Field::Time]);
+    /// let builder = Builder::points()
+    ///     .properties([Quantity::from(Field::Altitude), Derived::TrueHeading.into()]);
+    /// ```
+    pub fn properties<I>(mut self, quantities: I) -> Builder
+    where
+        I: IntoIterator,
+        I::Item: Into<Quantity>,
+    {
+        self.properties = quantities.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the units of velocity and speed properties, which default to meters per second.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::geojson::Builder;
+    /// use sbet::{Derived, Point, SpeedUnit};
+    ///
+    /// let point = Point { x_velocity: 10., ..Default::default() };
+    /// let geojson = Builder::points()
+    ///     .properties([Derived::Speed])
+    ///     .speed_units(SpeedUnit::KilometersPerHour)
+    ///     .to_string(&[point])
+    ///     .unwrap();
+    /// assert!(geojson.contains(r#""speed":36"#));
+    /// ```
+    pub fn speed_units(mut self, speed_units: SpeedUnit) -> Builder {
+        self.speed_units = speed_units;
+        self
+    }
+
+    /// Writes each point's longitude and latitude as projected x and y, without converting them to degrees.
+    ///
+    /// Use this for points whose positions have already been reprojected,
+    /// e.g. with a [Transformer](crate::Transformer).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::geojson::Builder;
+    /// use sbet::Point;
+    ///
+    /// let point = Point { longitude: 500000., latitude: 4000000., ..Default::default() };
+    /// let geojson = Builder::points().projected(true).to_string(&[point]).unwrap();
+    /// assert!(geojson.contains("[500000,4000000,0]"));
+    /// ```
+    pub fn projected(mut self, projected: bool) -> Builder {
+        self.projected = projected;
+        self
+    }
+
+    /// Writes the GeoJSON for the points.
+    ///
+    /// # Errors
+    ///
+    /// Returns [Error::DegenerateHull] for a [convex hull](Geometry::ConvexHull)
+    /// with fewer than three distinct, non-collinear positions, since a GeoJSON
+    /// polygon needs at least four positions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::geojson::Builder;
+    /// use sbet::Point;
+    ///
+    /// let mut geojson = Vec::new();
+    /// Builder::points().write(&[Point::default()], &mut geojson).unwrap();
+    /// ```
+    pub fn write<W: Write>(&self, points: &[Point], mut write: W) -> Result<()> {
+        let points: Vec<&Point> = points.iter().step_by(self.decimate).collect();
+        match self.geometry {
+            Geometry::LineString => {
+                write!(
+                    write,
+                    r#"{{"type":"Feature","geometry":{{"type":"LineString","coordinates":["#
+                )?;
+                for (i, point) in points.iter().enumerate() {
+                    if i > 0 {
+                        write!(write, ",")?;
+                    }
+                    self.write_position(&mut write, point)?;
+                }
+                write!(write, r#"]}},"properties":{{"#)?;
+                for (i, quantity) in self.properties.iter().enumerate() {
+                    if i > 0 {
+                        write!(write, ",")?;
+                    }
+                    write!(write, r#""{}":["#, quantity.name())?;
+                    for (j, point) in points.iter().enumerate() {
+                        if j > 0 {
+                            write!(write, ",")?;
+                        }
+                        write_number(&mut write, self.property(point, *quantity))?;
+                    }
+                    write!(write, "]")?;
+                }
+                write!(write, "}}}}")?;
+            }
+            Geometry::Points => {
+                write!(write, r#"{{"type":"FeatureCollection","features":["#)?;
+                for (i, point) in points.iter().enumerate() {
+                    if i > 0 {
+                        write!(write, ",")?;
+                    }
+                    write!(
+                        write,
+                        r#"{{"type":"Feature","geometry":{{"type":"Point","coordinates":"#
+                    )?;
+                    self.write_position(&mut write, point)?;
+                    write!(write, r#"}},"properties":{{"#)?;
+                    for (j, quantity) in self.properties.iter().enumerate() {
+                        if j > 0 {
+                            write!(write, ",")?;
+                        }
+                        write!(write, r#""{}":"#, quantity.name())?;
+                        write_number(&mut write, self.property(point, *quantity))?;
+                    }
+                    write!(write, "}}}}")?;
+                }
+                write!(write, "]}}")?;
+            }
+            Geometry::ConvexHull => {
+                let points: Vec<Point> = points.into_iter().copied().collect();
+                let hull = convex_hull(&points);
+                if hull.len() < 3 {
+                    return Err(Error::DegenerateHull(hull.len()));
+                }
+                write!(
+                    write,
+                    r#"{{"type":"Feature","geometry":{{"type":"Polygon","coordinates":[["#
+                )?;
+                for (i, point) in hull.iter().chain(hull.first()).enumerate() {
+                    if i > 0 {
+                        write!(write, ",")?;
+                    }
+                    let (x, y) = self.horizontal(point);
+                    write_coordinates(&mut write, &[x, y])?;
+                }
+                write!(write, r#"]]}},"properties":{{}}}}"#)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the GeoJSON for the points as a string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::geojson::Builder;
+    /// use sbet::Point;
+    ///
+    /// let geojson = Builder::line_string().to_string(&[Point::default()]).unwrap();
+    /// ```
+    pub fn to_string(&self, points: &[Point]) -> Result<String> {
+        let mut bytes = Vec::new();
+        self.write(points, &mut bytes)?;
+        Ok(String::from_utf8(bytes).expect("GeoJSON is valid UTF-8"))
+    }
+
+    fn horizontal(&self, point: &Point) -> (f64, f64) {
+        if self.projected {
+            (point.longitude, point.latitude)
+        } else {
+            (point.longitude.to_degrees(), point.latitude.to_degrees())
+        }
+    }
+
+    fn write_position<W: Write>(&self, write: &mut W, point: &Point) -> std::io::Result<()> {
+        let (x, y) = self.horizontal(point);
+        write_coordinates(write, &[x, y, point.altitude])
+    }
+
+    fn property(&self, point: &Point, quantity: Quantity) -> f64 {
+        let value = point.quantity(quantity);
+        if quantity.is_speed() {
+            self.speed_units.convert(value)
+        } else {
+            value
+        }
+    }
+}
+
+/// Writes a `FeatureCollection` with a `LineString` feature for each segment of the points.
+///
+/// Each feature has `kind` ("line" or "turn"), `number`, `start_time`,
+/// `end_time`, and `heading` properties, with the heading in degrees.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::{LineLimits, Point, Trajectory};
+///
+/// let trajectory: Trajectory = (0..60)
+///     .map(|i| Point { time: f64::from(i), ..Default::default() })
+///     .collect::<Vec<_>>()
+///     .into();
+/// let lines = trajectory.flight_lines(LineLimits::default());
+/// let mut geojson = Vec::new();
+/// sbet::geojson::write_segments(trajectory.points(), &lines, &mut geojson).unwrap();
+/// assert!(String::from_utf8(geojson).unwrap().contains(r#""kind":"line","number":1"#));
+/// ```
+pub fn write_segments<W: Write>(
+    points: &[Point],
+    segments: &[Segment],
+    mut write: W,
+) -> Result<()> {
+    write!(write, r#"{{"type":"FeatureCollection","features":["#)?;
+    for (i, segment) in segments.iter().enumerate() {
+        if i > 0 {
+            write!(write, ",")?;
+        }
+        write!(
+            write,
+            r#"{{"type":"Feature","geometry":{{"type":"LineString","coordinates":["#
+        )?;
+        for (j, point) in points[segment.start..segment.end].iter().enumerate() {
+            if j > 0 {
+                write!(write, ",")?;
+            }
+            write_position(&mut write, point)?;
+        }
+        write!(write, r#"]}},"properties":{{"kind":"#)?;
+        write_string(
+            &mut write,
+            match segment.kind {
+                SegmentKind::Line => "line",
+                SegmentKind::Turn => "turn",
+            },
+        )?;
+        write!(write, r#","number":{},"start_time":"#, segment.number)?;
+        write_number(&mut write, segment.start_time)?;
+        write!(write, r#","end_time":"#)?;
+        write_number(&mut write, segment.end_time)?;
+        write!(write, r#","heading":"#)?;
+        write_number(&mut write, segment.heading.to_degrees())?;
+        write!(write, "}}}}")?;
+    }
+    write!(write, "]}}")?;
+    Ok(())
+}
+
+fn write_position<W: Write>(write: &mut W, point: &Point) -> std::io::Result<()> {
+    write_coordinates(
+        write,
+        &[
+            point.longitude.to_degrees(),
+            point.latitude.to_degrees(),
+            point.altitude,
+        ],
+    )
+}
+
+fn write_coordinates<W: Write>(write: &mut W, coordinates: &[f64]) -> std::io::Result<()> {
+    write!(write, "[")?;
+    for (i, coordinate) in coordinates.iter().enumerate() {
+        if i > 0 {
+            write!(write, ",")?;
+        }
+        write_number(write, *coordinate)?;
+    }
+    write!(write, "]")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Builder;
+    use crate::{Error, Field, Point};
+
+    #[test]
+    fn line_string_properties() {
+        let points = [
+            Point {
+                time: 1.,
+                ..Default::default()
+            },
+            Point {
+                time: 2.,
+                ..Default::default()
+            },
+        ];
+        let geojson = Builder::line_string()
+            .properties([Field::Time])
+            .to_string(&points)
+            .unwrap();
+        assert_eq!(
+            geojson,
+            r#"{"type":"Feature","geometry":{"type":"LineString","coordinates":[[0,0,0],[0,0,0]]},"properties":{"time":[1,2]}}"#
+        );
+    }
+
+    #[test]
+    fn convex_hull() {
+        let points: Vec<Point> = [(0., 0.), (90., 0.), (45., 10.), (0., 45.)]
+            .into_iter()
+            .map(|(longitude, latitude)| {
+                Point::builder()
+                    .longitude_degrees(longitude)
+                    .latitude_degrees(latitude)
+                    .build()
+            })
+            .collect();
+        let geojson = Builder::convex_hull().to_string(&points).unwrap();
+        assert_eq!(
+            geojson,
+            r#"{"type":"Feature","geometry":{"type":"Polygon","coordinates":[[[0,0],[90,0],[0,45],[0,0]]]},"properties":{}}"#
+        );
+    }
+
+    #[test]
+    fn degenerate_convex_hull() {
+        let points: Vec<Point> = [(0., 0.), (1., 1.), (2., 2.)]
+            .into_iter()
+            .map(|(longitude, latitude)| Point {
+                longitude,
+                latitude,
+                ..Default::default()
+            })
+            .collect();
+        for n in 0..=3 {
+            assert!(matches!(
+                Builder::convex_hull().to_string(&points[..n]).unwrap_err(),
+                Error::DegenerateHull(_)
+            ));
+        }
+    }
+
+    #[test]
+    fn points_properties() {
+        let points = [Point {
+            latitude: 1f64.to_radians(),
+            altitude: 3.,
+            ..Default::default()
+        }];
+        let geojson = Builder::points()
+            .properties([Field::Altitude, Field::Time])
+            .to_string(&points)
+            .unwrap();
+        assert_eq!(
+            geojson,
+            r#"{"type":"FeatureCollection","features":[{"type":"Feature","geometry":{"type":"Point","coordinates":[0,1,3]},"properties":{"altitude":3,"time":0}}]}"#
+        );
+    }
+}
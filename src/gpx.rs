@@ -0,0 +1,232 @@
+//! GPX output for trajectories.
+//!
+//! # Examples
+//!
+//! ```
+//! use sbet::gpx::Builder;
+//! use sbet::Point;
+//!
+//! let points: Vec<Point> = [0., 1., 2., 60., 61.]
+//!     .into_iter()
+//!     .map(|time| Point { time, ..Default::default() })
+//!     .collect();
+//! let gpx = Builder::new().to_string(&points).unwrap();
+//! assert_eq!(gpx.matches("<trkseg>").count(), 2);
+//! ```
+
+use crate::{kml::escape, sample_rate::median_interval, Point, Result};
+use std::io::Write;
+
+/// A gap is this many times longer than the median time between points, by default.
+const GAP_FACTOR: f64 = 10.;
+
+/// Builds a GPX document with one track through a slice of points.
+///
+/// The track is split into a new segment wherever there is a gap in the
+/// points' times, or the time goes backwards.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::gpx::Builder;
+/// use sbet::Point;
+///
+/// let mut gpx = Vec::new();
+/// Builder::new().write(&[Point::default(); 2], &mut gpx).unwrap();
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Builder {
+    name: Option<String>,
+    decimate: Option<usize>,
+    gap: Option<f64>,
+    #[cfg(feature = "chrono")]
+    week: Option<u32>,
+}
+
+impl Builder {
+    /// Creates a builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::gpx::Builder;
+    ///
+    /// let builder = Builder::new();
+    /// ```
+    pub fn new() -> Builder {
+        Builder::default()
+    }
+
+    /// Sets the track name.
+    pub fn name(mut self, name: &str) -> Builder {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Only uses every `n`th point, starting with the first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    pub fn decimate(mut self, n: usize) -> Builder {
+        assert!(n > 0, "decimation must be positive");
+        self.decimate = Some(n);
+        self
+    }
+
+    /// Starts a new segment where consecutive points are more than this many seconds apart.
+    ///
+    /// By default, a gap is ten times the median time between points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::gpx::Builder;
+    /// use sbet::Point;
+    ///
+    /// let points: Vec<Point> = [0., 1., 3.]
+    ///     .into_iter()
+    ///     .map(|time| Point { time, ..Default::default() })
+    ///     .collect();
+    /// let gpx = Builder::new().gap(1.5).to_string(&points).unwrap();
+    /// assert_eq!(gpx.matches("<trkseg>").count(), 2);
+    /// ```
+    pub fn gap(mut self, seconds: f64) -> Builder {
+        self.gap = Some(seconds);
+        self
+    }
+
+    /// Writes a timestamp for each point, with point times in seconds-of-week of this GPS week.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::gpx::Builder;
+    /// use sbet::Point;
+    ///
+    /// let gpx = Builder::new()
+    ///     .timestamps(2264)
+    ///     .to_string(&[Point { time: 396018., ..Default::default() }])
+    ///     .unwrap();
+    /// assert!(gpx.contains("<time>2023-06-01T14:00:00Z</time>"));
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn timestamps(mut self, week: u32) -> Builder {
+        self.week = Some(week);
+        self
+    }
+
+    /// Returns the segments of the track, as ranges of indices into the points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::gpx::Builder;
+    /// use sbet::Point;
+    ///
+    /// let points: Vec<Point> = [0., 1., 2., 60.]
+    ///     .into_iter()
+    ///     .map(|time| Point { time, ..Default::default() })
+    ///     .collect();
+    /// assert_eq!(Builder::new().segments(&points), vec![0..3, 3..4]);
+    /// ```
+    pub fn segments(&self, points: &[Point]) -> Vec<std::ops::Range<usize>> {
+        if points.is_empty() {
+            return Vec::new();
+        }
+        let gap = self.gap.unwrap_or_else(|| {
+            median_interval(points).map_or(f64::INFINITY, |median| median * GAP_FACTOR)
+        });
+        let mut segments = Vec::new();
+        let mut start = 0;
+        for (i, pair) in points.windows(2).enumerate() {
+            let interval = pair[1].time - pair[0].time;
+            if interval > gap || interval < 0. {
+                segments.push(start..i + 1);
+                start = i + 1;
+            }
+        }
+        segments.push(start..points.len());
+        segments
+    }
+
+    /// Writes the GPX document for the points.
+    pub fn write<W: Write>(&self, points: &[Point], mut write: W) -> Result<()> {
+        let points: Vec<Point> = points
+            .iter()
+            .step_by(self.decimate.unwrap_or(1))
+            .copied()
+            .collect();
+        writeln!(write, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            write,
+            r#"<gpx version="1.1" creator="sbet" xmlns="http://www.topografix.com/GPX/1/1">"#
+        )?;
+        writeln!(write, "<trk>")?;
+        if let Some(name) = &self.name {
+            writeln!(write, "<name>{}</name>", escape(name))?;
+        }
+        for segment in self.segments(&points) {
+            writeln!(write, "<trkseg>")?;
+            for point in &points[segment] {
+                write!(
+                    write,
+                    r#"<trkpt lat="{}" lon="{}"><ele>{}</ele>"#,
+                    point.latitude.to_degrees(),
+                    point.longitude.to_degrees(),
+                    point.altitude
+                )?;
+                #[cfg(feature = "chrono")]
+                if let Some(week) = self.week {
+                    let utc = crate::time::to_utc(week, point.time)
+                        .ok_or(crate::Error::TimeOutOfRange(point.time))?;
+                    write!(
+                        write,
+                        "<time>{}</time>",
+                        utc.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true)
+                    )?;
+                }
+                writeln!(write, "</trkpt>")?;
+            }
+            writeln!(write, "</trkseg>")?;
+        }
+        writeln!(write, "</trk>")?;
+        writeln!(write, "</gpx>")?;
+        Ok(())
+    }
+
+    /// Returns the GPX document for the points as a string.
+    pub fn to_string(&self, points: &[Point]) -> Result<String> {
+        let mut bytes = Vec::new();
+        self.write(points, &mut bytes)?;
+        Ok(String::from_utf8(bytes).expect("GPX is valid UTF-8"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Builder;
+    use crate::Point;
+
+    fn points(times: &[f64]) -> Vec<Point> {
+        times
+            .iter()
+            .map(|&time| Point {
+                time,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn backwards() {
+        let points = points(&[0., 1., 2., 0.5, 1.5]);
+        assert_eq!(Builder::new().segments(&points), vec![0..3, 3..5]);
+    }
+
+    #[test]
+    fn no_gaps() {
+        assert_eq!(Builder::new().segments(&points(&[0.])), vec![0..1]);
+        assert!(Builder::new().segments(&[]).is_empty());
+    }
+}
@@ -0,0 +1,236 @@
+use crate::{Error, Reader, Result};
+use std::io::{Read, Seek, SeekFrom};
+
+/// The number of bytes fetched by each range request.
+const BLOCK_SIZE: usize = 1 << 20;
+
+/// A seekable stream over a remote file, fetched with HTTP range requests, behind the `http` feature.
+///
+/// Only the blocks that are read are downloaded, so seeking to a record
+/// with [Reader::seek] doesn't download the whole file. `s3://bucket/key`
+/// URLs are read from the bucket's public HTTPS endpoint; use a presigned
+/// `https://` URL for private objects.
+///
+/// # Examples
+///
+/// ```no_run
+/// use sbet::Reader;
+///
+/// let mut reader = Reader::from_url("s3://bucket/trajectory.sbet").unwrap();
+/// reader.seek(1000).unwrap();
+/// let point = reader.read_one().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct HttpReader {
+    url: String,
+    len: u64,
+    position: u64,
+    block: Vec<u8>,
+    block_start: u64,
+}
+
+impl HttpReader {
+    /// Opens a remote file, fetching its length.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use sbet::HttpReader;
+    ///
+    /// let reader = HttpReader::new("https://example.com/trajectory.sbet").unwrap();
+    /// println!("{} bytes", reader.len());
+    /// ```
+    pub fn new(url: &str) -> Result<HttpReader> {
+        let url = resolve(url);
+        let response = ureq::head(&url).call()?;
+        let len = response
+            .headers()
+            .get("content-length")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse().ok())
+            .ok_or_else(|| Error::RangeRequest(format!("{} has no content length", url)))?;
+        Ok(HttpReader {
+            url,
+            len,
+            position: 0,
+            block: Vec::new(),
+            block_start: 0,
+        })
+    }
+
+    /// Returns the length of the remote file in bytes.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns true if the remote file is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn fetch(&mut self) -> std::io::Result<()> {
+        let end = (self.position + BLOCK_SIZE as u64).min(self.len) - 1;
+        let mut response = ureq::get(&self.url)
+            .header("Range", format!("bytes={}-{}", self.position, end))
+            .call()
+            .map_err(std::io::Error::other)?;
+        if response.status() != 206 {
+            return Err(std::io::Error::other(Error::RangeRequest(format!(
+                "{} does not support range requests",
+                self.url
+            ))));
+        }
+        self.block = response
+            .body_mut()
+            .read_to_vec()
+            .map_err(std::io::Error::other)?;
+        self.block_start = self.position;
+        Ok(())
+    }
+}
+
+impl Read for HttpReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.position >= self.len || buf.is_empty() {
+            return Ok(0);
+        }
+        if self.position < self.block_start
+            || self.position >= self.block_start + self.block.len() as u64
+        {
+            self.fetch()?;
+        }
+        let start = (self.position - self.block_start) as usize;
+        let n = (&self.block[start..]).read(buf)?;
+        self.position += n as u64;
+        Ok(n)
+    }
+}
+
+impl Seek for HttpReader {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => self.len.checked_add_signed(offset),
+            SeekFrom::Current(offset) => self.position.checked_add_signed(offset),
+        };
+        self.position = position.ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+        Ok(self.position)
+    }
+}
+
+impl Reader<HttpReader> {
+    /// Creates a reader for a remote file at an `http://`, `https://`, or `s3://` URL.
+    ///
+    /// See [HttpReader].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use sbet::Reader;
+    ///
+    /// let reader = Reader::from_url("https://example.com/trajectory.sbet").unwrap();
+    /// ```
+    pub fn from_url(url: &str) -> Result<Reader<HttpReader>> {
+        HttpReader::new(url).map(Reader::new)
+    }
+}
+
+fn resolve(url: &str) -> String {
+    match url
+        .strip_prefix("s3://")
+        .and_then(|rest| rest.split_once('/'))
+    {
+        Some((bucket, key)) => format!("https://{}.s3.amazonaws.com/{}", bucket, key),
+        None => url.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HttpReader;
+    use crate::Reader;
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+    };
+
+    /// Serves a file, answering HEAD and ranged GET requests, and counts the bytes sent.
+    fn serve(bytes: Vec<u8>) -> (String, std::thread::JoinHandle<usize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/2-points.sbet", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let mut sent = 0;
+            for _ in 0..2 {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut lines = Vec::new();
+                loop {
+                    let mut line = String::new();
+                    let _ = reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    lines.push(line.trim().to_lowercase());
+                }
+                let mut stream = stream;
+                if lines[0].starts_with("head") {
+                    write!(
+                        stream,
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                        bytes.len()
+                    )
+                    .unwrap();
+                } else {
+                    let range = lines
+                        .iter()
+                        .find_map(|line| line.strip_prefix("range: bytes="))
+                        .unwrap();
+                    let (start, end) = range.split_once('-').unwrap();
+                    let (start, end): (usize, usize) =
+                        (start.parse().unwrap(), end.parse().unwrap());
+                    let body = &bytes[start..=end];
+                    write!(
+                        stream,
+                        "HTTP/1.1 206 Partial Content\r\nContent-Length: {}\r\nContent-Range: bytes {}-{}/{}\r\nConnection: close\r\n\r\n",
+                        body.len(),
+                        start,
+                        end,
+                        bytes.len()
+                    )
+                    .unwrap();
+                    stream.write_all(body).unwrap();
+                    sent += body.len();
+                }
+            }
+            sent
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn seek_and_read() {
+        let bytes = std::fs::read("data/2-points.sbet").unwrap();
+        let (url, handle) = serve(bytes.clone());
+        let http = HttpReader::new(&url).unwrap();
+        assert_eq!(http.len(), 272);
+        let mut reader = Reader::new(http);
+        reader.seek(1).unwrap();
+        let point = reader.read_one().unwrap().unwrap();
+        assert_eq!(point, crate::records(&bytes).nth(1).unwrap());
+        assert!(reader.read_one().unwrap().is_none());
+        assert_eq!(handle.join().unwrap(), 136);
+    }
+
+    #[test]
+    fn s3() {
+        assert_eq!(
+            super::resolve("s3://bucket/path/to/file.sbet"),
+            "https://bucket.s3.amazonaws.com/path/to/file.sbet"
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use crate::{Point, Trajectory};
+
+impl Trajectory {
+    /// Returns the points on the convex hull of this trajectory's horizontal positions.
+    ///
+    /// The hull is computed in longitude and latitude, which is a good
+    /// footprint for trajectories that don't cross the antimeridian or a
+    /// pole. Points are in counter-clockwise order, starting from the
+    /// westernmost, and the first point isn't repeated at the end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Point, Trajectory};
+    ///
+    /// let trajectory: Trajectory = [(0., 0.), (1., 0.), (0.5, 0.2), (1., 1.), (0., 1.)]
+    ///     .into_iter()
+    ///     .map(|(longitude, latitude)| Point { longitude, latitude, ..Default::default() })
+    ///     .collect::<Vec<_>>()
+    ///     .into();
+    /// assert_eq!(trajectory.convex_hull().len(), 4);
+    /// ```
+    pub fn convex_hull(&self) -> Vec<Point> {
+        convex_hull(self.points())
+    }
+}
+
+/// Returns the convex hull of the points, with Andrew's monotone chain algorithm.
+pub(crate) fn convex_hull(points: &[Point]) -> Vec<Point> {
+    let mut points = points.to_vec();
+    points.sort_by(|a, b| {
+        a.longitude
+            .total_cmp(&b.longitude)
+            .then(a.latitude.total_cmp(&b.latitude))
+    });
+    points.dedup_by(|a, b| a.longitude == b.longitude && a.latitude == b.latitude);
+    if points.len() < 3 {
+        return points;
+    }
+    let cross = |o: &Point, a: &Point, b: &Point| {
+        (a.longitude - o.longitude) * (b.latitude - o.latitude)
+            - (a.latitude - o.latitude) * (b.longitude - o.longitude)
+    };
+    let mut hull: Vec<Point> = Vec::with_capacity(points.len() + 1);
+    // The lower hull, west to east.
+    for point in &points {
+        while hull.len() >= 2 && cross(&hull[hull.len() - 2], &hull[hull.len() - 1], point) <= 0. {
+            hull.pop();
+        }
+        hull.push(*point);
+    }
+    // The upper hull, east to west.
+    let lower = hull.len() + 1;
+    for point in points.iter().rev().skip(1) {
+        while hull.len() >= lower
+            && cross(&hull[hull.len() - 2], &hull[hull.len() - 1], point) <= 0.
+        {
+            hull.pop();
+        }
+        hull.push(*point);
+    }
+    // The last point is the first point.
+    hull.pop();
+    hull
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Point;
+
+    fn point(longitude: f64, latitude: f64) -> Point {
+        Point {
+            longitude,
+            latitude,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn counter_clockwise() {
+        let hull = super::convex_hull(&[
+            point(0., 0.),
+            point(1., 1.),
+            point(0.5, 0.5),
+            point(1., 0.),
+            point(0., 1.),
+            point(0.5, 0.),
+        ]);
+        let corners: Vec<(f64, f64)> = hull
+            .iter()
+            .map(|point| (point.longitude, point.latitude))
+            .collect();
+        assert_eq!(corners, [(0., 0.), (1., 0.), (1., 1.), (0., 1.)]);
+    }
+
+    #[test]
+    fn degenerate() {
+        assert_eq!(super::convex_hull(&[point(0., 0.); 3]).len(), 1);
+        assert!(super::convex_hull(&[]).is_empty());
+    }
+}
@@ -0,0 +1,375 @@
+use crate::{Error, Reader, Result};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Read, Seek, Write},
+    path::{Path, PathBuf},
+};
+
+/// The first bytes of an index file, including the format version.
+const MAGIC: &[u8; 4] = b"SBX1";
+
+/// A sparse time index of an SBET file, stored in a `.sbx` sidecar.
+///
+/// The index holds the time of every `stride`th record, so a reader can
+/// seek close to any time without reading the records before it. The
+/// sidecar is little-endian: the magic bytes `SBX1`, then the record size
+/// (`u32`), stride (`u64`), number of records (`u64`), and source length
+/// in bytes (`u64`), then one time (`f64`) per indexed record. The source
+/// length is checked on [Index::open] to catch stale indices.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::{Index, Reader};
+///
+/// let reader = Reader::from_path("data/2-points.sbet").unwrap();
+/// let index = Index::build(reader, 1).unwrap();
+/// assert_eq!(index.len(), 2);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Index {
+    record_size: u32,
+    stride: u64,
+    records: u64,
+    source_len: u64,
+    times: Vec<f64>,
+}
+
+impl Index {
+    /// The default number of records between indexed records.
+    pub const DEFAULT_STRIDE: u64 = 1000;
+
+    /// Indexes the SBET file at a path and writes the index to its sidecar.
+    ///
+    /// The file must be uncompressed, little-endian records. Uses
+    /// [Index::DEFAULT_STRIDE], and returns the index.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use sbet::Index;
+    ///
+    /// let index = Index::create("trajectory.sbet").unwrap();
+    /// ```
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<Index> {
+        let path = path.as_ref();
+        let reader = Reader::new(BufReader::new(File::open(path)?));
+        let index = Index::build(reader, Index::DEFAULT_STRIDE)?;
+        let mut write = BufWriter::new(File::create(Index::sidecar_path(path))?);
+        index.write(&mut write)?;
+        write.flush()?;
+        Ok(index)
+    }
+
+    /// Reads the sidecar index of the SBET file at a path.
+    ///
+    /// Returns an error if the SBET file's length has changed since it was indexed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use sbet::Index;
+    ///
+    /// let index = Index::open("trajectory.sbet").unwrap();
+    /// ```
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Index> {
+        let path = path.as_ref();
+        let index = Index::read(BufReader::new(File::open(Index::sidecar_path(path))?))?;
+        let len = std::fs::metadata(path)?.len();
+        if len != index.source_len {
+            return Err(Error::InvalidIndex(format!(
+                "indexed {} bytes, but the file has {}",
+                index.source_len, len
+            )));
+        }
+        Ok(index)
+    }
+
+    /// Returns the path of the sidecar index for an SBET file, which is the path with `.sbx` appended.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Index;
+    /// use std::path::Path;
+    ///
+    /// assert_eq!(Index::sidecar_path("data/2-points.sbet"), Path::new("data/2-points.sbet.sbx"));
+    /// ```
+    pub fn sidecar_path<P: AsRef<Path>>(path: P) -> PathBuf {
+        let mut path = path.as_ref().as_os_str().to_owned();
+        path.push(".sbx");
+        path.into()
+    }
+
+    /// Indexes every `stride`th record of a reader, starting with the first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stride` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Index, Reader};
+    ///
+    /// let reader = Reader::from_path("data/2-points.sbet").unwrap();
+    /// let index = Index::build(reader, 1000).unwrap();
+    /// assert_eq!(index.len(), 1);
+    /// ```
+    pub fn build<R: Read>(mut reader: Reader<R>, stride: u64) -> Result<Index> {
+        assert!(stride > 0, "stride must be positive");
+        let record_size = reader.schema().size() as u32;
+        let start = reader.offset();
+        let mut times = Vec::new();
+        let mut records = 0;
+        while let Some(point) = reader.read_one()? {
+            if records % stride == 0 {
+                times.push(point.time);
+            }
+            records += 1;
+        }
+        Ok(Index {
+            record_size,
+            stride,
+            records,
+            source_len: reader.offset() - start,
+            times,
+        })
+    }
+
+    /// Reads an index.
+    ///
+    /// The indexed times are read to the end of the stream, and must match
+    /// the number of records and stride in the header, so a corrupt header
+    /// can't cause a huge allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Index, Reader};
+    ///
+    /// let index = Index::build(Reader::from_path("data/2-points.sbet").unwrap(), 1).unwrap();
+    /// let mut bytes = Vec::new();
+    /// index.write(&mut bytes).unwrap();
+    /// assert_eq!(Index::read(&bytes[..]).unwrap(), index);
+    /// ```
+    pub fn read<R: Read>(mut read: R) -> Result<Index> {
+        let mut magic = [0; 4];
+        read.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(Error::InvalidIndex("not an SBX index".to_string()));
+        }
+        let record_size = read.read_u32::<LittleEndian>()?;
+        let stride = read.read_u64::<LittleEndian>()?;
+        let records = read.read_u64::<LittleEndian>()?;
+        let source_len = read.read_u64::<LittleEndian>()?;
+        if record_size == 0 || stride == 0 {
+            return Err(Error::InvalidIndex(format!(
+                "record size {} and stride {}",
+                record_size, stride
+            )));
+        }
+        let mut bytes = Vec::new();
+        let _ = read.read_to_end(&mut bytes)?;
+        let expected = records.div_ceil(stride).checked_mul(8);
+        if expected != Some(bytes.len() as u64) {
+            return Err(Error::InvalidIndex(format!(
+                "{} records with stride {} don't match {} bytes of times",
+                records,
+                stride,
+                bytes.len()
+            )));
+        }
+        let mut times = vec![0.; bytes.len() / 8];
+        (&bytes[..]).read_f64_into::<LittleEndian>(&mut times)?;
+        Ok(Index {
+            record_size,
+            stride,
+            records,
+            source_len,
+            times,
+        })
+    }
+
+    /// Writes this index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Index, Reader};
+    ///
+    /// let index = Index::build(Reader::from_path("data/2-points.sbet").unwrap(), 1).unwrap();
+    /// let mut bytes = Vec::new();
+    /// index.write(&mut bytes).unwrap();
+    /// assert_eq!(bytes.len(), 48);
+    /// ```
+    pub fn write<W: Write>(&self, mut write: W) -> Result<()> {
+        write.write_all(MAGIC)?;
+        write.write_u32::<LittleEndian>(self.record_size)?;
+        write.write_u64::<LittleEndian>(self.stride)?;
+        write.write_u64::<LittleEndian>(self.records)?;
+        write.write_u64::<LittleEndian>(self.source_len)?;
+        for &time in &self.times {
+            write.write_f64::<LittleEndian>(time)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the number of indexed records.
+    pub fn len(&self) -> usize {
+        self.times.len()
+    }
+
+    /// Returns true if no records are indexed.
+    pub fn is_empty(&self) -> bool {
+        self.times.is_empty()
+    }
+
+    /// Returns the number of records between indexed records.
+    pub fn stride(&self) -> u64 {
+        self.stride
+    }
+
+    /// Returns the number of records in the indexed file.
+    pub fn records(&self) -> u64 {
+        self.records
+    }
+
+    /// Returns the record size, in bytes, of the indexed file.
+    pub fn record_size(&self) -> u32 {
+        self.record_size
+    }
+
+    /// Returns the index of the last indexed record at or before a time, or zero if the time is before them all.
+    ///
+    /// The records are assumed to be sorted by time, so reading forward
+    /// from the returned record reaches the time within one stride.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Index, Reader};
+    ///
+    /// let index = Index::build(Reader::from_path("data/2-points.sbet").unwrap(), 1).unwrap();
+    /// assert_eq!(index.lookup(0.), 0);
+    /// assert_eq!(index.lookup(f64::INFINITY), 1);
+    /// ```
+    pub fn lookup(&self, time: f64) -> u64 {
+        let position = self.times.partition_point(|&indexed| indexed <= time);
+        position.saturating_sub(1) as u64 * self.stride
+    }
+
+    /// Returns an error if this index was built for records of a different size than the reader's.
+    pub(crate) fn check_record_size<R: Read>(&self, reader: &Reader<R>) -> Result<()> {
+        let record_size = reader.schema().size();
+        if self.record_size as usize != record_size {
+            return Err(Error::InvalidIndex(format!(
+                "indexed {}-byte records, but the reader has {}-byte records",
+                self.record_size, record_size
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read + Seek> Reader<R> {
+    /// Seeks to the last indexed record at or before a time.
+    ///
+    /// The next point read is at most one stride before the time. Combine
+    /// with [Reader::filter_by_time] to read a window from a large file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [Error::InvalidIndex] if the index was built for records of a
+    /// different size than this reader's schema.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Index, Reader};
+    /// use std::fs::File;
+    ///
+    /// let index = Index::build(Reader::from_path("data/2-points.sbet").unwrap(), 1).unwrap();
+    /// let mut reader = Reader::new(File::open("data/2-points.sbet").unwrap());
+    /// reader.seek_to_time(&index, 151631.008).unwrap();
+    /// assert_eq!(reader.index(), 1);
+    /// ```
+    pub fn seek_to_time(&mut self, index: &Index, time: f64) -> Result<()> {
+        index.check_record_size(self)?;
+        self.seek(index.lookup(time))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Index;
+    use crate::{Column, Error, Field, Point, Reader, RecordSchema, TimeWindow};
+    use std::io::Cursor;
+
+    #[test]
+    fn seek_and_filter() {
+        let points: Vec<Point> = (0..95)
+            .map(|i| Point {
+                time: f64::from(i),
+                ..Default::default()
+            })
+            .collect();
+        let bytes = crate::to_vec(&points);
+        let index = Index::build(Reader::new(&bytes[..]), 10).unwrap();
+        assert_eq!(index.len(), 10);
+        assert_eq!(index.records(), 95);
+        assert_eq!(index.lookup(57.5), 50);
+        assert_eq!(index.lookup(-1.), 0);
+        assert_eq!(index.lookup(1000.), 90);
+
+        let mut reader = Reader::new(Cursor::new(bytes));
+        reader.seek_to_time(&index, 57.).unwrap();
+        let times: Vec<f64> = reader
+            .filter_by_time(TimeWindow::new(57., 59.))
+            .map(|result| result.unwrap().time)
+            .collect();
+        assert_eq!(times, [57., 58., 59.]);
+    }
+
+    #[test]
+    fn sidecar() {
+        let directory = std::env::temp_dir().join(format!("sbet-index-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("trajectory.sbet");
+        std::fs::copy("data/2-points.sbet", &path).unwrap();
+        let index = Index::create(&path).unwrap();
+        assert_eq!(Index::open(&path).unwrap(), index);
+        std::fs::write(&path, [0; 136]).unwrap();
+        assert!(Index::open(&path).is_err());
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn corrupt_header() {
+        let index = Index::build(Reader::from_path("data/2-points.sbet").unwrap(), 1).unwrap();
+        let mut bytes = Vec::new();
+        index.write(&mut bytes).unwrap();
+        let mut huge = bytes.clone();
+        huge[16..24].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(Index::read(&huge[..]).is_err());
+        let mut zero_stride = bytes.clone();
+        zero_stride[8..16].copy_from_slice(&0u64.to_le_bytes());
+        assert!(Index::read(&zero_stride[..]).is_err());
+        assert!(Index::read(&bytes[..bytes.len() - 1]).is_err());
+        assert_eq!(Index::read(&bytes[..]).unwrap(), index);
+    }
+
+    #[test]
+    fn record_size() {
+        let points = [Point::default(); 3];
+        let bytes = crate::to_vec(&points);
+        let index = Index::build(Reader::new(&bytes[..]), 1).unwrap();
+        let schema = RecordSchema::new(vec![Column::F64(Field::Time), Column::Skip(8)]);
+        let mut reader = Reader::with_schema(Cursor::new(bytes), schema);
+        assert!(matches!(
+            reader.seek_to_time(&index, 0.).unwrap_err(),
+            Error::InvalidIndex(_)
+        ));
+    }
+}
@@ -0,0 +1,173 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Read},
+    path::Path,
+};
+
+/// An input stream that transparently decompresses gzip and zstd data.
+///
+/// The compression format is detected from the first bytes of the stream,
+/// not from a file extension. Gzip is only detected from a whole, valid
+/// header, since an uncompressed record's time can start with the gzip
+/// magic bytes. Gzip requires the `gzip` feature and zstd
+/// the `zstd` feature; without them, compressed data is read as-is.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::{Input, Reader};
+///
+/// let input = Input::open("data/2-points.sbet").unwrap();
+/// assert!(!input.is_compressed());
+/// assert_eq!(Reader::new(input).count(), 2);
+/// ```
+pub struct Input {
+    inner: Inner,
+}
+
+enum Inner {
+    Plain(Box<dyn BufRead + Send>),
+    #[cfg(feature = "gzip")]
+    Gzip(flate2::bufread::MultiGzDecoder<Box<dyn BufRead + Send>>),
+    #[cfg(feature = "zstd")]
+    Zstd(zstd::Decoder<'static, Box<dyn BufRead + Send>>),
+}
+
+#[cfg(feature = "gzip")]
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+/// The length of a gzip header without optional fields.
+#[cfg(feature = "gzip")]
+const GZIP_HEADER_LEN: usize = 10;
+/// The gzip compression method for deflate, the only one defined.
+#[cfg(feature = "gzip")]
+const GZIP_DEFLATE: u8 = 8;
+/// The gzip flag bits that are reserved, and must be zero.
+#[cfg(feature = "gzip")]
+const GZIP_RESERVED_FLAGS: u8 = 0xe0;
+#[cfg(feature = "zstd")]
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+impl Input {
+    /// Opens a file, decompressing it if needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Input;
+    ///
+    /// let input = Input::open("data/2-points.sbet").unwrap();
+    /// ```
+    pub fn open<P: AsRef<Path>>(path: P) -> std::io::Result<Input> {
+        Input::new(File::open(path)?)
+    }
+
+    /// Wraps a stream, decompressing it if needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Input;
+    ///
+    /// let input = Input::new(std::io::stdin()).unwrap();
+    /// ```
+    pub fn new<R: Read + Send + 'static>(read: R) -> std::io::Result<Input> {
+        let mut read: Box<dyn BufRead + Send> = Box::new(BufReader::new(read));
+        let magic = read.fill_buf()?;
+        #[cfg(feature = "gzip")]
+        if is_gzip(magic) {
+            return Ok(Input {
+                inner: Inner::Gzip(flate2::bufread::MultiGzDecoder::new(read)),
+            });
+        }
+        #[cfg(feature = "zstd")]
+        if magic.starts_with(&ZSTD_MAGIC) {
+            return Ok(Input {
+                inner: Inner::Zstd(zstd::Decoder::with_buffer(read)?),
+            });
+        }
+        let _ = magic;
+        Ok(Input {
+            inner: Inner::Plain(read),
+        })
+    }
+
+    /// Returns true if the stream is being decompressed.
+    pub fn is_compressed(&self) -> bool {
+        !matches!(self.inner, Inner::Plain(_))
+    }
+}
+
+/// Returns true if the bytes start with a gzip header: the magic bytes, the deflate method, and no reserved flags.
+#[cfg(feature = "gzip")]
+fn is_gzip(bytes: &[u8]) -> bool {
+    bytes.len() >= GZIP_HEADER_LEN
+        && bytes.starts_with(&GZIP_MAGIC)
+        && bytes[2] == GZIP_DEFLATE
+        && bytes[3] & GZIP_RESERVED_FLAGS == 0
+}
+
+impl Read for Input {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match &mut self.inner {
+            Inner::Plain(read) => read.read(buf),
+            #[cfg(feature = "gzip")]
+            Inner::Gzip(read) => read.read(buf),
+            #[cfg(feature = "zstd")]
+            Inner::Zstd(read) => read.read(buf),
+        }
+    }
+}
+
+impl std::fmt::Debug for Input {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let format = match self.inner {
+            Inner::Plain(_) => "plain",
+            #[cfg(feature = "gzip")]
+            Inner::Gzip(_) => "gzip",
+            #[cfg(feature = "zstd")]
+            Inner::Zstd(_) => "zstd",
+        };
+        f.debug_struct("Input").field("format", &format).finish()
+    }
+}
+
+#[cfg(all(test, feature = "gzip", feature = "zstd"))]
+mod tests {
+    use super::Input;
+    use crate::{Point, Reader};
+    use std::io::Write;
+
+    #[test]
+    fn decompress() {
+        let bytes = std::fs::read("data/2-points.sbet").unwrap();
+        let mut gzip = flate2::write::GzEncoder::new(Vec::new(), Default::default());
+        gzip.write_all(&bytes).unwrap();
+        let gzip = gzip.finish().unwrap();
+        let zstd = zstd::encode_all(&bytes[..], 0).unwrap();
+        for compressed in [gzip, zstd] {
+            let input = Input::new(std::io::Cursor::new(compressed)).unwrap();
+            assert!(input.is_compressed());
+            let points = Reader::new(input)
+                .collect::<crate::Result<Vec<_>>>()
+                .unwrap();
+            assert_eq!(crate::to_vec(&points), bytes);
+        }
+    }
+
+    #[test]
+    fn gzip_magic_time() {
+        // This time's first two little-endian bytes are the gzip magic bytes.
+        let point = Point {
+            time: 151631.00000103653,
+            ..Default::default()
+        };
+        let bytes = crate::to_vec(&[point]);
+        assert_eq!(bytes[..2], [0x1f, 0x8b]);
+        let input = Input::new(std::io::Cursor::new(bytes)).unwrap();
+        assert!(!input.is_compressed());
+        let points = Reader::new(input)
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(points, [point]);
+    }
+}
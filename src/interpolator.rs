@@ -0,0 +1,208 @@
+use crate::{Error, Point, Result};
+use alloc::vec::Vec;
+use core::ops::ControlFlow;
+
+/// Interpolates points at arbitrary times within a sorted slice of points.
+///
+/// Lookups use a binary search, so an interpolator is suitable for
+/// interpolating many times within a large trajectory.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::{Interpolator, Point};
+///
+/// let points = [
+///     Point { time: 0., ..Default::default() },
+///     Point { time: 1., altitude: 2., ..Default::default() },
+/// ];
+/// let interpolator = Interpolator::new(&points).unwrap();
+/// let point = interpolator.interpolate(0.5).unwrap();
+/// assert_eq!(point.altitude, 1.);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Interpolator<'a> {
+    points: &'a [Point],
+}
+
+impl<'a> Interpolator<'a> {
+    /// Creates a new interpolator for a slice of points sorted by time.
+    ///
+    /// Sortedness isn't checked, and unsorted points give wrong results. Use
+    /// [is_sorted](crate::is_sorted) to check and
+    /// [sort_by_time](crate::sort_by_time) to sort.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slice is empty or only has one point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Interpolator, Point};
+    ///
+    /// assert!(Interpolator::new(&[]).is_err());
+    /// assert!(Interpolator::new(&[Point::default()]).is_err());
+    /// ```
+    pub fn new(points: &'a [Point]) -> Result<Interpolator<'a>> {
+        match points.len() {
+            0 => Err(Error::NoPoints),
+            1 => Err(Error::OnePoint),
+            _ => Ok(Interpolator { points }),
+        }
+    }
+
+    /// Interpolates a point at a time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the time is before the first point or after the last point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Interpolator, Point};
+    ///
+    /// let points = [
+    ///     Point { time: 1., altitude: 10., ..Default::default() },
+    ///     Point { time: 2., altitude: 20., ..Default::default() },
+    /// ];
+    /// let interpolator = Interpolator::new(&points).unwrap();
+    /// assert_eq!(interpolator.interpolate(1.5).unwrap().altitude, 15.);
+    /// assert!(interpolator.interpolate(2.5).is_err());
+    /// ```
+    pub fn interpolate(&self, time: f64) -> Result<Point> {
+        let first = &self.points[0];
+        let last = &self.points[self.points.len() - 1];
+        if first.time > time || last.time < time || time.is_nan() {
+            return Err(Error::Extrapolation {
+                time,
+                start_time: first.time,
+                end_time: last.time,
+            });
+        }
+        let index = self
+            .points
+            .partition_point(|point| point.time < time)
+            .max(1);
+        Ok(lerp(&self.points[index - 1], &self.points[index], time))
+    }
+
+    /// Interpolates a point at each of many times.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any time is before the first point or after the last point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Interpolator, Point};
+    ///
+    /// let points = [
+    ///     Point { time: 1., altitude: 10., ..Default::default() },
+    ///     Point { time: 2., altitude: 20., ..Default::default() },
+    /// ];
+    /// let interpolator = Interpolator::new(&points).unwrap();
+    /// let interpolated = interpolator.interpolate_all(&[1.25, 1.5]).unwrap();
+    /// assert_eq!(interpolated[1].altitude, 15.);
+    /// ```
+    pub fn interpolate_all(&self, times: &[f64]) -> Result<Vec<Point>> {
+        self.interpolate_all_with_progress(times, |_| ControlFlow::Continue(()))
+    }
+
+    /// Interpolates a point at each of many times, reporting progress as it goes.
+    ///
+    /// `progress` is called with the number of points interpolated so far
+    /// after each point. Return [ControlFlow::Break] from it to stop
+    /// interpolating, which returns [Error::Cancelled].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Error, Interpolator, Point};
+    /// use std::ops::ControlFlow;
+    ///
+    /// let points = [
+    ///     Point { time: 1., ..Default::default() },
+    ///     Point { time: 2., ..Default::default() },
+    /// ];
+    /// let interpolator = Interpolator::new(&points).unwrap();
+    /// let result = interpolator.interpolate_all_with_progress(&[1.25, 1.5, 1.75], |n| {
+    ///     if n < 2 {
+    ///         ControlFlow::Continue(())
+    ///     } else {
+    ///         ControlFlow::Break(())
+    ///     }
+    /// });
+    /// assert!(matches!(result, Err(Error::Cancelled)));
+    /// ```
+    pub fn interpolate_all_with_progress<F>(
+        &self,
+        times: &[f64],
+        mut progress: F,
+    ) -> Result<Vec<Point>>
+    where
+        F: FnMut(u64) -> ControlFlow<()>,
+    {
+        let mut points = Vec::with_capacity(times.len());
+        for &time in times {
+            points.push(self.interpolate(time)?);
+            if progress(points.len() as u64).is_break() {
+                return Err(Error::Cancelled);
+            }
+        }
+        Ok(points)
+    }
+}
+
+/// Linearly interpolates between two points.
+pub(crate) fn lerp(before: &Point, after: &Point, time: f64) -> Point {
+    let factor = (time - before.time) / (after.time - before.time);
+    Point {
+        time,
+        latitude: before.latitude + factor * (after.latitude - before.latitude),
+        longitude: before.longitude + factor * (after.longitude - before.longitude),
+        altitude: before.altitude + factor * (after.altitude - before.altitude),
+        x_velocity: before.x_velocity + factor * (after.x_velocity - before.x_velocity),
+        y_velocity: before.y_velocity + factor * (after.y_velocity - before.y_velocity),
+        z_velocity: before.z_velocity + factor * (after.z_velocity - before.z_velocity),
+        roll: before.roll + factor * (after.roll - before.roll),
+        pitch: before.pitch + factor * (after.pitch - before.pitch),
+        yaw: before.yaw + factor * (after.yaw - before.yaw),
+        wander_angle: before.wander_angle + factor * (after.wander_angle - before.wander_angle),
+        x_acceleration: before.x_acceleration
+            + factor * (after.x_acceleration - before.x_acceleration),
+        y_acceleration: before.y_acceleration
+            + factor * (after.y_acceleration - before.y_acceleration),
+        z_acceleration: before.z_acceleration
+            + factor * (after.z_acceleration - before.z_acceleration),
+        x_angular_rate: before.x_angular_rate
+            + factor * (after.x_angular_rate - before.x_angular_rate),
+        y_angular_rate: before.y_angular_rate
+            + factor * (after.y_angular_rate - before.y_angular_rate),
+        z_angular_rate: before.z_angular_rate
+            + factor * (after.z_angular_rate - before.z_angular_rate),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Interpolator;
+    use crate::Point;
+
+    #[test]
+    fn many_points() {
+        let points: Vec<Point> = (0..100)
+            .map(|i| Point {
+                time: f64::from(i),
+                altitude: f64::from(i) * 2.,
+                ..Default::default()
+            })
+            .collect();
+        let interpolator = Interpolator::new(&points).unwrap();
+        assert_eq!(interpolator.interpolate(0.).unwrap().altitude, 0.);
+        assert_eq!(interpolator.interpolate(42.25).unwrap().altitude, 84.5);
+        assert_eq!(interpolator.interpolate(99.).unwrap().altitude, 198.);
+    }
+}
@@ -0,0 +1,172 @@
+use crate::{Field, Point, Result};
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+/// Writes points as JSON Lines, one object per point with a member for each field.
+///
+/// Members are named after the [Point] members and are written in record
+/// order. Angles are in radians, as stored. Non-finite values are written as
+/// `null`, since JSON has no representation for them.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::{JsonWriter, Point};
+///
+/// let mut writer = JsonWriter::new(Vec::new());
+/// writer.write_one(&Point { time: 1.5, ..Default::default() }).unwrap();
+/// let json = String::from_utf8(writer.into_inner()).unwrap();
+/// assert!(json.starts_with("{\"time\":1.5,\"latitude\":0,"));
+/// assert!(json.ends_with("}\n"));
+/// ```
+#[derive(Debug)]
+pub struct JsonWriter<W: Write> {
+    write: W,
+}
+
+impl<W: Write> JsonWriter<W> {
+    /// Creates a JSON Lines writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::JsonWriter;
+    ///
+    /// let writer = JsonWriter::new(std::io::stdout());
+    /// ```
+    pub fn new(write: W) -> JsonWriter<W> {
+        JsonWriter { write }
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.write
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.write
+    }
+
+    /// Consumes this writer, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.write
+    }
+
+    /// Writes one point as a line of JSON.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{JsonWriter, Point};
+    ///
+    /// let mut writer = JsonWriter::new(std::io::stdout());
+    /// writer.write_one(&Point::default()).unwrap();
+    /// ```
+    pub fn write_one(&mut self, point: &Point) -> Result<()> {
+        let mut separator = "{";
+        for field in Field::ALL {
+            write!(self.write, "{}\"{}\":", separator, field.name())?;
+            write_number(&mut self.write, point.get(field))?;
+            separator = ",";
+        }
+        writeln!(self.write, "}}")?;
+        Ok(())
+    }
+
+    /// Writes many points, one line each.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{JsonWriter, Point};
+    ///
+    /// let mut writer = JsonWriter::new(Vec::new());
+    /// writer.write_all(&[Point::default(); 3]).unwrap();
+    /// let json = String::from_utf8(writer.into_inner()).unwrap();
+    /// assert_eq!(json.lines().count(), 3);
+    /// ```
+    pub fn write_all(&mut self, points: &[Point]) -> Result<()> {
+        for point in points {
+            self.write_one(point)?;
+        }
+        Ok(())
+    }
+
+    /// Flushes the underlying writer.
+    pub fn flush(&mut self) -> Result<()> {
+        self.write.flush()?;
+        Ok(())
+    }
+
+    /// Flushes and consumes this writer, returning the underlying writer.
+    ///
+    /// Unlike dropping a [BufWriter], this reports errors from the final flush.
+    pub fn finish(mut self) -> Result<W> {
+        self.flush()?;
+        Ok(self.write)
+    }
+}
+
+impl JsonWriter<BufWriter<File>> {
+    /// Creates a JSON Lines writer for the file at the path.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use sbet::JsonWriter;
+    ///
+    /// let writer = JsonWriter::from_path("outfile.jsonl").unwrap();
+    /// ```
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<JsonWriter<BufWriter<File>>> {
+        File::create(path)
+            .map(|f| JsonWriter::new(BufWriter::new(f)))
+            .map_err(|e| e.into())
+    }
+}
+
+/// Writes a number as JSON, or `null` if it isn't finite.
+pub(crate) fn write_number<W: Write>(write: &mut W, value: f64) -> std::io::Result<()> {
+    if value.is_finite() {
+        write!(write, "{}", value)
+    } else {
+        write!(write, "null")
+    }
+}
+
+/// Writes a string as JSON, escaping quotes, backslashes, and control characters.
+pub(crate) fn write_string<W: Write>(write: &mut W, s: &str) -> std::io::Result<()> {
+    write!(write, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(write, "\\\"")?,
+            '\\' => write!(write, "\\\\")?,
+            c if c.is_control() => write!(write, "\\u{:04x}", u32::from(c))?,
+            c => write!(write, "{}", c)?,
+        }
+    }
+    write!(write, "\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JsonWriter;
+    use crate::Point;
+
+    #[test]
+    fn non_finite() {
+        let mut writer = JsonWriter::new(Vec::new());
+        writer
+            .write_one(&Point {
+                roll: f64::NAN,
+                pitch: f64::INFINITY,
+                ..Default::default()
+            })
+            .unwrap();
+        let json = String::from_utf8(writer.into_inner()).unwrap();
+        assert!(json.contains("\"roll\":null,\"pitch\":null,"));
+    }
+}
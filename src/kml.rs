@@ -0,0 +1,334 @@
+//! KML output for viewing trajectories in Google Earth.
+//!
+//! # Examples
+//!
+//! ```
+//! use sbet::kml::{Builder, Coloring};
+//! use sbet::Point;
+//!
+//! let points = [Point::default(); 3];
+//! let kml = Builder::new()
+//!     .name("Flight 1")
+//!     .coloring(Coloring::ByAltitude)
+//!     .to_string(&points)
+//!     .unwrap();
+//! assert!(kml.contains("<name>Flight 1</name>"));
+//! ```
+
+use crate::{Error, Point, Result};
+use std::{io::Write, str::FromStr};
+
+/// The number of colors in the altitude and speed gradients.
+const GRADIENT_STEPS: usize = 8;
+
+/// How the track is colored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Coloring {
+    /// One color, as red, green, and blue.
+    Solid([u8; 3]),
+
+    /// A gradient from blue at the lowest altitude to red at the highest.
+    ByAltitude,
+
+    /// A gradient from blue at the lowest horizontal speed to red at the highest.
+    BySpeed,
+}
+
+impl Default for Coloring {
+    fn default() -> Coloring {
+        Coloring::Solid([255, 0, 0])
+    }
+}
+
+impl FromStr for Coloring {
+    type Err = Error;
+
+    /// Parses "altitude", "speed", or a hex color like "#ff8800".
+    fn from_str(s: &str) -> Result<Coloring> {
+        match s {
+            "altitude" => Ok(Coloring::ByAltitude),
+            "speed" => Ok(Coloring::BySpeed),
+            _ => s
+                .strip_prefix('#')
+                .filter(|hex| hex.len() == 6)
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .map(|rgb| {
+                    let [_, red, green, blue] = rgb.to_be_bytes();
+                    Coloring::Solid([red, green, blue])
+                })
+                .ok_or_else(|| Error::UnknownColoring(s.to_string())),
+        }
+    }
+}
+
+/// Builds a KML document with a track through a slice of points.
+///
+/// The track is a `LineString`, or a time-stamped `gx:Track` if a GPS week
+/// is set with [Builder::timestamps]. Altitudes are absolute.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::kml::Builder;
+/// use sbet::Point;
+///
+/// let mut kml = Vec::new();
+/// Builder::new().write(&[Point::default(); 2], &mut kml).unwrap();
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Builder {
+    name: Option<String>,
+    coloring: Coloring,
+    width: f64,
+    decimate: usize,
+    #[cfg(feature = "chrono")]
+    week: Option<u32>,
+}
+
+impl Default for Builder {
+    fn default() -> Builder {
+        Builder::new()
+    }
+}
+
+impl Builder {
+    /// Creates a builder for a solid red track, two pixels wide.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::kml::Builder;
+    ///
+    /// let builder = Builder::new();
+    /// ```
+    pub fn new() -> Builder {
+        Builder {
+            name: None,
+            coloring: Coloring::default(),
+            width: 2.,
+            decimate: 1,
+            #[cfg(feature = "chrono")]
+            week: None,
+        }
+    }
+
+    /// Sets the document name.
+    pub fn name(mut self, name: &str) -> Builder {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Sets how the track is colored.
+    pub fn coloring(mut self, coloring: Coloring) -> Builder {
+        self.coloring = coloring;
+        self
+    }
+
+    /// Sets the line width in pixels.
+    pub fn width(mut self, width: f64) -> Builder {
+        self.width = width;
+        self
+    }
+
+    /// Only uses every `n`th point, starting with the first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    pub fn decimate(mut self, n: usize) -> Builder {
+        assert!(n > 0, "decimation must be positive");
+        self.decimate = n;
+        self
+    }
+
+    /// Writes a time-stamped track, with point times in seconds-of-week of this GPS week.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::kml::Builder;
+    /// use sbet::Point;
+    ///
+    /// let kml = Builder::new()
+    ///     .timestamps(2264)
+    ///     .to_string(&[Point { time: 396018., ..Default::default() }])
+    ///     .unwrap();
+    /// assert!(kml.contains("<when>2023-06-01T14:00:00Z</when>"));
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn timestamps(mut self, week: u32) -> Builder {
+        self.week = Some(week);
+        self
+    }
+
+    /// Writes the KML document for the points.
+    pub fn write<W: Write>(&self, points: &[Point], mut write: W) -> Result<()> {
+        let points: Vec<&Point> = points.iter().step_by(self.decimate).collect();
+        writeln!(write, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            write,
+            r#"<kml xmlns="http://www.opengis.net/kml/2.2" xmlns:gx="http://www.google.com/kml/ext/2.2">"#
+        )?;
+        writeln!(write, "<Document>")?;
+        if let Some(name) = &self.name {
+            writeln!(write, "<name>{}</name>", escape(name))?;
+        }
+        let colors = match self.coloring {
+            Coloring::Solid(rgb) => vec![rgb],
+            Coloring::ByAltitude | Coloring::BySpeed => (0..GRADIENT_STEPS)
+                .map(|step| {
+                    let red = (255 * step / (GRADIENT_STEPS - 1)) as u8;
+                    [red, 0, 255 - red]
+                })
+                .collect(),
+        };
+        for (i, [red, green, blue]) in colors.iter().enumerate() {
+            writeln!(
+                write,
+                r#"<Style id="line{}"><LineStyle><color>ff{:02x}{:02x}{:02x}</color><width>{}</width></LineStyle></Style>"#,
+                i, blue, green, red, self.width
+            )?;
+        }
+        let steps = self.steps(&points);
+        let mut start = 0;
+        while start < points.len() {
+            let step = steps[start];
+            let mut end = start + 1;
+            while end < points.len() && steps[end] == step {
+                end += 1;
+            }
+            // Runs share their last point with the next run, so the track has no gaps.
+            let run = &points[start..(end + 1).min(points.len())];
+            self.write_placemark(&mut write, run, step)?;
+            start = end;
+        }
+        writeln!(write, "</Document>")?;
+        writeln!(write, "</kml>")?;
+        Ok(())
+    }
+
+    /// Returns the KML document for the points as a string.
+    pub fn to_string(&self, points: &[Point]) -> Result<String> {
+        let mut bytes = Vec::new();
+        self.write(points, &mut bytes)?;
+        Ok(String::from_utf8(bytes).expect("KML is valid UTF-8"))
+    }
+
+    /// Returns the gradient step of each point.
+    fn steps(&self, points: &[&Point]) -> Vec<usize> {
+        let value: fn(&Point) -> f64 = match self.coloring {
+            Coloring::Solid(_) => return vec![0; points.len()],
+            Coloring::ByAltitude => |point| point.altitude,
+            Coloring::BySpeed => Point::horizontal_speed,
+        };
+        let (min, max) = points
+            .iter()
+            .map(|point| value(point))
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+                (min.min(value), max.max(value))
+            });
+        points
+            .iter()
+            .map(|point| {
+                if max > min {
+                    let fraction = (value(point) - min) / (max - min);
+                    ((fraction * GRADIENT_STEPS as f64) as usize).min(GRADIENT_STEPS - 1)
+                } else {
+                    0
+                }
+            })
+            .collect()
+    }
+
+    fn write_placemark<W: Write>(
+        &self,
+        write: &mut W,
+        points: &[&Point],
+        step: usize,
+    ) -> Result<()> {
+        write!(write, "<Placemark><styleUrl>#line{}</styleUrl>", step)?;
+        #[cfg(feature = "chrono")]
+        if let Some(week) = self.week {
+            write!(write, "<gx:Track><altitudeMode>absolute</altitudeMode>")?;
+            for point in points {
+                let utc = crate::time::to_utc(week, point.time)
+                    .ok_or(Error::TimeOutOfRange(point.time))?;
+                write!(
+                    write,
+                    "<when>{}</when>",
+                    utc.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true)
+                )?;
+            }
+            for point in points {
+                write!(
+                    write,
+                    "<gx:coord>{} {} {}</gx:coord>",
+                    point.longitude.to_degrees(),
+                    point.latitude.to_degrees(),
+                    point.altitude
+                )?;
+            }
+            writeln!(write, "</gx:Track></Placemark>")?;
+            return Ok(());
+        }
+        write!(
+            write,
+            "<LineString><altitudeMode>absolute</altitudeMode><coordinates>"
+        )?;
+        for (i, point) in points.iter().enumerate() {
+            if i > 0 {
+                write!(write, " ")?;
+            }
+            write!(
+                write,
+                "{},{},{}",
+                point.longitude.to_degrees(),
+                point.latitude.to_degrees(),
+                point.altitude
+            )?;
+        }
+        writeln!(write, "</coordinates></LineString></Placemark>")?;
+        Ok(())
+    }
+}
+
+/// Escapes text for XML element content.
+pub(crate) fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Builder, Coloring};
+    use crate::Point;
+
+    #[test]
+    fn parse_coloring() {
+        assert_eq!("speed".parse::<Coloring>().unwrap(), Coloring::BySpeed);
+        assert_eq!(
+            "#ff8800".parse::<Coloring>().unwrap(),
+            Coloring::Solid([0xff, 0x88, 0x00])
+        );
+        assert!("ff8800".parse::<Coloring>().is_err());
+    }
+
+    #[test]
+    fn gradient_runs() {
+        let points: Vec<Point> = (0..4)
+            .map(|i| Point {
+                altitude: if i < 2 { 0. } else { 100. },
+                ..Default::default()
+            })
+            .collect();
+        let kml = Builder::new()
+            .coloring(Coloring::ByAltitude)
+            .to_string(&points)
+            .unwrap();
+        assert_eq!(kml.matches("<Placemark>").count(), 2);
+        assert!(kml.contains("<styleUrl>#line0</styleUrl>"));
+        assert!(kml.contains("<styleUrl>#line7</styleUrl>"));
+        assert!(kml.contains("<coordinates>0,0,0 0,0,0 0,0,100</coordinates>"));
+    }
+}
@@ -0,0 +1,465 @@
+//! LAS and LAZ integration, behind the `las` feature.
+//!
+//! Lidar returns are matched back to the platform trajectory by GPS time.
+//! LAS files store either GPS seconds-of-week or adjusted standard GPS
+//! time, so times are converted to the trajectory's
+//! [TimeConvention](crate::time::TimeConvention) first. Reading LAZ files
+//! requires the `laz` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use sbet::{las::Lookup, Point};
+//!
+//! let trajectory = [
+//!     Point { time: 396018., altitude: 1000., ..Default::default() },
+//!     Point { time: 396019., altitude: 1010., ..Default::default() },
+//! ];
+//! let header = las::Header::default();
+//! let lookup = Lookup::new(&trajectory, &header).unwrap();
+//! assert_eq!(lookup.get(396018.5).unwrap().altitude, 1005.);
+//! ```
+
+use crate::{
+    time::{TimeConvention, SECONDS_PER_WEEK},
+    Error, Interpolator, Point, Result, Writer,
+};
+use las::{GpsTimeType, Header};
+use std::{io::Write, str::FromStr};
+
+const CHUNK_SIZE: u64 = 4096;
+
+/// How the x, y, and z of LAS points are defined.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::las::Coordinates;
+///
+/// assert_eq!("geographic".parse::<Coordinates>().unwrap(), Coordinates::Geographic);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Coordinates {
+    /// Longitude and latitude in degrees and height above the WGS84 ellipsoid in meters.
+    #[default]
+    Geographic,
+
+    /// WGS84 UTM easting and northing, and height above the ellipsoid, in meters.
+    #[cfg(feature = "utm")]
+    Utm {
+        /// The zone number, from 1 to 60.
+        zone: u8,
+
+        /// True for the northern hemisphere.
+        north: bool,
+    },
+}
+
+/// The geometry of the line of sight from the sensor to one lidar return.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScanGeometry {
+    /// The distance from the sensor to the return, in meters.
+    pub range: f64,
+
+    /// The angle between the line of sight and the platform's down axis, in radians.
+    ///
+    /// The down axis tilts with the platform's roll and pitch, so this is
+    /// the angle in the sensor's frame, not from the vertical.
+    pub off_nadir: f64,
+}
+
+/// Interpolates the trajectory at the GPS times of LAS points.
+#[derive(Clone, Copy, Debug)]
+pub struct Lookup<'a> {
+    interpolator: Interpolator<'a>,
+    las_convention: TimeConvention,
+    trajectory_convention: TimeConvention,
+    week_start: f64,
+}
+
+/// The number of LAS points that were and weren't matched to the trajectory by [attach] or [scan_geometry].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Attached {
+    /// The number of points within the trajectory's time range.
+    pub matched: u64,
+
+    /// The number of points outside of the trajectory's time range.
+    pub unmatched: u64,
+}
+
+impl<'a> Lookup<'a> {
+    /// Creates a lookup into a time-sorted trajectory for points in a LAS file with this header.
+    ///
+    /// The trajectory's time convention is detected from its times, and is
+    /// assumed to be seconds-of-week if it can't be detected.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the trajectory has fewer than two points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::las::Lookup;
+    ///
+    /// assert!(Lookup::new(&[], &las::Header::default()).is_err());
+    /// ```
+    pub fn new(trajectory: &'a [Point], header: &Header) -> Result<Lookup<'a>> {
+        let interpolator = Interpolator::new(trajectory)?;
+        let trajectory_convention =
+            TimeConvention::detect(trajectory).unwrap_or(TimeConvention::SecondsOfWeek);
+        // Seconds-of-week LAS times can only be converted to standard time within a known week.
+        let week_start = match trajectory_convention {
+            TimeConvention::Standard | TimeConvention::AdjustedStandard => {
+                let gps_seconds = trajectory_convention.to_gps_seconds(trajectory[0].time, 0.);
+                (gps_seconds / SECONDS_PER_WEEK).floor() * SECONDS_PER_WEEK
+            }
+            TimeConvention::SecondsOfWeek | TimeConvention::SecondsOfDay => 0.,
+        };
+        Ok(Lookup {
+            interpolator,
+            las_convention: match header.gps_time_type() {
+                GpsTimeType::Week => TimeConvention::SecondsOfWeek,
+                GpsTimeType::Standard => TimeConvention::AdjustedStandard,
+            },
+            trajectory_convention,
+            week_start,
+        })
+    }
+
+    /// Converts a LAS GPS time to the trajectory's time convention.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::GpsTimeType;
+    /// use sbet::{las::Lookup, Point};
+    ///
+    /// let trajectory = [
+    ///     Point { time: 396018., ..Default::default() },
+    ///     Point { time: 396019., ..Default::default() },
+    /// ];
+    /// let mut header = las::Builder::default();
+    /// header.gps_time_type = GpsTimeType::Standard;
+    /// let header = header.into_header().unwrap();
+    /// let lookup = Lookup::new(&trajectory, &header).unwrap();
+    /// assert_eq!(lookup.time(369663218.), 396018.);
+    /// ```
+    pub fn time(&self, gps_time: f64) -> f64 {
+        self.las_convention
+            .convert(gps_time, self.week_start, self.trajectory_convention)
+    }
+
+    /// Interpolates the trajectory at a LAS GPS time.
+    ///
+    /// The point's time is in the trajectory's time convention. Returns
+    /// `None` if the time is outside of the trajectory.
+    pub fn get(&self, gps_time: f64) -> Option<Point> {
+        self.interpolator.interpolate(self.time(gps_time)).ok()
+    }
+}
+
+/// Writes an SBET sidecar with the trajectory interpolated at each LAS point's GPS time.
+///
+/// The sidecar has one record per LAS point, in the same order, so the
+/// `n`th record is the platform at the `n`th return. Points outside of the
+/// trajectory's time range get a record with their time and every other
+/// field set to NaN. The sidecar is written with [Writer], which is
+/// finished before returning.
+///
+/// # Errors
+///
+/// Returns an error if the LAS point format has no GPS time, the trajectory
+/// has fewer than two points, or on any read or write error.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::Point;
+/// use std::io::Cursor;
+///
+/// let mut header = las::Builder::default();
+/// header.point_format = las::point::Format::new(1).unwrap();
+/// let mut writer = las::Writer::new(Cursor::new(Vec::new()), header.into_header().unwrap()).unwrap();
+/// writer.write_point(las::Point { gps_time: Some(396018.5), ..Default::default() }).unwrap();
+/// let mut reader = las::Reader::new(writer.into_inner().unwrap()).unwrap();
+///
+/// let trajectory = [
+///     Point { time: 396018., ..Default::default() },
+///     Point { time: 396019., ..Default::default() },
+/// ];
+/// let mut sidecar = Vec::new();
+/// let attached = sbet::las::attach(&mut reader, &trajectory, &mut sidecar).unwrap();
+/// assert_eq!(attached.matched, 1);
+/// assert_eq!(sidecar.len(), sbet::RECORD_SIZE);
+/// ```
+pub fn attach<W: Write>(
+    reader: &mut las::Reader,
+    trajectory: &[Point],
+    write: W,
+) -> Result<Attached> {
+    if !reader.header().point_format().has_gps_time {
+        return Err(Error::MissingGpsTime);
+    }
+    let lookup = Lookup::new(trajectory, reader.header())?;
+    let mut writer = Writer::new(write);
+    let mut attached = Attached::default();
+    loop {
+        let points = reader.read_points(CHUNK_SIZE)?;
+        if points.is_empty() {
+            break;
+        }
+        for gps_time in points.gps_time().into_iter().flatten() {
+            let point = match lookup.get(gps_time) {
+                Some(point) => {
+                    attached.matched += 1;
+                    point
+                }
+                None => {
+                    attached.unmatched += 1;
+                    unmatched(lookup.time(gps_time))
+                }
+            };
+            writer.write_one(point)?;
+        }
+    }
+    let _ = writer.finish()?;
+    Ok(attached)
+}
+
+impl FromStr for Coordinates {
+    type Err = Error;
+
+    /// Parses "geographic", or a UTM zone number and hemisphere like "10N" or "33S" if the `utm` feature is enabled.
+    fn from_str(s: &str) -> Result<Coordinates> {
+        if s == "geographic" {
+            return Ok(Coordinates::Geographic);
+        }
+        #[cfg(feature = "utm")]
+        if let Some((zone, north)) = s
+            .strip_suffix(['N', 'n'])
+            .map(|zone| (zone, true))
+            .or_else(|| s.strip_suffix(['S', 's']).map(|zone| (zone, false)))
+        {
+            if let Some(zone) = zone.parse().ok().filter(|zone| (1..=60).contains(zone)) {
+                return Ok(Coordinates::Utm { zone, north });
+            }
+        }
+        Err(Error::UnknownCoordinates(s.to_string()))
+    }
+}
+
+impl Coordinates {
+    /// Returns a point with the latitude, longitude, and altitude of a LAS position.
+    ///
+    /// Returns `None` if the position can't be converted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::las::Coordinates;
+    ///
+    /// let point = Coordinates::Geographic.to_point(-105., 40., 1600.).unwrap();
+    /// assert_eq!(point.latitude, 40f64.to_radians());
+    /// ```
+    pub fn to_point(&self, x: f64, y: f64, z: f64) -> Option<Point> {
+        let (latitude, longitude) = match *self {
+            Coordinates::Geographic => (y.to_radians(), x.to_radians()),
+            #[cfg(feature = "utm")]
+            Coordinates::Utm { zone, north } => {
+                let (latitude, longitude) =
+                    ::utm::wsg84_utm_to_lat_lon(x, y, zone, if north { 'N' } else { 'M' }).ok()?;
+                (latitude.to_radians(), longitude.to_radians())
+            }
+        };
+        Some(Point {
+            latitude,
+            longitude,
+            altitude: z,
+            ..Default::default()
+        })
+    }
+}
+
+impl ScanGeometry {
+    /// Computes the geometry from an interpolated sensor point to a return's position.
+    ///
+    /// Only the return's latitude, longitude, and altitude are used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{las::ScanGeometry, Point};
+    ///
+    /// let sensor = Point { altitude: 1000., ..Default::default() };
+    /// let geometry = ScanGeometry::new(&sensor, &Point::default());
+    /// assert!((geometry.range - 1000.).abs() < 1e-6);
+    /// assert!(geometry.off_nadir.abs() < 1e-6);
+    /// ```
+    pub fn new(sensor: &Point, target: &Point) -> ScanGeometry {
+        let ned = target.to_ned(sensor);
+        let line_of_sight = [ned.north, ned.east, ned.down];
+        let range = line_of_sight
+            .iter()
+            .map(|value| value * value)
+            .sum::<f64>()
+            .sqrt();
+        let rotation = sensor.rotation_body_to_ned();
+        let cosine = (0..3)
+            .map(|i| rotation[i][2] * line_of_sight[i])
+            .sum::<f64>()
+            / range;
+        ScanGeometry {
+            range,
+            off_nadir: cosine.clamp(-1., 1.).acos(),
+        }
+    }
+}
+
+/// Writes the range and off-nadir angle of each LAS point as CSV.
+///
+/// The CSV has `gps_time`, `range`, and `off_nadir` columns, with one row
+/// per LAS point in the same order. The range is in meters and the
+/// off-nadir angle is in degrees. Points outside of the trajectory's time
+/// range, or whose coordinates can't be converted, have empty range and
+/// off-nadir values.
+///
+/// # Errors
+///
+/// Returns an error if the LAS point format has no GPS time, the trajectory
+/// has fewer than two points, or on any read or write error.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::{las::Coordinates, Point};
+/// use std::io::Cursor;
+///
+/// let mut header = las::Builder::default();
+/// header.point_format = las::point::Format::new(1).unwrap();
+/// let mut writer = las::Writer::new(Cursor::new(Vec::new()), header.into_header().unwrap()).unwrap();
+/// writer.write_point(las::Point { gps_time: Some(396018.5), ..Default::default() }).unwrap();
+/// let mut reader = las::Reader::new(writer.into_inner().unwrap()).unwrap();
+///
+/// let trajectory = [
+///     Point { time: 396018., altitude: 100., ..Default::default() },
+///     Point { time: 396019., altitude: 100., ..Default::default() },
+/// ];
+/// let mut csv = Vec::new();
+/// sbet::las::scan_geometry(&mut reader, &trajectory, Coordinates::Geographic, &mut csv).unwrap();
+/// assert!(String::from_utf8(csv).unwrap().starts_with("gps_time,range,off_nadir\n396018.5,100"));
+/// ```
+pub fn scan_geometry<W: Write>(
+    reader: &mut las::Reader,
+    trajectory: &[Point],
+    coordinates: Coordinates,
+    mut write: W,
+) -> Result<Attached> {
+    if !reader.header().point_format().has_gps_time {
+        return Err(Error::MissingGpsTime);
+    }
+    let lookup = Lookup::new(trajectory, reader.header())?;
+    let mut attached = Attached::default();
+    writeln!(write, "gps_time,range,off_nadir")?;
+    loop {
+        let points = reader.read_points(CHUNK_SIZE)?;
+        if points.is_empty() {
+            break;
+        }
+        for point in points.points() {
+            let point = point?;
+            let gps_time = point.gps_time.unwrap_or(f64::NAN);
+            let geometry = lookup.get(gps_time).and_then(|sensor| {
+                coordinates
+                    .to_point(point.x, point.y, point.z)
+                    .map(|target| ScanGeometry::new(&sensor, &target))
+            });
+            match geometry {
+                Some(geometry) => {
+                    attached.matched += 1;
+                    writeln!(
+                        write,
+                        "{},{},{}",
+                        gps_time,
+                        geometry.range,
+                        geometry.off_nadir.to_degrees()
+                    )?;
+                }
+                None => {
+                    attached.unmatched += 1;
+                    writeln!(write, "{},,", gps_time)?;
+                }
+            }
+        }
+    }
+    Ok(attached)
+}
+
+/// Returns a record with this time and every other field NaN.
+fn unmatched(time: f64) -> Point {
+    Point {
+        time,
+        latitude: f64::NAN,
+        longitude: f64::NAN,
+        altitude: f64::NAN,
+        x_velocity: f64::NAN,
+        y_velocity: f64::NAN,
+        z_velocity: f64::NAN,
+        roll: f64::NAN,
+        pitch: f64::NAN,
+        yaw: f64::NAN,
+        wander_angle: f64::NAN,
+        x_acceleration: f64::NAN,
+        y_acceleration: f64::NAN,
+        z_acceleration: f64::NAN,
+        x_angular_rate: f64::NAN,
+        y_angular_rate: f64::NAN,
+        z_angular_rate: f64::NAN,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Lookup, ScanGeometry};
+    use crate::Point;
+    use las::{Builder, GpsTimeType};
+
+    #[test]
+    fn standard_trajectory_week_las() {
+        // Seconds-of-week LAS times are placed in the trajectory's week.
+        let week_start = 2264. * 604800. - 1e9;
+        let trajectory = [
+            Point {
+                time: week_start + 396018.,
+                ..Default::default()
+            },
+            Point {
+                time: week_start + 396019.,
+                ..Default::default()
+            },
+        ];
+        let mut builder = Builder::default();
+        builder.gps_time_type = GpsTimeType::Week;
+        let lookup = Lookup::new(&trajectory, &builder.into_header().unwrap()).unwrap();
+        assert_eq!(lookup.time(396018.5), week_start + 396018.5);
+    }
+
+    #[test]
+    fn off_nadir_follows_roll() {
+        let sensor = Point {
+            altitude: 1000.,
+            roll: 0.2,
+            ..Default::default()
+        };
+        // A return straight below is 0.2 radians off the rolled down axis.
+        let below = ScanGeometry::new(&sensor, &Point::default());
+        assert!((below.off_nadir - 0.2).abs() < 1e-6);
+        // A return along the rolled down axis, to the west since roll is right wing down.
+        let target = Point {
+            longitude: -(1000. * 0.2f64.tan() / 6_378_137.),
+            ..Default::default()
+        };
+        let along = ScanGeometry::new(&sensor, &target);
+        assert!(along.off_nadir < 1e-3, "{}", along.off_nadir);
+    }
+}
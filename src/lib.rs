@@ -1,20 +1,246 @@
 //! Micro-crate to read and write Smoothed Best Estimate of Trajectory (SBET) data.
+//!
+//! Record decoding ([records], [to_vec]), [Point], [Field], and
+//! [Interpolator] work without the standard library. Disable default features
+//! to build for `no_std + alloc` targets; file IO, [Reader], [Writer], and the
+//! geodesy helpers require the `std` feature, which is on by default.
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![deny(missing_docs)]
 
-use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
-use std::{
-    fs::File,
-    io::{BufReader, BufWriter, ErrorKind, Read, Write},
-    path::Path,
-};
+extern crate alloc;
+
+use alloc::{boxed::Box, string::String};
+#[cfg(feature = "std")]
+use std::path::Path;
 use thiserror::Error;
 
-const SIZE_OF_SBET_POINT_IN_BYTES: u64 = 112;
+/// Emits a debug event if the `tracing` feature is enabled.
+#[allow(unused_macros)]
+macro_rules! debug {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "tracing")]
+        tracing::debug!($($arg)*);
+    }};
+}
+
+#[cfg(feature = "approx")]
+mod approx;
+#[cfg(feature = "std")]
+mod bounds;
+mod builder;
+#[cfg(feature = "std")]
+pub mod csv;
+#[cfg(feature = "chrono")]
+pub mod czml;
+#[cfg(feature = "std")]
+mod dataset;
+#[cfg(feature = "std")]
+mod dedup;
+#[cfg(feature = "dem")]
+mod dem;
+#[cfg(feature = "std")]
+mod derived;
+#[cfg(feature = "std")]
+mod diff;
+#[cfg(feature = "std")]
+mod dynamics;
+mod endianness;
+mod field;
+#[cfg(feature = "geo")]
+mod geo;
+#[cfg(feature = "std")]
+mod geodesy;
+#[cfg(feature = "std")]
+mod geoid;
+#[cfg(feature = "std")]
+pub mod geojson;
+#[cfg(feature = "std")]
+pub mod gpx;
+#[cfg(feature = "http")]
+mod http;
+#[cfg(feature = "std")]
+mod hull;
+#[cfg(feature = "std")]
+mod index;
+#[cfg(feature = "std")]
+mod input;
+mod interpolator;
+#[cfg(feature = "std")]
+mod json;
+#[cfg(feature = "std")]
+pub mod kml;
+#[cfg(feature = "las")]
+pub mod las;
+#[cfg(feature = "std")]
+mod lines;
+#[cfg(feature = "std")]
+mod merge;
+#[cfg(feature = "nalgebra")]
+mod nalgebra;
+#[cfg(feature = "std")]
+mod offset;
+#[cfg(feature = "std")]
+mod outliers;
+#[cfg(feature = "std")]
+mod output;
+#[cfg(feature = "rayon")]
+pub mod parallel;
+#[cfg(feature = "plot")]
+pub mod plot;
+pub mod prelude;
+#[cfg(feature = "std")]
+mod query;
+#[cfg(feature = "bytemuck")]
+mod raw;
+#[cfg(feature = "std")]
+mod reader;
+mod record;
+#[cfg(feature = "std")]
+pub mod report;
+#[cfg(feature = "std")]
+mod resample;
+#[cfg(feature = "std")]
+mod sample_rate;
+mod schema;
+#[cfg(feature = "std")]
+mod smooth;
+#[cfg(feature = "smoother")]
+pub mod smoother;
+#[cfg(feature = "std")]
+pub mod smrmsg;
+#[cfg(feature = "std")]
+mod sniff;
+mod sort;
+#[cfg(feature = "std")]
+mod stats;
+#[cfg(feature = "std")]
+pub mod synthetic;
+#[cfg(feature = "std")]
+pub mod time;
+#[cfg(feature = "std")]
+mod trajectory;
+#[cfg(feature = "proj")]
+mod transform;
+#[cfg(feature = "uom")]
+mod typed;
+mod units;
+#[cfg(feature = "utm")]
+mod utm;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+mod window;
+#[cfg(feature = "std")]
+mod writer;
+
+#[cfg(feature = "std")]
+pub use bounds::Bounds;
+pub use builder::PointBuilder;
+#[cfg(feature = "std")]
+pub use dataset::{overlaps, Dataset, DatasetFile, DatasetPoints, Overlap};
+#[cfg(feature = "std")]
+pub use dedup::DuplicatePolicy;
+#[cfg(feature = "dem")]
+pub use dem::Dem;
+#[cfg(feature = "std")]
+pub use derived::{Derived, Quantity};
+#[cfg(feature = "std")]
+pub use diff::{compare, diff, ComparisonReport, Diff, FieldDifference};
+#[cfg(feature = "std")]
+pub use dynamics::DynamicsLimits;
+pub use endianness::Endianness;
+pub use field::{Field, FIELD_NAMES};
+#[cfg(feature = "std")]
+pub use geodesy::{Ecef, Enu, Ned};
+#[cfg(feature = "std")]
+pub use geoid::Geoid;
+#[cfg(feature = "http")]
+pub use http::HttpReader;
+#[cfg(feature = "std")]
+pub use index::Index;
+#[cfg(feature = "std")]
+pub use input::Input;
+pub use interpolator::Interpolator;
+#[cfg(feature = "std")]
+pub use json::JsonWriter;
+#[cfg(feature = "std")]
+pub use lines::{LineLimits, Segment, SegmentKind};
+#[cfg(feature = "std")]
+pub use merge::Weighting;
+#[cfg(feature = "std")]
+pub use offset::{time_offset, TimeOffset};
+#[cfg(feature = "std")]
+pub use outliers::{OutlierLimits, OutlierRepair};
+#[cfg(feature = "std")]
+pub use output::{Compression, Output};
+#[cfg(feature = "std")]
+pub use query::query_time;
+#[cfg(feature = "bytemuck")]
+pub use raw::RawPoint;
+#[cfg(feature = "std")]
+pub use reader::{Chunks, FilterByTime, Follow, IterRev, Reader};
+pub use record::{records, to_vec, Records, RECORD_SIZE};
+#[cfg(feature = "std")]
+pub use resample::SampleMethod;
+#[cfg(feature = "std")]
+pub use sample_rate::{IntervalBucket, IntervalHistogram, SampleRate};
+pub use schema::{Column, RecordSchema};
+#[cfg(feature = "std")]
+pub use sniff::{sniff, Sniff};
+pub use sort::{is_sorted, sort_by_time, TimedPoint};
+#[cfg(feature = "std")]
+pub use stats::{Percentiles, Stats, Summary};
+#[cfg(feature = "std")]
+pub use trajectory::Trajectory;
+#[cfg(feature = "proj")]
+pub use transform::Transformer;
+#[cfg(feature = "uom")]
+pub use typed::TypedPoint;
+pub use units::SpeedUnit;
+#[cfg(feature = "utm")]
+pub use utm::Utm;
+pub use window::TimeWindow;
+#[cfg(feature = "std")]
+pub use writer::{Writer, WriterStats};
 
 /// Crate-specific error enum.
 #[derive(Debug, Error)]
 pub enum Error {
+    /// An error while reading a record.
+    #[error("failed to read record {index} at byte offset {offset}")]
+    AtRecord {
+        /// The index of the record.
+        index: u64,
+
+        /// The byte offset of the start of the record.
+        offset: u64,
+
+        /// The underlying error.
+        #[source]
+        source: Box<Error>,
+    },
+
+    /// An operation was cancelled by its progress callback.
+    #[error("cancelled")]
+    Cancelled,
+
+    /// The bytes can't be viewed as raw records.
+    #[cfg(feature = "bytemuck")]
+    #[error("cannot view bytes as records: {0:?}")]
+    Cast(bytemuck::PodCastError),
+
+    /// A convex hull has fewer than three distinct positions, so it isn't a polygon.
+    #[error("the convex hull has only {0} distinct positions")]
+    DegenerateHull(usize),
+
+    /// Two consecutive points have the same time.
+    #[error("duplicate time: {0}")]
+    DuplicateTime(f64),
+
+    /// A CSV file has no header line.
+    #[error("the CSV file is empty")]
+    EmptyCsv,
+
     /// Extrapolation.
     #[error("extrapolation, time {time} does not fall between {start_time} and {end_time}")]
     Extrapolation {
@@ -28,10 +254,84 @@ pub enum Error {
         end_time: f64,
     },
 
+    /// A CSV value can't be parsed, or is missing.
+    #[error("invalid or missing value for column {column} at line {line}")]
+    InvalidCsvValue {
+        /// The one-based line number.
+        line: u64,
+
+        /// The column name.
+        column: String,
+    },
+
+    /// The digital elevation model is invalid or unsupported.
+    #[cfg(feature = "dem")]
+    #[error("invalid DEM: {0}")]
+    InvalidDem(String),
+
+    /// The geoid grid is invalid.
+    #[error("invalid geoid grid: {0}")]
+    InvalidGeoid(String),
+
+    /// A time index is invalid, or doesn't match its SBET file.
+    #[error("invalid index: {0}")]
+    InvalidIndex(String),
+
+    /// A time window isn't `start:stop`, or stops before it starts.
+    #[error("invalid time window: {0}")]
+    InvalidTimeWindow(String),
+
+    /// [ureq::Error]
+    #[cfg(feature = "http")]
+    #[error(transparent)]
+    Http(#[from] ureq::Error),
+
     /// [std::io::Error]
+    #[cfg(feature = "std")]
     #[error(transparent)]
     Io(#[from] std::io::Error),
 
+    /// [las::Error]
+    #[cfg(feature = "las")]
+    #[error(transparent)]
+    Las(#[from] ::las::Error),
+
+    /// [proj::ProjCreateError]
+    #[cfg(feature = "proj")]
+    #[error(transparent)]
+    ProjCreate(#[from] proj::ProjCreateError),
+
+    /// [proj::ProjError]
+    #[cfg(feature = "proj")]
+    #[error(transparent)]
+    Proj(#[from] proj::ProjError),
+
+    /// A CSV column is not in the header.
+    #[error("no column named {0} in the CSV")]
+    MissingColumn(String),
+
+    /// The LAS point format has no GPS time.
+    #[error("the LAS points have no GPS time")]
+    MissingGpsTime,
+
+    /// A record's time doesn't follow the previous record's time, in a strict reading or writing mode.
+    ///
+    /// Unlike [Error::Unsorted], which is about points already in memory,
+    /// this is raised while streaming, with the times that are out of order.
+    #[error(
+        "record {index} has time {current}, which doesn't follow the previous time {previous}"
+    )]
+    NonMonotonicTime {
+        /// The index of the record.
+        index: u64,
+
+        /// The previous record's time.
+        previous: f64,
+
+        /// This record's time.
+        current: f64,
+    },
+
     /// There are no points to iterpolate.
     #[error("no points to interpolate within")]
     NoPoints,
@@ -39,28 +339,170 @@ pub enum Error {
     /// There is only one point.
     #[error("only points to interpolate within")]
     OnePoint,
+
+    /// Two files' time ranges overlap.
+    #[cfg(feature = "std")]
+    #[error("{} and {} overlap in time", .first.display(), .second.display())]
+    OverlappingFiles {
+        /// The file that starts first.
+        first: std::path::PathBuf,
+
+        /// The file that starts second.
+        second: std::path::PathBuf,
+    },
+
+    /// A plot can't be drawn.
+    #[cfg(feature = "plot")]
+    #[error("failed to draw a plot: {0}")]
+    Plot(String),
+
+    /// A remote file can't be read with range requests.
+    #[cfg(feature = "http")]
+    #[error("range request failed: {0}")]
+    RangeRequest(String),
+
+    /// A record index is too large to seek to, because its byte offset doesn't fit in a `u64`.
+    #[error("record {0} is too far into the stream to seek to")]
+    SeekOutOfRange(u64),
+
+    /// [tiff::TiffError]
+    #[cfg(feature = "dem")]
+    #[error(transparent)]
+    Tiff(#[from] tiff::TiffError),
+
+    /// A time can't be converted to a UTC datetime.
+    #[error("time {0} is out of range")]
+    TimeOutOfRange(f64),
+
+    /// A file's length isn't a whole number of records.
+    #[error(
+        "the file length {len} isn't a whole number of records, {remainder} bytes are left over"
+    )]
+    UnalignedLength {
+        /// The file length, in bytes.
+        len: u64,
+
+        /// The number of bytes after the last whole record.
+        remainder: u64,
+    },
+
+    /// The KML coloring is not recognized.
+    #[error("unknown coloring: {0}")]
+    UnknownColoring(String),
+
+    /// The compression format is not recognized, or its feature isn't enabled.
+    #[error("unknown compression: {0}")]
+    UnknownCompression(String),
+
+    /// The LAS coordinates are not recognized.
+    #[cfg(feature = "las")]
+    #[error("unknown coordinates: {0}")]
+    UnknownCoordinates(String),
+
+    /// The duplicate policy is not recognized.
+    #[error("unknown duplicate policy: {0}")]
+    UnknownDuplicatePolicy(String),
+
+    /// The endianness is not recognized.
+    #[error("unknown endianness: {0}")]
+    UnknownEndianness(String),
+
+    /// The field name is not recognized.
+    #[error("unknown field: {0}")]
+    UnknownField(String),
+
+    /// The report format is not recognized.
+    #[error("unknown report format: {0}")]
+    UnknownReportFormat(String),
+
+    /// The sample method is not recognized.
+    #[error("unknown sample method: {0}")]
+    UnknownSampleMethod(String),
+
+    /// The speed unit is not recognized.
+    #[error("unknown speed unit: {0}")]
+    UnknownSpeedUnit(String),
+
+    /// The merge weighting is not recognized.
+    #[error("unknown weighting: {0}")]
+    UnknownWeighting(String),
+
+    /// A point's time is before the previous point's time, or is NaN.
+    #[error("point {0} is out of time order")]
+    Unsorted(usize),
 }
 
 /// Crate-specific result type.
-pub type Result<T> = std::result::Result<T, Error>;
+pub type Result<T, E = Error> = core::result::Result<T, E>;
 
 /// Estimate the number of SBET points in a file based on file size.
 ///
+/// A trailing partial record is ignored; use [exact_number_of_points] to
+/// treat one as an error. To count the records in any seekable stream, use
+/// [Reader::estimate_number_of_points].
+///
 /// # Examples
 ///
 /// ```
 /// assert_eq!(sbet::estimate_number_of_points("data/2-points.sbet").unwrap(), 2);
 /// ```
+#[cfg(feature = "std")]
 pub fn estimate_number_of_points<P: AsRef<Path>>(path: P) -> Result<u64> {
     let metadata = std::fs::metadata(path)?;
-    Ok(metadata.len() / SIZE_OF_SBET_POINT_IN_BYTES)
+    Ok(metadata.len() / RECORD_SIZE as u64)
 }
 
-/// Interpolate a sorted slice of points at a point in time.
+/// Returns the number of SBET points in a file based on file size, requiring the size to be a whole number of records.
+///
+/// # Errors
 ///
-/// This is pretty inefficient because it scans from the start.
+/// Returns [Error::UnalignedLength], with the number of left over bytes,
+/// if the file ends with a partial record. See also
+/// [Reader::exact_number_of_points].
 ///
-/// TODO make this better by building an index first.
+/// # Examples
+///
+/// ```
+/// assert_eq!(sbet::exact_number_of_points("data/2-points.sbet").unwrap(), 2);
+/// ```
+#[cfg(feature = "std")]
+pub fn exact_number_of_points<P: AsRef<Path>>(path: P) -> Result<u64> {
+    let len = std::fs::metadata(path)?.len();
+    let remainder = len % RECORD_SIZE as u64;
+    if remainder == 0 {
+        Ok(len / RECORD_SIZE as u64)
+    } else {
+        Err(Error::UnalignedLength { len, remainder })
+    }
+}
+
+/// Returns the times of the first and last records in a file, without reading the records between.
+///
+/// The file must be uncompressed, little-endian SBET records. This is a
+/// quick way to find which of many files covers a time.
+///
+/// # Errors
+///
+/// Returns [Error::NoPoints] if the file has no whole records.
+///
+/// # Examples
+///
+/// ```
+/// let (start, end) = sbet::time_bounds("data/2-points.sbet").unwrap();
+/// assert!(start < end);
+/// ```
+#[cfg(feature = "std")]
+pub fn time_bounds<P: AsRef<Path>>(path: P) -> Result<(f64, f64)> {
+    let mut reader = Reader::new(std::fs::File::open(path)?);
+    let last = reader.read_last()?.ok_or(Error::NoPoints)?;
+    let first = reader.read_first()?.ok_or(Error::NoPoints)?;
+    Ok((first.time, last.time))
+}
+
+/// Interpolate a sorted slice of points at a point in time.
+///
+/// To interpolate many times within the same points, create an
+/// [Interpolator] once and reuse it.
 ///
 /// # Errors
 ///
@@ -73,59 +515,18 @@ pub fn estimate_number_of_points<P: AsRef<Path>>(path: P) -> Result<u64> {
 /// # Examples
 ///
 /// ```
-/// use sbet::Reader;
+/// use sbet::Point;
 ///
-/// let reader = Reader::from_path("data/2-points.sbet").unwrap();
-/// let points = reader.into_iter().collect::<Result<Vec<_>, _>>().unwrap();
-/// let interpolated_point = sbet::interpolate(&points, 151631.004);
+/// let points = [
+///     Point { time: 0., ..Default::default() },
+///     Point { time: 1., altitude: 2., ..Default::default() },
+/// ];
+/// let interpolated_point = sbet::interpolate(&points, 0.5).unwrap();
+/// assert_eq!(interpolated_point.altitude, 1.);
 /// ```
 ///
 pub fn interpolate(points: &[Point], time: f64) -> Result<Point> {
-    if points.is_empty() {
-        return Err(Error::NoPoints);
-    }
-    if points.len() == 1 {
-        return Err(Error::OnePoint);
-    }
-    if points[0].time > time || points.last().unwrap().time < time {
-        return Err(Error::Extrapolation {
-            time,
-            start_time: points[0].time,
-            end_time: points.last().unwrap().time,
-        });
-    }
-    for (before, after) in points.iter().zip(points.iter().skip(1)) {
-        if before.time <= time && after.time >= time {
-            let factor = (time - before.time) / (after.time - before.time);
-            return Ok(Point {
-                time,
-                latitude: before.latitude + factor * (after.latitude - before.latitude),
-                longitude: before.longitude + factor * (after.longitude - before.longitude),
-                altitude: before.altitude + factor * (after.altitude - before.altitude),
-                x_velocity: before.x_velocity + factor * (after.x_velocity - before.x_velocity),
-                y_velocity: before.y_velocity + factor * (after.y_velocity - before.y_velocity),
-                z_velocity: before.z_velocity + factor * (after.z_velocity - before.z_velocity),
-                roll: before.roll + factor * (after.roll - before.roll),
-                pitch: before.pitch + factor * (after.pitch - before.pitch),
-                yaw: before.yaw + factor * (after.yaw - before.yaw),
-                wander_angle: before.wander_angle
-                    + factor * (after.wander_angle - before.wander_angle),
-                x_acceleration: before.x_acceleration
-                    + factor * (after.x_acceleration - before.x_acceleration),
-                y_acceleration: before.y_acceleration
-                    + factor * (after.y_acceleration - before.y_acceleration),
-                z_acceleration: before.z_acceleration
-                    + factor * (after.z_acceleration - before.z_acceleration),
-                x_angular_rate: before.x_angular_rate
-                    + factor * (after.x_angular_rate - before.x_angular_rate),
-                y_angular_rate: before.y_angular_rate
-                    + factor * (after.y_angular_rate - before.y_angular_rate),
-                z_angular_rate: before.z_angular_rate
-                    + factor * (after.z_angular_rate - before.z_angular_rate),
-            });
-        }
-    }
-    unreachable!()
+    Interpolator::new(points)?.interpolate(time)
 }
 
 /// Smoothed Best Estimate of Trajectory (SBET) point.
@@ -151,150 +552,12 @@ pub struct Point {
     pub z_angular_rate: f64,
 }
 
-/// Use this structure to read sbet data from a source.
-///
-/// # Examples
-///
-/// [Reader] implements [Iterator]:
-///
-/// ```
-/// use sbet::Reader;
-///
-/// let reader = Reader::from_path("data/2-points.sbet").unwrap();
-/// for result in reader {
-///     let point = result.unwrap();
-///     dbg!(point);
-/// }
-/// ```
-pub struct Reader<R: Read>(pub R);
-
-/// Use this structure to write sbet data.
-pub struct Writer<W: Write>(pub W);
-
-impl<R: Read> Reader<R> {
-    /// Reads one point.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use sbet::Reader;
-    ///
-    /// let mut reader = Reader::from_path("data/2-points.sbet").unwrap();
-    /// let point = reader.read_one().unwrap().unwrap();
-    /// ```
-    pub fn read_one(&mut self) -> Result<Option<Point>> {
-        let time = match self.0.read_f64::<LittleEndian>() {
-            Ok(time) => time,
-            Err(err) => match err.kind() {
-                ErrorKind::UnexpectedEof => return Ok(None),
-                _ => return Err(err.into()),
-            },
-        };
-        Ok(Some(Point {
-            time,
-            latitude: self.0.read_f64::<LittleEndian>()?,
-            longitude: self.0.read_f64::<LittleEndian>()?,
-            altitude: self.0.read_f64::<LittleEndian>()?,
-            x_velocity: self.0.read_f64::<LittleEndian>()?,
-            y_velocity: self.0.read_f64::<LittleEndian>()?,
-            z_velocity: self.0.read_f64::<LittleEndian>()?,
-            roll: self.0.read_f64::<LittleEndian>()?,
-            pitch: self.0.read_f64::<LittleEndian>()?,
-            yaw: self.0.read_f64::<LittleEndian>()?,
-            wander_angle: self.0.read_f64::<LittleEndian>()?,
-            x_acceleration: self.0.read_f64::<LittleEndian>()?,
-            y_acceleration: self.0.read_f64::<LittleEndian>()?,
-            z_acceleration: self.0.read_f64::<LittleEndian>()?,
-            x_angular_rate: self.0.read_f64::<LittleEndian>()?,
-            y_angular_rate: self.0.read_f64::<LittleEndian>()?,
-            z_angular_rate: self.0.read_f64::<LittleEndian>()?,
-        }))
-    }
-}
-
-impl Reader<BufReader<File>> {
-    /// Creates a reader for the file at the path.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use sbet::Reader;
-    ///
-    /// let reader = Reader::from_path("data/2-points.sbet").unwrap();
-    /// ```
-    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Reader<BufReader<File>>> {
-        File::open(path)
-            .map(|f| Reader(BufReader::new(f)))
-            .map_err(|e| e.into())
-    }
-}
-
-impl<R: Read> Iterator for Reader<R> {
-    type Item = Result<Point>;
-
-    fn next(&mut self) -> Option<Result<Point>> {
-        match self.read_one() {
-            Ok(option) => option.map(Ok),
-            Err(err) => Some(Err(err)),
-        }
-    }
-}
-
-impl<W: Write> Writer<W> {
-    /// Writes one point to the writer.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use sbet::{Writer, Point};
-    ///
-    /// let mut writer = Writer(std::io::stdout());
-    /// writer.write_one(Point::default());
-    /// ```
-    pub fn write_one(&mut self, point: Point) -> Result<()> {
-        self.0.write_f64::<LittleEndian>(point.time)?;
-        self.0.write_f64::<LittleEndian>(point.latitude)?;
-        self.0.write_f64::<LittleEndian>(point.longitude)?;
-        self.0.write_f64::<LittleEndian>(point.altitude)?;
-        self.0.write_f64::<LittleEndian>(point.x_velocity)?;
-        self.0.write_f64::<LittleEndian>(point.y_velocity)?;
-        self.0.write_f64::<LittleEndian>(point.z_velocity)?;
-        self.0.write_f64::<LittleEndian>(point.roll)?;
-        self.0.write_f64::<LittleEndian>(point.pitch)?;
-        self.0.write_f64::<LittleEndian>(point.yaw)?;
-        self.0.write_f64::<LittleEndian>(point.wander_angle)?;
-        self.0.write_f64::<LittleEndian>(point.x_acceleration)?;
-        self.0.write_f64::<LittleEndian>(point.y_acceleration)?;
-        self.0.write_f64::<LittleEndian>(point.z_acceleration)?;
-        self.0.write_f64::<LittleEndian>(point.x_angular_rate)?;
-        self.0.write_f64::<LittleEndian>(point.y_angular_rate)?;
-        self.0.write_f64::<LittleEndian>(point.z_angular_rate)?;
-        Ok(())
-    }
-}
-
-impl Writer<BufWriter<File>> {
-    /// Creates a writer for the file at the path.
-    ///
-    /// # Examples
-    ///
-    /// ```no_run
-    /// use sbet::Writer;
-    ///
-    /// let writer = Writer::from_path("outfile.sbet").unwrap();
-    /// ```
-    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Writer<BufWriter<File>>> {
-        File::create(path)
-            .map(|f| Writer(BufWriter::new(f)))
-            .map_err(|e| e.into())
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
+    #[cfg(feature = "std")]
     fn read() {
         let reader = Reader::from_path("data/2-points.sbet").unwrap();
         let points = reader.collect::<Result<Vec<Point>>>().unwrap();
@@ -0,0 +1,225 @@
+use crate::{smooth::wrap, Trajectory};
+
+/// Thresholds that separate straight flight lines from turns.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::LineLimits;
+///
+/// let limits = LineLimits { min_duration: 60., ..Default::default() };
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LineLimits {
+    /// The largest change in true heading within a line, in radians.
+    pub heading: f64,
+
+    /// The largest absolute roll within a line, in radians.
+    pub roll: f64,
+
+    /// The shortest line, in seconds. Shorter stable stretches are part of a turn.
+    pub min_duration: f64,
+}
+
+/// Whether a segment is a flight line or a turn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SegmentKind {
+    /// A straight, level stretch.
+    Line,
+
+    /// Everything between lines.
+    Turn,
+}
+
+/// A stretch of a trajectory that is either a flight line or a turn.
+///
+/// Created by [Trajectory::segments].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Segment {
+    /// Whether this is a flight line or a turn.
+    pub kind: SegmentKind,
+
+    /// The one-based number of this segment among segments of the same kind.
+    pub number: usize,
+
+    /// The index of the first point.
+    pub start: usize,
+
+    /// The index after the last point.
+    pub end: usize,
+
+    /// The time of the first point.
+    pub start_time: f64,
+
+    /// The time of the last point.
+    pub end_time: f64,
+
+    /// The mean true heading, in radians in `[0, 2π)`.
+    pub heading: f64,
+}
+
+impl Default for LineLimits {
+    /// Five degrees of heading change, five degrees of roll, and thirty seconds.
+    fn default() -> LineLimits {
+        LineLimits {
+            heading: 5f64.to_radians(),
+            roll: 5f64.to_radians(),
+            min_duration: 30.,
+        }
+    }
+}
+
+impl Segment {
+    /// Returns the duration of this segment, in seconds.
+    pub fn duration(&self) -> f64 {
+        self.end_time - self.start_time
+    }
+}
+
+impl Trajectory {
+    /// Splits the trajectory into numbered flight lines and the turns between them.
+    ///
+    /// A line is a run of points whose absolute roll is within the limit and
+    /// whose [true heading](crate::Point::true_heading) stays within the
+    /// limit of the heading at the start of the run, lasting at least the
+    /// minimum duration. Every other point is part of a turn. The segments
+    /// cover every point, in order. The points must be sorted by time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{LineLimits, Point, SegmentKind, Trajectory};
+    ///
+    /// let trajectory: Trajectory = (0..100)
+    ///     .map(|i| Point {
+    ///         time: f64::from(i),
+    ///         roll: if (40..60).contains(&i) { 0.5 } else { 0. },
+    ///         ..Default::default()
+    ///     })
+    ///     .collect::<Vec<_>>()
+    ///     .into();
+    /// let segments = trajectory.segments(LineLimits::default());
+    /// let kinds: Vec<SegmentKind> = segments.iter().map(|segment| segment.kind).collect();
+    /// assert_eq!(kinds, [SegmentKind::Line, SegmentKind::Turn, SegmentKind::Line]);
+    /// assert_eq!(segments[2].number, 2);
+    /// ```
+    pub fn segments(&self, limits: LineLimits) -> Vec<Segment> {
+        let points = self.points();
+        let mut lines = Vec::new();
+        let mut start = 0;
+        while start < points.len() {
+            if points[start].roll.abs() > limits.roll {
+                start += 1;
+                continue;
+            }
+            let reference = points[start].true_heading();
+            let end = points[start..]
+                .iter()
+                .position(|point| {
+                    point.roll.abs() > limits.roll
+                        || wrap(point.true_heading() - reference).abs() > limits.heading
+                })
+                .map_or(points.len(), |offset| start + offset);
+            if points[end - 1].time - points[start].time >= limits.min_duration {
+                lines.push((start, end));
+            }
+            start = end;
+        }
+        let mut segments = Vec::new();
+        let mut next = 0;
+        for (start, end) in lines {
+            if start > next {
+                segments.push(self.segment(SegmentKind::Turn, next, start));
+            }
+            segments.push(self.segment(SegmentKind::Line, start, end));
+            next = end;
+        }
+        if next < points.len() {
+            segments.push(self.segment(SegmentKind::Turn, next, points.len()));
+        }
+        let (mut lines, mut turns) = (0, 0);
+        for segment in &mut segments {
+            let count = match segment.kind {
+                SegmentKind::Line => &mut lines,
+                SegmentKind::Turn => &mut turns,
+            };
+            *count += 1;
+            segment.number = *count;
+        }
+        segments
+    }
+
+    /// Returns only the flight lines from [Trajectory::segments].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{LineLimits, Trajectory};
+    ///
+    /// let trajectory = Trajectory::from_path("data/2-points.sbet").unwrap();
+    /// assert!(trajectory.flight_lines(LineLimits::default()).is_empty());
+    /// ```
+    pub fn flight_lines(&self, limits: LineLimits) -> Vec<Segment> {
+        self.segments(limits)
+            .into_iter()
+            .filter(|segment| segment.kind == SegmentKind::Line)
+            .collect()
+    }
+
+    fn segment(&self, kind: SegmentKind, start: usize, end: usize) -> Segment {
+        let points = &self.points()[start..end];
+        let (sin, cos) = points.iter().fold((0., 0.), |(sin, cos), point| {
+            let heading = point.true_heading();
+            (sin + heading.sin(), cos + heading.cos())
+        });
+        Segment {
+            kind,
+            number: 0,
+            start,
+            end,
+            start_time: points[0].time,
+            end_time: points[points.len() - 1].time,
+            heading: f64::atan2(sin, cos).rem_euclid(std::f64::consts::TAU),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{LineLimits, Point, SegmentKind, Trajectory};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn heading_reversal() {
+        // A racetrack: north, a turn through east, then south.
+        let trajectory: Trajectory = (0..200)
+            .map(|i| Point {
+                time: f64::from(i),
+                yaw: match i {
+                    0..80 => 0.,
+                    80..120 => PI * f64::from(i - 80) / 40.,
+                    _ => PI,
+                },
+                ..Default::default()
+            })
+            .collect::<Vec<_>>()
+            .into();
+        let segments = trajectory.segments(LineLimits::default());
+        assert_eq!(segments.len(), 3);
+        assert_eq!(segments[1].kind, SegmentKind::Turn);
+        assert_eq!(segments[0].end, segments[1].start);
+        assert!((segments[2].heading - PI).abs() < 1e-9);
+        assert_eq!(trajectory.flight_lines(LineLimits::default()).len(), 2);
+    }
+
+    #[test]
+    fn all_turn() {
+        let trajectory: Trajectory = vec![Point::default(); 3].into();
+        let segments = trajectory.segments(LineLimits::default());
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].kind, SegmentKind::Turn);
+        assert!(Trajectory::default()
+            .segments(LineLimits::default())
+            .is_empty());
+    }
+}
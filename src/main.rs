@@ -1,14 +1,61 @@
-use clap::{Parser, Subcommand};
-use sbet::{Reader, Writer};
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use clap::{ArgAction, CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
+use indicatif::{ProgressBar, ProgressFinish, ProgressStyle};
+use sbet::{
+    csv, czml, geojson, gpx, kml, Compression, DuplicatePolicy, DynamicsLimits, Endianness, Field,
+    HttpReader, Input, JsonWriter, LineLimits, Output, Quantity, Reader, SegmentKind, SpeedUnit,
+    TimeWindow, Trajectory, Weighting, Writer, RECORD_SIZE,
+};
+use serde::Deserialize;
 use std::{
+    collections::HashMap,
     fs::File,
-    io::{BufReader, BufWriter, Read, Write},
+    io::{BufReader, ErrorKind, IsTerminal, Read, Write},
 };
+use tracing::{info, warn, Level};
 
 #[derive(Debug, Parser)]
+#[command(
+    name = "sbet",
+    version,
+    about = "Read, write, and convert Smoothed Best Estimate of Trajectory (SBET) data"
+)]
 struct Args {
     #[command(subcommand)]
     command: Command,
+
+    /// Compress output files, "none", "gzip", or "zstd".
+    #[arg(long, global = true, default_value = "none")]
+    compress: Compression,
+
+    /// Fail if a record's time is before the previous record's.
+    #[arg(long, global = true)]
+    strict: bool,
+
+    /// Print velocities and speeds in "mps", "knots", or "kmh".
+    ///
+    /// Applies to `stats`, `report`, and the columns and properties of `to-csv`
+    /// and `to-geojson`. KML speed colors are relative, so they don't change.
+    #[arg(long, global = true, default_value = "mps")]
+    speed_units: SpeedUnit,
+
+    /// Don't show progress bars or informational messages.
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
+    /// Print more diagnostics to stderr; repeat for more detail, e.g. `-vv`.
+    #[arg(short, long, global = true, action = ArgAction::Count, conflicts_with = "quiet")]
+    verbose: u8,
+}
+
+/// Options that apply to every subcommand's inputs and outputs.
+struct Options {
+    compress: Compression,
+    strict: bool,
+    speed_units: SpeedUnit,
+    quiet: bool,
 }
 
 #[derive(Debug, Subcommand)]
@@ -30,11 +77,331 @@ enum Command {
         decimate: usize,
 
         /// Include time in the output.
-        #[arg(short, long)]
+        #[arg(short, long, conflicts_with = "fields")]
         include_time: bool,
+
+        /// The fields to write, comma-separated, e.g. `time,latitude,longitude,roll`.
+        ///
+        /// Derived values are computed from each point's fields:
+        /// `true_heading`, `speed`, `horizontal_speed`, `course`,
+        /// `acceleration_magnitude`, and `angular_rate_magnitude`. Defaults to
+        /// latitude, longitude, and altitude.
+        #[arg(long, value_delimiter = ',')]
+        fields: Option<Vec<Quantity>>,
+
+        /// Write all seventeen fields, in the same order as the SBET record.
+        #[arg(long, conflicts_with_all = ["fields", "include_time"])]
+        all_fields: bool,
+
+        /// The column delimiter.
+        #[arg(long, default_value = ",")]
+        delimiter: char,
+
+        /// Write values with this many digits after the decimal point.
+        ///
+        /// By default, values are written with as many digits as are needed
+        /// to read them back exactly.
+        #[arg(long)]
+        precision: Option<usize>,
+
+        /// Write these angular fields in degrees, comma-separated.
+        ///
+        /// Latitude and longitude are written in degrees unless they're
+        /// listed in `--in-radians`; other angles and angular rates are
+        /// written in radians unless they're listed here.
+        #[arg(long, value_delimiter = ',')]
+        in_degrees: Vec<Quantity>,
+
+        /// Write these angular fields in radians, comma-separated.
+        #[arg(long, value_delimiter = ',')]
+        in_radians: Vec<Quantity>,
+
+        /// Write all angles and angular rates in degrees, except those listed in `--in-radians`.
+        #[arg(long, conflicts_with = "in_degrees")]
+        degrees: bool,
+
+        /// Include an ISO 8601 UTC timestamp column, using this GPS week.
+        #[arg(long, conflicts_with = "date")]
+        week: Option<u32>,
+
+        /// Include an ISO 8601 UTC timestamp column, using the GPS week containing this date.
+        #[arg(long)]
+        date: Option<NaiveDate>,
+
+        /// Write orthometric heights using this GTX geoid grid, e.g. EGM96 or EGM2008.
+        ///
+        /// The altitude column is replaced by the orthometric height.
+        #[arg(long)]
+        geoid: Option<String>,
+
+        /// Write heights above ground using this GeoTIFF elevation model.
+        ///
+        /// The altitude column is replaced by the height above ground. The
+        /// model must use the same vertical datum as the altitudes, so pass
+        /// `--geoid` too if the model is orthometric.
+        #[cfg(feature = "dem")]
+        #[arg(long)]
+        agl: Option<String>,
+
+        /// Reproject positions into this EPSG code.
+        ///
+        /// The latitude and longitude columns are replaced by x and y.
+        #[cfg(feature = "proj")]
+        #[arg(long)]
+        epsg: Option<u32>,
+
+        /// Only write points at or after this time.
+        #[arg(long, default_value = "-inf")]
+        start_time: f64,
+
+        /// Only write points at or before this time.
+        #[arg(long, default_value = "+inf")]
+        stop_time: f64,
+
+        /// Seek to the start time with the file's `.sbx` index, from the `index` subcommand.
+        ///
+        /// The file must be sorted by time, so reading stops after the stop time.
+        #[arg(long)]
+        use_index: bool,
+
+        /// Stop reading at the first point after the stop time.
+        ///
+        /// Only use this if the input is sorted by time, or later points within the window are dropped.
+        #[arg(long)]
+        sorted: bool,
+    },
+
+    /// Convert an SBET file to JSON Lines, one object per point.
+    ///
+    /// Each object has a member for every field, named as in `to-csv
+    /// --all-fields`. Angles are in radians.
+    ToJsonl {
+        /// The input file path.
+        ///
+        /// Omit or use `-` to read from stdin.
+        infile: Option<String>,
+
+        /// The output file path.
+        ///
+        /// Omit or use `-` to print to stdout.
+        outfile: Option<String>,
+
+        /// Decimate the data by this amount.
+        #[arg(short, long, default_value = "1")]
+        decimate: usize,
+    },
+
+    /// Convert an SBET file to GeoJSON.
+    ///
+    /// Writes a LineString feature by default, or a FeatureCollection of
+    /// Point features with `--points`.
+    ToGeojson {
+        /// The input file path.
+        ///
+        /// Omit or use `-` to read from stdin.
+        infile: Option<String>,
+
+        /// The output file path.
+        ///
+        /// Omit or use `-` to print to stdout.
+        outfile: Option<String>,
+
+        /// Write one Point feature per point instead of a LineString.
+        #[arg(long)]
+        points: bool,
+
+        /// Write a Polygon around the convex hull of the points instead of a LineString.
+        #[arg(long, conflicts_with = "points")]
+        hull: bool,
+
+        /// Decimate the data by this amount.
+        #[arg(short, long, default_value = "1")]
+        decimate: usize,
+
+        /// Fields or derived values to include as properties, comma-separated, in SBET units.
+        ///
+        /// Velocities and speeds are in the `--speed-units`.
+        #[arg(long, value_delimiter = ',')]
+        properties: Vec<Quantity>,
+
+        /// Write orthometric heights using this GTX geoid grid, e.g. EGM96 or EGM2008.
+        #[arg(long)]
+        geoid: Option<String>,
+
+        /// Reproject positions into this EPSG code.
+        ///
+        /// Coordinates are written as x and y instead of longitude and latitude.
+        #[cfg(feature = "proj")]
+        #[arg(long)]
+        epsg: Option<u32>,
+    },
+
+    /// Convert an SBET file to KML for Google Earth.
+    ToKml {
+        /// The input file path.
+        ///
+        /// Omit or use `-` to read from stdin.
+        infile: Option<String>,
+
+        /// The output file path.
+        ///
+        /// Omit or use `-` to print to stdout.
+        outfile: Option<String>,
+
+        /// The document name.
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Color the track by "altitude" or "speed", or with a hex color like "#ff0000".
+        #[arg(long, default_value = "#ff0000")]
+        color: kml::Coloring,
+
+        /// The line width in pixels.
+        #[arg(long, default_value = "2")]
+        width: f64,
+
+        /// Decimate the data by this amount.
+        #[arg(short, long, default_value = "1")]
+        decimate: usize,
+
+        /// Write a time-stamped track, using this GPS week.
+        #[arg(long, conflicts_with = "date")]
+        week: Option<u32>,
+
+        /// Write a time-stamped track, using the GPS week containing this date.
+        #[arg(long)]
+        date: Option<NaiveDate>,
+    },
+
+    /// Convert an SBET file to a GPX track.
+    ///
+    /// The track is split into segments at gaps in time.
+    ToGpx {
+        /// The input file path.
+        ///
+        /// Omit or use `-` to read from stdin.
+        infile: Option<String>,
+
+        /// The output file path.
+        ///
+        /// Omit or use `-` to print to stdout.
+        outfile: Option<String>,
+
+        /// The track name.
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Decimate the data by this amount.
+        #[arg(short, long, default_value = "1")]
+        decimate: usize,
+
+        /// Start a new segment where points are more than this many seconds apart.
+        ///
+        /// Defaults to ten times the median time between points.
+        #[arg(long)]
+        gap: Option<f64>,
+
+        /// Write timestamps, using this GPS week.
+        #[arg(long, conflicts_with = "date")]
+        week: Option<u32>,
+
+        /// Write timestamps, using the GPS week containing this date.
+        #[arg(long)]
+        date: Option<NaiveDate>,
+    },
+
+    /// Convert an SBET file to CZML for replaying the flight in CesiumJS.
+    ///
+    /// Position and orientation are sampled at every point.
+    ToCzml {
+        /// The input file path.
+        ///
+        /// Omit or use `-` to read from stdin.
+        infile: Option<String>,
+
+        /// The output file path.
+        ///
+        /// Omit or use `-` to print to stdout.
+        outfile: Option<String>,
+
+        /// The GPS week of the SBET file's seconds-of-week times.
+        #[arg(long, required_unless_present = "date", conflicts_with = "date")]
+        week: Option<u32>,
+
+        /// A date in the GPS week of the SBET file's seconds-of-week times.
+        #[arg(long)]
+        date: Option<NaiveDate>,
+
+        /// The document name.
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Draw the aircraft with the glTF model at this URI.
+        #[arg(long)]
+        model: Option<String>,
+
+        /// Decimate the data by this amount.
+        #[arg(short, long, default_value = "1")]
+        decimate: usize,
+    },
+
+    /// Convert a CSV file to SBET.
+    ///
+    /// Without a mapping file, columns are matched to SBET fields by header
+    /// name, with latitude and longitude in degrees, as written by `to-csv`.
+    /// Fields without a column are set to zero.
+    FromCsv {
+        /// The input file path.
+        ///
+        /// Omit or use `-` to read from stdin.
+        infile: Option<String>,
+
+        /// The output file path.
+        ///
+        /// Omit or use `-` to print to stdout.
+        outfile: Option<String>,
+
+        /// A TOML file mapping CSV columns to SBET fields.
+        ///
+        /// For example:
+        ///
+        /// delimiter = ";"
+        ///
+        /// [columns]
+        /// GPSTime = { field = "time" }
+        /// Lat = { field = "latitude", units = "degrees" }
+        /// Lon = { field = "longitude", units = "degrees" }
+        /// HeightFt = { field = "altitude", scale = 0.3048 }
+        ///
+        /// Values are multiplied by `scale` (default 1), then converted to
+        /// radians if `units = "degrees"` (default "radians"). Columns
+        /// that aren't in the mapping are ignored.
+        #[arg(short, long, verbatim_doc_comment)]
+        mapping: Option<String>,
+    },
+
+    /// Make times continuous across a GPS week rollover.
+    ///
+    /// Points after the trajectory crosses from Saturday into Sunday have a
+    /// week's worth of seconds added, so times count past 604800 instead of
+    /// jumping back to zero.
+    FixTime {
+        /// The input file path.
+        ///
+        /// Omit or use `-` to read from stdin.
+        infile: Option<String>,
+
+        /// The output file path.
+        ///
+        /// Omit or use `-` to print to stdout.
+        outfile: Option<String>,
     },
 
     /// Filter an SBET file by a start and end time.
+    ///
+    /// The whole input is read, so unsorted files are filtered correctly.
+    /// For sorted files, `--sorted` stops reading at the first point after the
+    /// stop time, and `--use-index` also seeks to the start time.
     Filter {
         /// The input file path.
         ///
@@ -47,82 +414,2128 @@ enum Command {
         outfile: Option<String>,
 
         /// The start time.
-        #[arg(long, default_value = "-inf")]
+        #[arg(long, default_value = "-inf", conflicts_with = "start")]
         start_time: f64,
 
         /// The stop time.
-        #[arg(long, default_value = "+inf")]
+        #[arg(long, default_value = "+inf", conflicts_with = "stop")]
         stop_time: f64,
+
+        /// The start time as a UTC datetime, e.g. `2023-06-01T14:00:00Z`.
+        #[arg(long)]
+        start: Option<DateTime<Utc>>,
+
+        /// The stop time as a UTC datetime, e.g. `2023-06-01T15:00:00Z`.
+        #[arg(long)]
+        stop: Option<DateTime<Utc>>,
+
+        /// The GPS week of the SBET file's seconds-of-week times.
+        ///
+        /// Used to convert `--start` and `--stop`. Defaults to the week
+        /// containing `--start` (or `--stop`, if there is no start).
+        #[arg(long)]
+        gps_week: Option<u32>,
+
+        /// Time windows to remove, as comma-separated `start:stop` pairs, e.g. from the `dynamics` subcommand.
+        #[arg(long, value_delimiter = ',')]
+        exclude: Vec<TimeWindow>,
+
+        /// Seek to the start time with the file's `.sbx` index, from the `index` subcommand.
+        ///
+        /// The file must be sorted by time, so reading stops after the stop time.
+        #[arg(long)]
+        use_index: bool,
+
+        /// Stop reading at the first point after the stop time.
+        ///
+        /// Only use this if the input is sorted by time, or later points within the window are dropped.
+        #[arg(long)]
+        sorted: bool,
+
+        /// Remove epochs whose RMS values in this smrmsg file exceed the `--max-*-rms` limits.
+        ///
+        /// Epochs outside the smrmsg file's times have unknown accuracy and are removed too.
+        #[arg(long)]
+        smrmsg: Option<String>,
+
+        /// The largest horizontal position RMS, in meters.
+        #[arg(long, default_value = "1", requires = "smrmsg")]
+        max_horizontal_rms: f64,
+
+        /// The largest vertical position RMS, in meters.
+        #[arg(long, default_value = "1", requires = "smrmsg")]
+        max_vertical_rms: f64,
+
+        /// The largest north, east, or down velocity RMS, in meters per second.
+        #[arg(long, default_value = "inf", requires = "smrmsg")]
+        max_velocity_rms: f64,
+
+        /// The largest roll or pitch RMS, in arc minutes.
+        #[arg(long, default_value = "inf", requires = "smrmsg")]
+        max_attitude_rms: f64,
+
+        /// The largest heading RMS, in arc minutes.
+        #[arg(long, default_value = "inf", requires = "smrmsg")]
+        max_heading_rms: f64,
+
+        /// Keep epochs that exceed the smrmsg limits, and only report them.
+        #[arg(long, requires = "smrmsg")]
+        flag_only: bool,
+
+        /// Write the time windows of epochs that exceed the smrmsg limits to this file, one `start:stop` per line.
+        #[arg(long, requires = "smrmsg")]
+        rejected: Option<String>,
     },
-}
 
-fn main() {
-    let args = Args::parse();
-    match args.command {
-        Command::Filter {
-            infile,
-            outfile,
-            start_time,
-            stop_time,
-        } => {
-            let reader: Reader<Box<dyn Read>> = if let Some(infile) = infile.filter(|s| s != "-") {
-                let reader = BufReader::new(File::open(infile).unwrap());
-                Reader(Box::new(reader))
-            } else {
-                Reader(Box::new(std::io::stdin()))
-            };
-            let mut writer: Writer<Box<dyn Write>> =
-                if let Some(outfile) = outfile.filter(|s| s != "-") {
-                    let writer = BufWriter::new(File::create(outfile).unwrap());
-                    Writer(Box::new(writer))
-                } else {
-                    Writer(Box::new(std::io::stdout()))
-                };
-            for result in reader {
-                let point = result.unwrap();
-                if (point.time >= start_time) & (point.time <= stop_time) {
-                    writer.write_one(point).unwrap()
-                }
-            }
-        }
-        Command::ToCsv {
-            infile,
-            outfile,
-            decimate,
-            include_time,
-        } => {
-            let reader: Reader<Box<dyn Read>> = if let Some(infile) = infile.filter(|s| s != "-") {
-                let reader = BufReader::new(File::open(infile).unwrap());
-                Reader(Box::new(reader))
-            } else {
-                Reader(Box::new(std::io::stdin()))
-            };
-            let mut writer: Box<dyn Write> = if let Some(outfile) = outfile.filter(|s| s != "-") {
-                let writer = BufWriter::new(File::create(outfile).unwrap());
-                Box::new(writer)
-            } else {
-                Box::new(std::io::stdout())
-            };
-            write!(writer, "latitude,longitude,altitude").unwrap();
-            if include_time {
-                write!(writer, ",time").unwrap();
-            }
-            writeln!(writer, "").unwrap();
-            for result in reader.step_by(decimate) {
-                let point = result.unwrap();
-                write!(
-                    writer,
-                    "{},{},{}",
-                    point.latitude.to_degrees(),
-                    point.longitude.to_degrees(),
-                    point.altitude
-                )
-                .unwrap();
-                if include_time {
-                    write!(writer, ",{}", point.time).unwrap();
-                }
-                writeln!(writer, "").unwrap();
-            }
-        }
-    }
+    /// Print the time windows of turns and turbulence, one `start:stop` per line.
+    ///
+    /// Pass the windows to `filter --exclude` to remove them.
+    Dynamics {
+        /// The input file path.
+        ///
+        /// Omit or use `-` to read from stdin.
+        infile: Option<String>,
+
+        /// The largest absolute roll, in degrees.
+        #[arg(long, default_value = "10")]
+        roll: f64,
+
+        /// The largest angular rate, in degrees per second.
+        #[arg(long, default_value = "10")]
+        angular_rate: f64,
+
+        /// Seconds to add before and after each window.
+        #[arg(long, default_value = "0")]
+        padding: f64,
+    },
+
+    /// Compare two SBET files record-by-record.
+    ///
+    /// Records are matched by time, and the maximum and RMS difference of
+    /// each field is reported. Exits with a non-zero status if any record is
+    /// unmatched or any difference exceeds the tolerance.
+    Diff {
+        /// The first file path.
+        first: String,
+
+        /// The second file path.
+        second: String,
+
+        /// Times and field values within this tolerance are considered equal.
+        #[arg(short, long, default_value = "1e-9")]
+        tolerance: f64,
+
+        /// Report differences over the overlapping time range instead,
+        /// interpolating the second file at the first file's times.
+        ///
+        /// Always exits successfully, and ignores the tolerance.
+        #[arg(long)]
+        report: bool,
+    },
+
+    /// Estimate the constant time offset between two SBET files.
+    ///
+    /// Cross-correlates a field or derived value, which helps diagnose GPS
+    /// time misconfiguration between systems. The printed offset is what to
+    /// subtract from the second file's times to align it with the first.
+    Offset {
+        /// The first file path.
+        first: String,
+
+        /// The second file path.
+        second: String,
+
+        /// The field or derived value to correlate, e.g. "speed" or "altitude".
+        #[arg(long, default_value = "speed")]
+        quantity: Quantity,
+
+        /// The time step, in seconds, between correlated samples.
+        #[arg(short, long, default_value = "0.01")]
+        resolution: f64,
+
+        /// The largest offset to search for, in seconds.
+        #[arg(short, long, default_value = "10")]
+        max_offset: f64,
+    },
+
+    /// Print an overview of an SBET file: its number of points, time span, sample rate, and length.
+    Info {
+        /// The input file path.
+        ///
+        /// Omit or use `-` to read from stdin.
+        infile: Option<String>,
+
+        /// Only print the number of points and the start and end times, read from the first and last records.
+        ///
+        /// This is nearly instant on large files, but needs an uncompressed
+        /// file path. Fails if the file ends with a partial record.
+        #[arg(long)]
+        times: bool,
+    },
+
+    /// Write a sparse time index of an SBET file to a `.sbx` sidecar next to it.
+    ///
+    /// Use the index with `filter --use-index` to extract time windows
+    /// without reading the whole file. Rebuild it if the file changes.
+    Index {
+        /// The input file path, which must be an uncompressed local file.
+        infile: String,
+
+        /// The number of records between indexed records.
+        #[arg(long, default_value_t = sbet::Index::DEFAULT_STRIDE)]
+        stride: u64,
+    },
+
+    /// Print the first points of an SBET file, with angles in degrees.
+    Head {
+        /// The input file path.
+        ///
+        /// Omit or use `-` to read from stdin.
+        infile: Option<String>,
+
+        /// The number of points to print.
+        #[arg(short = 'n', long, default_value_t = 10)]
+        count: usize,
+    },
+
+    /// Print the last points of an SBET file, with angles in degrees.
+    ///
+    /// Seeks to the last points instead of reading the whole file.
+    Tail {
+        /// The input file path, which must be an uncompressed local file.
+        infile: String,
+
+        /// The number of points to print.
+        #[arg(short = 'n', long, default_value_t = 10)]
+        count: u64,
+
+        /// Keep printing points as they're appended, e.g. during acquisition.
+        #[arg(short, long)]
+        follow: bool,
+
+        /// Seconds between checks for new points when following.
+        #[arg(long, default_value_t = 1.)]
+        interval: f64,
+    },
+
+    /// Print points interpolated at times, reading only a few records of the file.
+    ///
+    /// The file must be sorted by time.
+    Interpolate {
+        /// The input file path, which must be an uncompressed local file.
+        infile: String,
+
+        /// The times to interpolate at, comma-separated.
+        #[arg(short, long, required = true, value_delimiter = ',')]
+        times: Vec<f64>,
+
+        /// Narrow each search with the file's `.sbx` index, from the `index` subcommand.
+        #[arg(long)]
+        use_index: bool,
+
+        /// Print each point as a JSON object, with angles in radians, instead of as text.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Print one record of an SBET file, seeking directly to it.
+    Get {
+        /// The input file path, which must be an uncompressed local file.
+        infile: String,
+
+        /// The index of the record, counting from zero.
+        #[arg(short, long)]
+        index: u64,
+
+        /// Print the record as a JSON object, with angles in radians, instead of as text.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Plot a field against time, or the ground track, to an image or the terminal.
+    ///
+    /// Angles are plotted in degrees.
+    #[cfg(feature = "plot")]
+    Plot {
+        /// The input file path.
+        ///
+        /// Omit or use `-` to read from stdin.
+        infile: Option<String>,
+
+        /// The field or derived value to plot against time, e.g. `altitude` or `speed`.
+        #[arg(long, required_unless_present = "ground_track")]
+        field: Option<Quantity>,
+
+        /// Plot latitude against longitude instead of a field.
+        #[arg(long, conflicts_with_all = ["field", "terminal"])]
+        ground_track: bool,
+
+        /// The image path; `.svg` files are drawn as SVG, and others as bitmaps, e.g. `.png`.
+        #[arg(short, long, required_unless_present = "terminal")]
+        output: Option<String>,
+
+        /// Print a one-line sparkline to the terminal instead of drawing an image.
+        #[arg(long, conflicts_with = "output")]
+        terminal: bool,
+
+        /// The width of the sparkline, in characters.
+        #[arg(long, default_value_t = 80)]
+        width: usize,
+    },
+
+    /// Print a histogram of the times between points, with the number of dropped epochs in each bucket.
+    ///
+    /// Intervals are measured in nominal time steps, so an interval of
+    /// three steps means two epochs were dropped.
+    Histogram {
+        /// The input file path.
+        ///
+        /// Omit or use `-` to read from stdin.
+        infile: Option<String>,
+
+        /// The number of buckets; the last one also counts every longer interval.
+        #[arg(short, long, default_value_t = 10)]
+        buckets: usize,
+    },
+
+    /// Write a quality control report with bounds, sample rate, gaps, and statistics.
+    ///
+    /// The report includes an image of the ground track if the `plot`
+    /// feature is enabled.
+    Report {
+        /// The input file path.
+        ///
+        /// Omit or use `-` to read from stdin.
+        infile: Option<String>,
+
+        /// The output file path.
+        ///
+        /// Omit or use `-` to print to stdout.
+        outfile: Option<String>,
+
+        /// The document format, "md" or "html".
+        #[arg(short, long, default_value = "md")]
+        format: sbet::report::Format,
+
+        /// The report title.
+        #[arg(long)]
+        title: Option<String>,
+
+        /// List gaps where points are more than this many seconds apart.
+        ///
+        /// Defaults to one and a half nominal time steps.
+        #[arg(long)]
+        gap: Option<f64>,
+    },
+
+    /// Print the minimum and maximum latitude, longitude, and altitude.
+    Bounds {
+        /// The input file path.
+        ///
+        /// Omit or use `-` to read from stdin.
+        infile: Option<String>,
+
+        /// Print latitudes and longitudes in radians instead of degrees.
+        #[arg(long)]
+        radians: bool,
+    },
+
+    /// Print summary statistics of each field and derived value.
+    ///
+    /// Values are in SBET units: meters, seconds, and radians, except
+    /// velocities and speeds, which are in `--speed-units`.
+    Stats {
+        /// The input file path.
+        ///
+        /// Omit or use `-` to read from stdin.
+        infile: Option<String>,
+
+        /// Also print the 5th, 50th, and 95th percentiles.
+        ///
+        /// Every value is kept in memory to compute them.
+        #[arg(long)]
+        percentiles: bool,
+
+        /// Only print roll, pitch, the angular rates, and speed, with angles in degrees.
+        #[arg(long)]
+        dynamics: bool,
+    },
+
+    /// Check that an SBET file survives a read-write-read cycle unchanged.
+    ///
+    /// Reads the file, writes its points to a temporary file, and compares
+    /// the two files byte-for-byte. Exits with a non-zero status if they
+    /// differ.
+    Roundtrip {
+        /// The input file path.
+        infile: String,
+    },
+
+    /// Stitch consecutive weekly SBET files into one continuous trajectory.
+    ///
+    /// Output times are seconds since the GPS epoch instead of seconds-of-week.
+    Stitch {
+        /// The input file paths.
+        #[arg(required = true)]
+        infiles: Vec<String>,
+
+        /// The GPS week of each input file, comma-separated and in the same order.
+        #[arg(short, long, required = true, value_delimiter = ',')]
+        weeks: Vec<u32>,
+
+        /// The output file path.
+        ///
+        /// Omit or use `-` to print to stdout.
+        #[arg(short, long)]
+        outfile: Option<String>,
+    },
+
+    /// Remove points that repeat the previous point's time.
+    Dedupe {
+        /// The input file path.
+        ///
+        /// Omit or use `-` to read from stdin.
+        infile: Option<String>,
+
+        /// The output file path.
+        ///
+        /// Omit or use `-` to print to stdout.
+        outfile: Option<String>,
+
+        /// What to do with duplicates: "keep-first", "keep-last", "average", or "error".
+        #[arg(short, long, default_value = "keep-first")]
+        policy: DuplicatePolicy,
+    },
+
+    /// Interpolate the trajectory at each point of a LAS or LAZ file.
+    ///
+    /// Writes an SBET sidecar with one record per LAS point, in the same
+    /// order. Points outside of the trajectory get a record with their time
+    /// and NaN for every other field.
+    #[cfg(feature = "las")]
+    LasAttach {
+        /// The LAS or LAZ file path.
+        las: String,
+
+        /// The SBET file path.
+        ///
+        /// Use `-` to read from stdin.
+        sbet: String,
+
+        /// The output sidecar file path.
+        ///
+        /// Omit or use `-` to print to stdout.
+        outfile: Option<String>,
+    },
+
+    /// Compute the range and off-nadir angle from the sensor to each point of a LAS or LAZ file.
+    ///
+    /// Writes CSV with `gps_time`, `range` in meters, and `off_nadir` in
+    /// degrees from the platform's down axis, one row per LAS point.
+    #[cfg(feature = "las")]
+    LasGeometry {
+        /// The LAS or LAZ file path.
+        las: String,
+
+        /// The SBET file path.
+        ///
+        /// Use `-` to read from stdin.
+        sbet: String,
+
+        /// The output CSV file path.
+        ///
+        /// Omit or use `-` to print to stdout.
+        outfile: Option<String>,
+
+        /// The LAS coordinates: "geographic" for longitude and latitude in
+        /// degrees, or a UTM zone like "10N" if built with the `utm` feature.
+        #[arg(long, default_value = "geographic")]
+        coordinates: sbet::las::Coordinates,
+    },
+
+    /// Split an SBET file into flight lines and write them as GeoJSON.
+    ///
+    /// Each line is a LineString feature with its number, start and end
+    /// times, and mean true heading in degrees.
+    Lines {
+        /// The input file path.
+        ///
+        /// Omit or use `-` to read from stdin.
+        infile: Option<String>,
+
+        /// The output file path.
+        ///
+        /// Omit or use `-` to print to stdout.
+        outfile: Option<String>,
+
+        /// The largest change in heading within a line, in degrees.
+        #[arg(long, default_value = "5")]
+        heading: f64,
+
+        /// The largest absolute roll within a line, in degrees.
+        #[arg(long, default_value = "5")]
+        roll: f64,
+
+        /// The shortest line, in seconds.
+        #[arg(long, default_value = "30")]
+        min_duration: f64,
+
+        /// Also write the turns between lines.
+        #[arg(long)]
+        turns: bool,
+    },
+
+    /// Merge two SBET files with overlapping times, like forward and backward solutions.
+    ///
+    /// Where the files overlap, the first file's points are blended with the
+    /// second file interpolated at the same times.
+    Merge {
+        /// The first file path.
+        first: String,
+
+        /// The second file path.
+        second: String,
+
+        /// The output file path.
+        ///
+        /// Omit or use `-` to print to stdout.
+        outfile: Option<String>,
+
+        /// How to blend the overlap: "equal", "linear", or the first file's weight from 0 to 1.
+        #[arg(short, long, default_value = "equal")]
+        weighting: Weighting,
+    },
+
+    /// Report which SBET files overlap in time, and by how much.
+    ///
+    /// Only the first and last records of each file are read, so the files
+    /// must be uncompressed and sorted by time. Exits with an error if any
+    /// files overlap.
+    Overlaps {
+        /// The input file paths.
+        #[arg(required = true)]
+        infiles: Vec<String>,
+    },
+
+    /// Interpolate points at a fixed rate.
+    Resample {
+        /// The input file path.
+        ///
+        /// Omit or use `-` to read from stdin.
+        infile: Option<String>,
+
+        /// The output file path.
+        ///
+        /// Omit or use `-` to print to stdout.
+        outfile: Option<String>,
+
+        /// The output rate, in hertz.
+        #[arg(short, long)]
+        rate: f64,
+
+        /// Don't interpolate across gaps longer than this many seconds.
+        #[arg(long)]
+        max_gap: Option<f64>,
+    },
+
+    /// Write one point per time interval, independent of the input rate.
+    ///
+    /// Unlike `--decimate`, which keeps every nth record, this samples by
+    /// time, so the output rate doesn't depend on the input rate.
+    Sample {
+        /// The input file path.
+        ///
+        /// Omit or use `-` to read from stdin.
+        infile: Option<String>,
+
+        /// The output file path.
+        ///
+        /// Omit or use `-` to print to stdout.
+        outfile: Option<String>,
+
+        /// The interval between samples, in seconds.
+        #[arg(short, long)]
+        every: f64,
+
+        /// How to pick each sample, "nearest" for the nearest record or "interpolate".
+        #[arg(short, long, default_value = "interpolate")]
+        method: sbet::SampleMethod,
+
+        /// Write CSV with every field instead of SBET records.
+        #[arg(long)]
+        csv: bool,
+    },
+
+    /// Smooth noisy fields with a centered moving average.
+    Smooth {
+        /// The input file path.
+        ///
+        /// Omit or use `-` to read from stdin.
+        infile: Option<String>,
+
+        /// The output file path.
+        ///
+        /// Omit or use `-` to print to stdout.
+        outfile: Option<String>,
+
+        /// The fields to smooth, comma-separated.
+        ///
+        /// Defaults to the accelerations and angular rates.
+        #[arg(long, value_delimiter = ',')]
+        fields: Option<Vec<Field>>,
+
+        /// The number of points to average, which must be odd.
+        #[arg(short, long, default_value = "5")]
+        window: usize,
+    },
+
+    /// Remove or re-interpolate points whose position or attitude jumps implausibly.
+    ///
+    /// Each point is compared with where the previous good point's
+    /// velocity and angular rates could have taken it.
+    Despike {
+        /// The input file path.
+        ///
+        /// Omit or use `-` to read from stdin.
+        infile: Option<String>,
+
+        /// The output file path.
+        ///
+        /// Omit or use `-` to print to stdout.
+        outfile: Option<String>,
+
+        /// The largest allowed position error, in meters.
+        #[arg(long, default_value = "1")]
+        position: f64,
+
+        /// The largest allowed attitude error, in degrees.
+        #[arg(long, default_value = "1")]
+        attitude: f64,
+
+        /// Replace outliers with interpolated points instead of removing them.
+        #[arg(long)]
+        interpolate: bool,
+    },
+
+    /// Recover the readable records from a damaged SBET file.
+    ///
+    /// Implausible records are skipped, scanning forward to the next
+    /// plausible record boundary. Skipped byte ranges are printed to stderr.
+    Recover {
+        /// The input file path.
+        ///
+        /// Omit or use `-` to read from stdin.
+        infile: Option<String>,
+
+        /// The output file path.
+        ///
+        /// Omit or use `-` to print to stdout.
+        outfile: Option<String>,
+    },
+
+    /// Report whether files look like valid SBETs.
+    ///
+    /// Samples the first records of each file and prints a confidence
+    /// score. Exits with a non-zero status if any file doesn't look like an
+    /// SBET.
+    Sniff {
+        /// The input file paths.
+        #[arg(required = true)]
+        infiles: Vec<String>,
+    },
+
+    /// Convert an SBET file between byte orders.
+    ///
+    /// Some legacy toolchains write big-endian records; this converts them
+    /// to standard little-endian SBET.
+    Convert {
+        /// The input file path.
+        ///
+        /// Omit or use `-` to read from stdin.
+        infile: Option<String>,
+
+        /// The output file path.
+        ///
+        /// Omit or use `-` to print to stdout.
+        outfile: Option<String>,
+
+        /// The byte order of the input file, "little" or "big".
+        ///
+        /// Detected from the first few records if omitted.
+        #[arg(long)]
+        from: Option<Endianness>,
+
+        /// The byte order of the output file, "little" or "big".
+        #[arg(long, default_value = "little")]
+        to: Endianness,
+    },
+
+    /// Print a shell completion script to stdout.
+    ///
+    /// See also `sbet man` for man pages.
+    Completions {
+        /// The shell, e.g. "bash", "zsh", or "fish".
+        shell: Shell,
+    },
+
+    /// Write man pages for sbet and each of its subcommands.
+    ///
+    /// E.g. `sbet man /usr/share/man/man1` when packaging.
+    Man {
+        /// The output directory, which is created if it doesn't exist.
+        #[arg(default_value = ".")]
+        out_dir: String,
+    },
+}
+
+/// Exit status when a file can't be read or written.
+const EXIT_IO: i32 = 74;
+
+/// Exit status when input data is malformed or invalid.
+const EXIT_DATA: i32 = 65;
+
+fn main() {
+    let args = Args::parse();
+    let level = match (args.quiet, args.verbose) {
+        (true, _) => Level::WARN,
+        (false, 0) => Level::INFO,
+        (false, 1) => Level::DEBUG,
+        (false, _) => Level::TRACE,
+    };
+    tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_writer(std::io::stderr)
+        .with_ansi(std::io::stderr().is_terminal())
+        .init();
+    if let Err(err) = run(args) {
+        if is_broken_pipe(&err) {
+            return;
+        }
+        eprintln!("error: {:#}", err);
+        std::process::exit(exit_code(&err));
+    }
+}
+
+fn run(args: Args) -> Result<()> {
+    let options = Options {
+        compress: args.compress,
+        strict: args.strict,
+        speed_units: args.speed_units,
+        quiet: args.quiet,
+    };
+    match args.command {
+        Command::Filter {
+            infile,
+            outfile,
+            start_time,
+            stop_time,
+            start,
+            stop,
+            gps_week,
+            exclude,
+            use_index,
+            sorted,
+            smrmsg,
+            max_horizontal_rms,
+            max_vertical_rms,
+            max_velocity_rms,
+            max_attitude_rms,
+            max_heading_rms,
+            flag_only,
+            rejected,
+        } => {
+            let gps_week = gps_week.or_else(|| {
+                start
+                    .or(stop)
+                    .map(|datetime| sbet::time::gps_week(datetime).0)
+            });
+            let start_time = start
+                .zip(gps_week)
+                .map(|(start, week)| sbet::time::seconds_of_week(start, week))
+                .unwrap_or(start_time);
+            let stop_time = stop
+                .zip(gps_week)
+                .map(|(stop, week)| sbet::time::seconds_of_week(stop, week))
+                .unwrap_or(stop_time);
+            let name = input_name(&infile);
+            let window = TimeWindow::new(start_time, stop_time);
+            let reader = options.open_window(&infile, window, use_index, sorted)?;
+            let mut accuracy_filter = match &smrmsg {
+                Some(path) => {
+                    let records = sbet::smrmsg::from_path(path)
+                        .with_context(|| format!("failed to read {}", path))?;
+                    let limits = sbet::smrmsg::Limits {
+                        horizontal_position: max_horizontal_rms,
+                        vertical_position: max_vertical_rms,
+                        velocity: max_velocity_rms,
+                        attitude: max_attitude_rms,
+                        heading: max_heading_rms,
+                    };
+                    Some(sbet::smrmsg::AccuracyFilter::new(records, limits))
+                }
+                None => None,
+            };
+            let mut writer = Writer::new(options.open_writer(&outfile)?);
+            for result in reader {
+                let point = result.with_context(|| format!("failed to read {}", name))?;
+                if exclude.iter().any(|window| window.contains(point.time)) {
+                    continue;
+                }
+                let accurate = accuracy_filter
+                    .as_mut()
+                    .is_none_or(|accuracy_filter| accuracy_filter.accepts(&point));
+                if accurate || flag_only {
+                    writer.write_one(point)?;
+                }
+            }
+            if let Some(accuracy_filter) = &accuracy_filter {
+                let windows = accuracy_filter.rejected();
+                if !windows.is_empty() {
+                    warn!(
+                        "{} time windows exceed the smrmsg limits, from {} to {}",
+                        windows.len(),
+                        windows[0].start,
+                        windows[windows.len() - 1].stop
+                    );
+                }
+                if let Some(path) = &rejected {
+                    let mut file = std::io::BufWriter::new(
+                        File::create(path).with_context(|| format!("failed to create {}", path))?,
+                    );
+                    for window in windows {
+                        writeln!(file, "{}", window)?;
+                    }
+                    file.flush()?;
+                }
+            }
+            let stats = writer.stats();
+            writer.finish()?.finish()?;
+            match (stats.first_time, stats.last_time) {
+                (Some(first), Some(last)) => {
+                    info!("wrote {} points from {} to {}", stats.points, first, last)
+                }
+                _ => info!("wrote no points"),
+            }
+        }
+        Command::Dynamics {
+            infile,
+            roll,
+            angular_rate,
+            padding,
+        } => {
+            let trajectory = options.read_trajectory(&infile)?;
+            let windows = trajectory.high_dynamics(DynamicsLimits {
+                roll: roll.to_radians(),
+                angular_rate: angular_rate.to_radians(),
+                padding,
+            });
+            for window in windows {
+                println!("{}", window);
+            }
+        }
+        Command::ToCsv {
+            infile,
+            outfile,
+            decimate,
+            include_time,
+            fields,
+            all_fields,
+            delimiter,
+            precision,
+            in_degrees,
+            in_radians,
+            degrees,
+            week,
+            date,
+            geoid,
+            #[cfg(feature = "dem")]
+            agl,
+            #[cfg(feature = "proj")]
+            epsg,
+            start_time,
+            stop_time,
+            use_index,
+            sorted,
+        } => {
+            if decimate == 0 {
+                bail!("decimation must be positive");
+            }
+            let week = week.or(date.map(sbet::time::week_of_date));
+            let mut fields: Vec<Quantity> = if all_fields {
+                Field::ALL.into_iter().map(Quantity::from).collect()
+            } else {
+                fields.unwrap_or_else(|| {
+                    vec![
+                        Field::Latitude.into(),
+                        Field::Longitude.into(),
+                        Field::Altitude.into(),
+                    ]
+                })
+            };
+            if include_time {
+                fields.push(Field::Time.into());
+            }
+            if let Some(quantity) = in_degrees
+                .iter()
+                .chain(&in_radians)
+                .find(|quantity| !quantity.is_angular())
+            {
+                bail!("{} is not an angular field", quantity.name());
+            }
+            let name = input_name(&infile);
+            let window = TimeWindow::new(start_time, stop_time);
+            let reader = options.open_window(&infile, window, use_index, sorted)?;
+            let geoid = geoid
+                .map(|path| {
+                    sbet::Geoid::from_path(&path)
+                        .with_context(|| format!("failed to read geoid grid {}", path))
+                })
+                .transpose()?;
+            #[cfg(feature = "dem")]
+            let dem = agl
+                .map(|path| {
+                    sbet::Dem::from_path(&path)
+                        .with_context(|| format!("failed to read elevation model {}", path))
+                })
+                .transpose()?;
+            #[cfg(feature = "dem")]
+            let above_ground = dem.is_some();
+            #[cfg(not(feature = "dem"))]
+            let above_ground = false;
+            #[cfg(feature = "proj")]
+            let transformer = epsg.map(sbet::Transformer::from_epsg).transpose()?;
+            #[cfg(feature = "proj")]
+            let projected = transformer.is_some();
+            #[cfg(not(feature = "proj"))]
+            let projected = false;
+            let position = [Field::Latitude.into(), Field::Longitude.into()];
+            let degrees: Vec<Quantity> = if degrees {
+                fields
+                    .iter()
+                    .copied()
+                    .filter(|quantity| quantity.is_angular() && !in_radians.contains(quantity))
+                    .filter(|quantity| !projected || !position.contains(quantity))
+                    .collect()
+            } else {
+                position
+                    .into_iter()
+                    .filter(|quantity| !projected && !in_radians.contains(quantity))
+                    .chain(in_degrees)
+                    .collect()
+            };
+            let columns = fields
+                .into_iter()
+                .map(|quantity| {
+                    let mut column = csv::Column::new(quantity);
+                    column.units = if degrees.contains(&quantity) {
+                        csv::Units::Degrees
+                    } else {
+                        csv::Units::Native
+                    };
+                    if quantity.is_speed() {
+                        column.scale = 1. / options.speed_units.convert(1.);
+                    }
+                    match quantity {
+                        Quantity::Field(Field::Latitude) if projected => {
+                            column.name = "x".to_string()
+                        }
+                        Quantity::Field(Field::Longitude) if projected => {
+                            column.name = "y".to_string()
+                        }
+                        Quantity::Field(Field::Altitude) if above_ground => {
+                            column.name = "agl".to_string()
+                        }
+                        Quantity::Field(Field::Altitude) if geoid.is_some() => {
+                            column.name = "orthometric_height".to_string()
+                        }
+                        _ => {}
+                    }
+                    column
+                })
+                .chain(week.map(|week| csv::Column {
+                    name: "utc".to_string(),
+                    units: csv::Units::Utc { week },
+                    ..csv::Column::new(Field::Time)
+                }))
+                .collect();
+            let mut writer = csv::Writer::with_columns(options.open_writer(&outfile)?, columns);
+            writer.set_delimiter(delimiter);
+            writer.set_precision(precision);
+            for result in reader.step_by(decimate) {
+                let mut point = result.with_context(|| format!("failed to read {}", name))?;
+                if let Some(geoid) = &geoid {
+                    point.altitude = geoid.orthometric_height(&point).ok_or_else(|| {
+                        anyhow!("point at time {} is outside of the geoid grid", point.time)
+                    })?;
+                }
+                #[cfg(feature = "dem")]
+                if let Some(dem) = &dem {
+                    point.altitude = dem.height_above_ground(&point).ok_or_else(|| {
+                        anyhow!(
+                            "point at time {} is outside of the elevation model",
+                            point.time
+                        )
+                    })?;
+                }
+                #[cfg(feature = "proj")]
+                if let Some(transformer) = &transformer {
+                    let (x, y, _) = transformer.transform(&point)?;
+                    point.latitude = x;
+                    point.longitude = y;
+                }
+                writer.write_one(&point)?;
+            }
+            writer.finish()?.finish()?;
+        }
+        Command::ToJsonl {
+            infile,
+            outfile,
+            decimate,
+        } => {
+            if decimate == 0 {
+                bail!("decimation must be positive");
+            }
+            let name = input_name(&infile);
+            let reader = options.open_reader(&infile)?;
+            let mut writer = JsonWriter::new(options.open_writer(&outfile)?);
+            for result in reader.step_by(decimate) {
+                let point = result.with_context(|| format!("failed to read {}", name))?;
+                writer.write_one(&point)?;
+            }
+            writer.finish()?.finish()?;
+        }
+        Command::ToGeojson {
+            infile,
+            outfile,
+            points,
+            hull,
+            decimate,
+            properties,
+            geoid,
+            #[cfg(feature = "proj")]
+            epsg,
+        } => {
+            if decimate == 0 {
+                bail!("decimation must be positive");
+            }
+            let geometry = if points {
+                geojson::Geometry::Points
+            } else if hull {
+                geojson::Geometry::ConvexHull
+            } else {
+                geojson::Geometry::LineString
+            };
+            let mut points = options
+                .open_reader(&infile)?
+                .into_points()
+                .with_context(|| format!("failed to read {}", input_name(&infile)))?;
+            if let Some(path) = geoid {
+                let geoid = sbet::Geoid::from_path(&path)
+                    .with_context(|| format!("failed to read geoid grid {}", path))?;
+                for point in &mut points {
+                    point.altitude = geoid.orthometric_height(point).ok_or_else(|| {
+                        anyhow!("point at time {} is outside of the geoid grid", point.time)
+                    })?;
+                }
+            }
+            #[cfg(feature = "proj")]
+            let projected = epsg.is_some();
+            #[cfg(not(feature = "proj"))]
+            let projected = false;
+            #[cfg(feature = "proj")]
+            if let Some(transformer) = epsg.map(sbet::Transformer::from_epsg).transpose()? {
+                for point in &mut points {
+                    let (x, y, _) = transformer.transform(point)?;
+                    point.longitude = x;
+                    point.latitude = y;
+                }
+            }
+            let mut output = options.open_writer(&outfile)?;
+            geojson::Builder::new(geometry)
+                .decimate(decimate)
+                .properties(properties)
+                .speed_units(options.speed_units)
+                .projected(projected)
+                .write(&points, &mut output)?;
+            writeln!(output)?;
+            output.finish()?;
+        }
+        Command::ToKml {
+            infile,
+            outfile,
+            name,
+            color,
+            width,
+            decimate,
+            week,
+            date,
+        } => {
+            if decimate == 0 {
+                bail!("decimation must be positive");
+            }
+            let points = options
+                .open_reader(&infile)?
+                .into_points()
+                .with_context(|| format!("failed to read {}", input_name(&infile)))?;
+            let mut builder = kml::Builder::new()
+                .coloring(color)
+                .width(width)
+                .decimate(decimate);
+            if let Some(name) = &name {
+                builder = builder.name(name);
+            }
+            if let Some(week) = week.or(date.map(sbet::time::week_of_date)) {
+                builder = builder.timestamps(week);
+            }
+            let mut output = options.open_writer(&outfile)?;
+            builder.write(&points, &mut output)?;
+            output.finish()?;
+        }
+        Command::ToGpx {
+            infile,
+            outfile,
+            name,
+            decimate,
+            gap,
+            week,
+            date,
+        } => {
+            if decimate == 0 {
+                bail!("decimation must be positive");
+            }
+            let points = options
+                .open_reader(&infile)?
+                .into_points()
+                .with_context(|| format!("failed to read {}", input_name(&infile)))?;
+            let mut builder = gpx::Builder::new().decimate(decimate);
+            if let Some(name) = &name {
+                builder = builder.name(name);
+            }
+            if let Some(gap) = gap {
+                builder = builder.gap(gap);
+            }
+            if let Some(week) = week.or(date.map(sbet::time::week_of_date)) {
+                builder = builder.timestamps(week);
+            }
+            let mut output = options.open_writer(&outfile)?;
+            builder.write(&points, &mut output)?;
+            output.finish()?;
+        }
+        Command::ToCzml {
+            infile,
+            outfile,
+            week,
+            date,
+            name,
+            model,
+            decimate,
+        } => {
+            if decimate == 0 {
+                bail!("decimation must be positive");
+            }
+            let week = week
+                .or(date.map(sbet::time::week_of_date))
+                .ok_or_else(|| anyhow!("a GPS week or date is required"))?;
+            let points = options
+                .open_reader(&infile)?
+                .into_points()
+                .with_context(|| format!("failed to read {}", input_name(&infile)))?;
+            let mut builder = czml::Builder::new(week).decimate(decimate);
+            if let Some(name) = &name {
+                builder = builder.name(name);
+            }
+            if let Some(model) = &model {
+                builder = builder.model(model);
+            }
+            let mut output = options.open_writer(&outfile)?;
+            builder.write(&points, &mut output)?;
+            output.finish()?;
+        }
+        Command::FromCsv {
+            infile,
+            outfile,
+            mapping,
+        } => {
+            let mapping = mapping
+                .map(|path| -> Result<Mapping> {
+                    let contents = std::fs::read_to_string(&path)
+                        .with_context(|| format!("failed to read {}", path))?;
+                    toml::from_str(&contents).with_context(|| format!("invalid mapping {}", path))
+                })
+                .transpose()?
+                .unwrap_or_default();
+            let name = input_name(&infile);
+            let input = BufReader::new(options.open_stream(&infile)?);
+            let mut reader = match mapping.columns() {
+                Some(columns) => csv::Reader::with_columns(input, columns),
+                None => csv::Reader::new(input),
+            };
+            reader.set_delimiter(mapping.delimiter.unwrap_or(','));
+            let mut writer = Writer::new(options.open_writer(&outfile)?);
+            for result in reader {
+                writer.write_one(result.with_context(|| format!("failed to read {}", name))?)?;
+            }
+            writer.finish()?.finish()?;
+        }
+        Command::FixTime { infile, outfile } => {
+            let mut points = options
+                .open_reader(&infile)?
+                .into_points()
+                .with_context(|| format!("failed to read {}", input_name(&infile)))?;
+            let rollovers = sbet::time::unwrap_week_crossing(&mut points);
+            info!("fixed {} week rollover(s)", rollovers);
+            let mut writer = Writer::new(options.open_writer(&outfile)?);
+            writer.write_all(&points)?;
+            writer.finish()?.finish()?;
+        }
+        Command::Diff {
+            first,
+            second,
+            tolerance,
+            report,
+        } => {
+            let first = options.read_points(&first)?;
+            let second = options.read_points(&second)?;
+            if report {
+                if !sbet::is_sorted(&first) || !sbet::is_sorted(&second) {
+                    bail!("both files must be sorted by time");
+                }
+                let report = sbet::compare(&first, &second);
+                println!("compared: {}", report.compared);
+                if let (Some(start), Some(end)) = (report.start_time, report.end_time) {
+                    println!("start time: {}", start);
+                    println!("end time: {}", end);
+                }
+                println!("{:<16}{:>24}{:>24}", "field", "max", "rms");
+                println!(
+                    "{:<16}{:>24e}{:>24e}",
+                    "horizontal", report.horizontal_max, report.horizontal_rms
+                );
+                for field in &report.fields {
+                    println!(
+                        "{:<16}{:>24e}{:>24e}",
+                        field.field.name(),
+                        field.max,
+                        field.rms
+                    );
+                }
+                return Ok(());
+            }
+            let diff = sbet::diff(&first, &second, tolerance);
+            println!("matched: {}", diff.matched);
+            println!("only in first: {}", diff.only_in_first);
+            println!("only in second: {}", diff.only_in_second);
+            println!("{:<16}{:>24}{:>24}", "field", "max", "rms");
+            for field in &diff.fields {
+                println!(
+                    "{:<16}{:>24e}{:>24e}",
+                    field.field.name(),
+                    field.max,
+                    field.rms
+                );
+            }
+            if !diff.is_within(tolerance) {
+                std::process::exit(1);
+            }
+        }
+        Command::Offset {
+            first,
+            second,
+            quantity,
+            resolution,
+            max_offset,
+        } => {
+            if resolution <= 0. {
+                bail!("resolution must be positive");
+            }
+            let first = options.read_points(&first)?;
+            let second = options.read_points(&second)?;
+            if !sbet::is_sorted(&first) || !sbet::is_sorted(&second) {
+                bail!("both files must be sorted by time");
+            }
+            let Some(offset) = sbet::time_offset(&first, &second, quantity, resolution, max_offset)
+            else {
+                bail!(
+                    "the files don't overlap, or {} is constant",
+                    quantity.name()
+                );
+            };
+            println!("offset: {}", offset.offset);
+            println!("correlation: {}", offset.correlation);
+        }
+        Command::Info {
+            infile: Some(path),
+            times: true,
+        } if path != "-" => {
+            let points = sbet::exact_number_of_points(&path)
+                .with_context(|| format!("failed to count the points in {}", path))?;
+            let (start, end) = sbet::time_bounds(&path)
+                .with_context(|| format!("failed to read time bounds of {}", path))?;
+            println!("points: {}", points);
+            println!("start time: {}", start);
+            println!("end time: {}", end);
+        }
+        Command::Info { times: true, .. } => bail!("--times needs an input file path"),
+        Command::Info { infile, .. } => {
+            let trajectory = options.read_trajectory(&infile)?;
+            println!("points: {}", trajectory.len());
+            if let (Some(first), Some(last)) =
+                (trajectory.points().first(), trajectory.points().last())
+            {
+                println!("start time: {}", first.time);
+                println!("end time: {}", last.time);
+                println!("duration: {} s", last.time - first.time);
+            }
+            if let Some(sample_rate) = trajectory.sample_rate() {
+                println!("nominal sample rate: {} Hz", sample_rate.nominal);
+                println!("detected sample rate: {:.3} Hz", sample_rate.detected);
+                println!("timing jitter (rms): {:e} s", sample_rate.jitter_rms);
+                println!("timing jitter (max): {:e} s", sample_rate.jitter_max);
+            }
+            println!("length: {:.3} m", trajectory.length_meters());
+        }
+        Command::Index { infile, stride } => {
+            let reader = Reader::new(BufReader::new(
+                File::open(&infile).with_context(|| format!("failed to open {}", infile))?,
+            ));
+            let index = sbet::Index::build(reader, stride)
+                .with_context(|| format!("failed to index {}", infile))?;
+            let path = sbet::Index::sidecar_path(&infile);
+            let mut writer = std::io::BufWriter::new(File::create(&path)?);
+            index.write(&mut writer)?;
+            writer.flush()?;
+            info!(
+                "indexed {} of {} records in {}",
+                index.len(),
+                index.records(),
+                path.display()
+            );
+        }
+        Command::Head { infile, count } => {
+            let name = input_name(&infile);
+            let mut stdout = std::io::stdout().lock();
+            for result in options.open_reader(&infile)?.take(count) {
+                let point = result.with_context(|| format!("failed to read {}", name))?;
+                writeln!(stdout, "{:.9}", point)?;
+            }
+        }
+        Command::Tail {
+            infile,
+            count,
+            follow,
+            interval,
+        } => {
+            let mut reader = Reader::new(BufReader::new(
+                File::open(&infile).with_context(|| format!("failed to open {}", infile))?,
+            ));
+            let records = reader.estimate_number_of_points()?;
+            reader.seek(records.saturating_sub(count))?;
+            let points: Box<dyn Iterator<Item = sbet::Result<sbet::Point>>> = if follow {
+                let interval = std::time::Duration::try_from_secs_f64(interval)
+                    .map_err(|_| anyhow!("invalid interval: {}", interval))?;
+                Box::new(reader.follow(interval))
+            } else {
+                Box::new(reader)
+            };
+            let mut stdout = std::io::stdout().lock();
+            for result in points {
+                let point = result.with_context(|| format!("failed to read {}", infile))?;
+                writeln!(stdout, "{:.9}", point)?;
+            }
+        }
+        Command::Interpolate {
+            infile,
+            times,
+            use_index,
+            json,
+        } => {
+            let index = if use_index {
+                Some(open_index(&infile)?)
+            } else {
+                None
+            };
+            let mut reader = Reader::new(BufReader::new(
+                File::open(&infile).with_context(|| format!("failed to open {}", infile))?,
+            ));
+            let mut stdout = std::io::stdout().lock();
+            for (i, &time) in times.iter().enumerate() {
+                let point = reader
+                    .query_time(time, index.as_ref())
+                    .with_context(|| format!("failed to interpolate {} at {}", infile, time))?;
+                if json {
+                    JsonWriter::new(&mut stdout).write_one(&point)?;
+                } else {
+                    if i > 0 {
+                        writeln!(stdout)?;
+                    }
+                    write!(stdout, "{:.9}", point)?;
+                }
+            }
+        }
+        Command::Get {
+            infile,
+            index,
+            json,
+        } => {
+            let mut reader = Reader::new(BufReader::new(
+                File::open(&infile).with_context(|| format!("failed to open {}", infile))?,
+            ));
+            reader.seek(index)?;
+            let point = reader
+                .read_one()
+                .with_context(|| format!("failed to read {}", infile))?
+                .ok_or_else(|| {
+                    anyhow!(
+                        "record {} is past the end of {}, which has {} records",
+                        index,
+                        infile,
+                        reader.estimate_number_of_points().unwrap_or_default()
+                    )
+                })?;
+            if json {
+                JsonWriter::new(std::io::stdout().lock()).write_one(&point)?;
+            } else {
+                print!("{:.9}", point);
+            }
+        }
+        #[cfg(feature = "plot")]
+        Command::Plot {
+            infile,
+            field,
+            ground_track,
+            output,
+            terminal,
+            width,
+        } => {
+            let trajectory = options.read_trajectory(&infile)?;
+            let plot = match field {
+                Some(quantity) if !ground_track => sbet::plot::Plot::Quantity(quantity),
+                _ => sbet::plot::Plot::GroundTrack,
+            };
+            match (plot, output) {
+                (sbet::plot::Plot::Quantity(quantity), None) if terminal => {
+                    let values: Vec<f64> = plot
+                        .data(trajectory.points())
+                        .into_iter()
+                        .map(|(_, value)| value)
+                        .collect();
+                    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+                    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                    println!("{}: {} to {}", quantity.name(), min, max);
+                    println!("{}", sbet::plot::sparkline(&values, width));
+                }
+                (plot, Some(output)) => {
+                    plot.write_to_path(trajectory.points(), &output)
+                        .with_context(|| format!("failed to plot to {}", output))?;
+                    info!("plotted {} points to {}", trajectory.len(), output);
+                }
+                _ => bail!("an output path is required"),
+            }
+        }
+        Command::Histogram { infile, buckets } => {
+            if buckets == 0 {
+                bail!("there must be at least one bucket");
+            }
+            let trajectory = options.read_trajectory(&infile)?;
+            let histogram = trajectory
+                .interval_histogram(buckets)
+                .ok_or_else(|| anyhow!("the sample rate can't be detected"))?;
+            println!(
+                "nominal interval: {} s ({} Hz)",
+                histogram.step,
+                1. / histogram.step
+            );
+            println!("{:>8} {:>12} {:>12}", "steps", "intervals", "dropped");
+            for (i, bucket) in histogram.buckets.iter().enumerate() {
+                let steps = if i + 1 == histogram.buckets.len() {
+                    format!("{}+", bucket.steps)
+                } else {
+                    bucket.steps.to_string()
+                };
+                println!(
+                    "{:>8} {:>12} {:>12}",
+                    steps, bucket.intervals, bucket.dropped
+                );
+            }
+            println!("dropped epochs: {}", histogram.dropped());
+            println!("repeated or backwards times: {}", histogram.non_positive);
+        }
+        Command::Report {
+            infile,
+            outfile,
+            format,
+            title,
+            gap,
+        } => {
+            let trajectory = options.read_trajectory(&infile)?;
+            let mut builder = sbet::report::Builder::new()
+                .format(format)
+                .speed_units(options.speed_units);
+            if let Some(title) = &title {
+                builder = builder.title(title);
+            }
+            if let Some(gap) = gap {
+                builder = builder.gap(gap);
+            }
+            let mut output = options.open_writer(&outfile)?;
+            builder.write(&trajectory, &mut output)?;
+            output.finish()?;
+        }
+        Command::Bounds { infile, radians } => {
+            let trajectory = options.read_trajectory(&infile)?;
+            let bounds = trajectory.bounds().ok_or(sbet::Error::NoPoints)?;
+            let bounds = if radians { bounds } else { bounds.to_degrees() };
+            println!("latitude: {} {}", bounds.min_latitude, bounds.max_latitude);
+            println!(
+                "longitude: {} {}",
+                bounds.min_longitude, bounds.max_longitude
+            );
+            println!("altitude: {} {}", bounds.min_altitude, bounds.max_altitude);
+        }
+        Command::Stats {
+            infile,
+            percentiles,
+            dynamics,
+        } => {
+            let name = input_name(&infile);
+            let mut stats = if percentiles {
+                sbet::Stats::with_percentiles()
+            } else {
+                sbet::Stats::new()
+            };
+            for result in options.open_reader(&infile)? {
+                stats.add(&result.with_context(|| format!("failed to read {}", name))?);
+            }
+            println!("count: {}", stats.count());
+            if stats.count() > 0 {
+                print!(
+                    "{:<24}{:>24}{:>24}{:>24}{:>24}{:>24}",
+                    "quantity", "min", "max", "max_abs", "mean", "std_dev"
+                );
+                if percentiles {
+                    print!("{:>24}{:>24}{:>24}", "p5", "p50", "p95");
+                }
+                println!();
+            }
+            let summaries = if dynamics {
+                stats.dynamics()
+            } else {
+                stats.summaries()
+            };
+            for summary in summaries {
+                let scale = if dynamics && summary.quantity.is_angular() {
+                    1f64.to_degrees()
+                } else if summary.quantity.is_speed() {
+                    options.speed_units.convert(1.)
+                } else {
+                    1.
+                };
+                print!(
+                    "{:<24}{:>24e}{:>24e}{:>24e}{:>24e}{:>24e}",
+                    summary.quantity.name(),
+                    summary.min * scale,
+                    summary.max * scale,
+                    summary.max_abs * scale,
+                    summary.mean * scale,
+                    summary.std_dev * scale
+                );
+                if let Some(percentiles) = summary.percentiles {
+                    print!(
+                        "{:>24e}{:>24e}{:>24e}",
+                        percentiles.p5 * scale,
+                        percentiles.p50 * scale,
+                        percentiles.p95 * scale
+                    );
+                }
+                println!();
+            }
+        }
+        Command::Roundtrip { infile } => {
+            let mut original = Vec::new();
+            options
+                .open_input(&infile)?
+                .read_to_end(&mut original)
+                .with_context(|| format!("failed to read {}", infile))?;
+            let points = options.read_points(&infile)?;
+            let path =
+                std::env::temp_dir().join(format!("sbet-roundtrip-{}.sbet", std::process::id()));
+            let mut writer = Writer::from_path(&path)?;
+            writer.write_all(&points)?;
+            writer.finish()?;
+            let written = std::fs::read(&path);
+            std::fs::remove_file(&path)?;
+            let written = written?;
+            if let Some(offset) = original.iter().zip(&written).position(|(a, b)| a != b) {
+                eprintln!(
+                    "{}: byte {} differs after round trip (record {}, field {})",
+                    infile,
+                    offset,
+                    offset / RECORD_SIZE,
+                    Field::ALL[offset % RECORD_SIZE / 8].name()
+                );
+                std::process::exit(1);
+            } else if original.len() != written.len() {
+                eprintln!(
+                    "{}: {} bytes read but {} bytes written after round trip",
+                    infile,
+                    original.len(),
+                    written.len()
+                );
+                std::process::exit(1);
+            } else {
+                println!(
+                    "{}: {} records round-tripped without changes",
+                    infile,
+                    written.len() / RECORD_SIZE
+                );
+            }
+        }
+        Command::Stitch {
+            infiles,
+            weeks,
+            outfile,
+        } => {
+            if infiles.len() != weeks.len() {
+                bail!(
+                    "got {} input files but {} weeks",
+                    infiles.len(),
+                    weeks.len()
+                );
+            }
+            let points = infiles
+                .iter()
+                .map(|infile| options.read_points(infile))
+                .collect::<Result<Vec<_>>>()?;
+            let points = sbet::time::stitch(weeks.into_iter().zip(points));
+            let mut writer = Writer::new(options.open_writer(&outfile)?);
+            writer.write_all(&points)?;
+            writer.finish()?.finish()?;
+        }
+        Command::Dedupe {
+            infile,
+            outfile,
+            policy,
+        } => {
+            let trajectory = options.read_trajectory(&infile)?;
+            let deduped = trajectory.dedup(policy)?;
+            info!("removed {} points", trajectory.len() - deduped.len());
+            let mut writer = Writer::new(options.open_writer(&outfile)?);
+            writer.write_all(deduped.points())?;
+            writer.finish()?.finish()?;
+        }
+        #[cfg(feature = "las")]
+        Command::LasAttach { las, sbet, outfile } => {
+            let trajectory = options.read_trajectory(&Some(sbet))?;
+            if !trajectory.is_sorted() {
+                bail!("the SBET file must be sorted by time");
+            }
+            let mut reader =
+                las::Reader::from_path(&las).with_context(|| format!("failed to open {}", las))?;
+            let mut writer = options.open_writer(&outfile)?;
+            let attached = sbet::las::attach(&mut reader, trajectory.points(), &mut writer)?;
+            writer.finish()?;
+            info!(
+                "attached {} points, {} outside of the trajectory",
+                attached.matched, attached.unmatched
+            );
+        }
+        #[cfg(feature = "las")]
+        Command::LasGeometry {
+            las,
+            sbet,
+            outfile,
+            coordinates,
+        } => {
+            let trajectory = options.read_trajectory(&Some(sbet))?;
+            if !trajectory.is_sorted() {
+                bail!("the SBET file must be sorted by time");
+            }
+            let mut reader =
+                las::Reader::from_path(&las).with_context(|| format!("failed to open {}", las))?;
+            let mut writer = options.open_writer(&outfile)?;
+            let computed = sbet::las::scan_geometry(
+                &mut reader,
+                trajectory.points(),
+                coordinates,
+                &mut writer,
+            )?;
+            writer.finish()?;
+            info!(
+                "computed {} points, {} outside of the trajectory",
+                computed.matched, computed.unmatched
+            );
+        }
+        Command::Lines {
+            infile,
+            outfile,
+            heading,
+            roll,
+            min_duration,
+            turns,
+        } => {
+            let trajectory = options.read_trajectory(&infile)?;
+            let limits = LineLimits {
+                heading: heading.to_radians(),
+                roll: roll.to_radians(),
+                min_duration,
+            };
+            let segments = if turns {
+                trajectory.segments(limits)
+            } else {
+                trajectory.flight_lines(limits)
+            };
+            info!(
+                "found {} flight lines",
+                segments
+                    .iter()
+                    .filter(|segment| segment.kind == SegmentKind::Line)
+                    .count()
+            );
+            let mut writer = options.open_writer(&outfile)?;
+            geojson::write_segments(trajectory.points(), &segments, &mut writer)?;
+            writer.finish()?;
+        }
+        Command::Overlaps { infiles } => {
+            let overlaps = sbet::overlaps(&infiles)?;
+            for overlap in &overlaps {
+                println!(
+                    "{} and {} overlap from {} to {} ({} s)",
+                    overlap.first.display(),
+                    overlap.second.display(),
+                    overlap.window.start,
+                    overlap.window.stop,
+                    overlap.window.duration()
+                );
+            }
+            if !overlaps.is_empty() {
+                bail!("found {} overlapping pair(s) of files", overlaps.len());
+            }
+            info!("no files overlap");
+        }
+        Command::Merge {
+            first,
+            second,
+            outfile,
+            weighting,
+        } => {
+            let first = options.read_trajectory(&Some(first))?;
+            let second = options.read_trajectory(&Some(second))?;
+            if !first.is_sorted() || !second.is_sorted() {
+                bail!("both files must be sorted by time");
+            }
+            let merged = first.merge(&second, weighting);
+            info!(
+                "merged {} and {} points into {}",
+                first.len(),
+                second.len(),
+                merged.len()
+            );
+            let mut writer = Writer::new(options.open_writer(&outfile)?);
+            writer.write_all(merged.points())?;
+            writer.finish()?.finish()?;
+        }
+        Command::Resample {
+            infile,
+            outfile,
+            rate,
+            max_gap,
+        } => {
+            if rate <= 0. {
+                bail!("rate must be positive");
+            }
+            let trajectory = options
+                .read_trajectory(&infile)?
+                .resample(rate, max_gap.unwrap_or(f64::INFINITY));
+            let mut writer = Writer::new(options.open_writer(&outfile)?);
+            writer.write_all(trajectory.points())?;
+            writer.finish()?.finish()?;
+        }
+        Command::Sample {
+            infile,
+            outfile,
+            every,
+            method,
+            csv,
+        } => {
+            if every <= 0. {
+                bail!("the sample interval must be positive");
+            }
+            let trajectory = options.read_trajectory(&infile)?;
+            if !trajectory.is_sorted() {
+                bail!("the SBET file must be sorted by time");
+            }
+            let sampled = trajectory.sample(every, method);
+            info!("sampled {} of {} points", sampled.len(), trajectory.len());
+            if csv {
+                let columns = Field::ALL.into_iter().map(csv::Column::new).collect();
+                let mut writer = csv::Writer::with_columns(options.open_writer(&outfile)?, columns);
+                writer.write_all(sampled.points())?;
+                writer.finish()?.finish()?;
+            } else {
+                let mut writer = Writer::new(options.open_writer(&outfile)?);
+                writer.write_all(sampled.points())?;
+                writer.finish()?.finish()?;
+            }
+        }
+        Command::Smooth {
+            infile,
+            outfile,
+            fields,
+            window,
+        } => {
+            if window % 2 == 0 {
+                bail!("window must be odd");
+            }
+            let fields = fields.unwrap_or_else(|| {
+                vec![
+                    Field::XAcceleration,
+                    Field::YAcceleration,
+                    Field::ZAcceleration,
+                    Field::XAngularRate,
+                    Field::YAngularRate,
+                    Field::ZAngularRate,
+                ]
+            });
+            let trajectory = options.read_trajectory(&infile)?.smooth(&fields, window);
+            let mut writer = Writer::new(options.open_writer(&outfile)?);
+            writer.write_all(trajectory.points())?;
+            writer.finish()?.finish()?;
+        }
+        Command::Despike {
+            infile,
+            outfile,
+            position,
+            attitude,
+            interpolate,
+        } => {
+            let trajectory = options.read_trajectory(&infile)?;
+            let limits = sbet::OutlierLimits {
+                position,
+                attitude: attitude.to_radians(),
+            };
+            let repair = if interpolate {
+                sbet::OutlierRepair::Interpolate
+            } else {
+                sbet::OutlierRepair::Drop
+            };
+            info!(
+                "found {} outliers",
+                trajectory.detect_outliers(limits).len()
+            );
+            let trajectory = trajectory.repair_outliers(limits, repair);
+            let mut writer = Writer::new(options.open_writer(&outfile)?);
+            writer.write_all(trajectory.points())?;
+            writer.finish()?.finish()?;
+        }
+        Command::Recover { infile, outfile } => {
+            let name = input_name(&infile);
+            let mut reader = options.open_reader(&infile)?;
+            reader.set_lenient(true);
+            let mut writer = Writer::new(options.open_writer(&outfile)?);
+            for result in reader.by_ref() {
+                writer.write_one(result.with_context(|| format!("failed to read {}", name))?)?;
+            }
+            writer.finish()?.finish()?;
+            for range in reader.skipped() {
+                warn!(
+                    "skipped bytes {}..{} ({} bytes)",
+                    range.start,
+                    range.end,
+                    range.end - range.start
+                );
+            }
+            info!("recovered {} records", reader.index());
+        }
+        Command::Sniff { infiles } => {
+            let mut all = true;
+            for infile in infiles {
+                let sniff =
+                    sbet::sniff(&infile).with_context(|| format!("failed to read {}", infile))?;
+                println!(
+                    "{}: confidence {:.2}, {}-endian, {} time{}",
+                    infile,
+                    sniff.confidence,
+                    sniff.endianness.unwrap_or_default(),
+                    sniff
+                        .time_convention
+                        .map(|convention| format!("{:?}", convention))
+                        .unwrap_or_else(|| "unknown".to_string()),
+                    if sniff.is_record_aligned {
+                        ""
+                    } else {
+                        ", not a whole number of records"
+                    }
+                );
+                all &= sniff.is_sbet();
+            }
+            if !all {
+                std::process::exit(1);
+            }
+        }
+        Command::Convert {
+            infile,
+            outfile,
+            from,
+            to,
+        } => {
+            let name = input_name(&infile);
+            let mut reader = options.open_reader(&infile)?;
+            if let Some(from) = from {
+                reader.set_endianness(from);
+            } else {
+                let from = reader
+                    .detect_endianness()
+                    .with_context(|| format!("failed to read {}", name))?;
+                info!("detected {}-endian input", from);
+            }
+            let mut writer = Writer::with_endianness(options.open_writer(&outfile)?, to);
+            for result in reader {
+                writer.write_one(result.with_context(|| format!("failed to read {}", name))?)?;
+            }
+            writer.finish()?.finish()?;
+        }
+        Command::Completions { shell } => {
+            let mut script = Vec::new();
+            clap_complete::generate(shell, &mut Args::command(), "sbet", &mut script);
+            std::io::stdout().write_all(&script)?;
+        }
+        Command::Man { out_dir } => {
+            std::fs::create_dir_all(&out_dir)
+                .with_context(|| format!("failed to create {}", out_dir))?;
+            clap_mangen::generate_to(Args::command(), &out_dir)
+                .with_context(|| format!("failed to write man pages to {}", out_dir))?;
+        }
+    }
+    Ok(())
+}
+
+/// Reads the sidecar index of an SBET file.
+fn open_index(path: &str) -> Result<sbet::Index> {
+    sbet::Index::open(path).with_context(|| {
+        format!(
+            "failed to read the index of {}, create it with `sbet index`",
+            path
+        )
+    })
+}
+
+impl Options {
+    /// Opens an input file, or stdin, and reads the points in a time window.
+    ///
+    /// With `use_index`, the file's index is used to seek to the start of the
+    /// window. With `use_index` or `sorted`, reading stops after the window;
+    /// otherwise the whole input is read, in case it isn't sorted.
+    fn open_window(
+        &self,
+        infile: &Option<String>,
+        window: TimeWindow,
+        use_index: bool,
+        sorted: bool,
+    ) -> Result<Box<dyn Iterator<Item = sbet::Result<sbet::Point>>>> {
+        if use_index {
+            let path = infile
+                .as_deref()
+                .filter(|path| *path != "-")
+                .ok_or_else(|| anyhow!("--use-index needs an input file path"))?;
+            let index = open_index(path)?;
+            let mut reader = Reader::new(BufReader::new(
+                File::open(path).with_context(|| format!("failed to open {}", path))?,
+            ));
+            reader.set_strict(self.strict);
+            reader.seek_to_time(&index, window.start)?;
+            Ok(Box::new(reader.filter_by_time(window)))
+        } else if sorted {
+            Ok(Box::new(self.open_reader(infile)?.filter_by_time(window)))
+        } else {
+            Ok(Box::new(self.open_reader(infile)?.filter(move |result| {
+                result
+                    .as_ref()
+                    .map_or(true, |point| window.contains(point.time))
+            })))
+        }
+    }
+
+    fn open_reader(&self, infile: &Option<String>) -> Result<Reader<Input>> {
+        let mut reader = Reader::new(self.open_stream(infile)?);
+        reader.set_strict(self.strict);
+        Ok(reader)
+    }
+
+    /// Opens an input file, or stdin if it's omitted or `-`.
+    fn open_stream(&self, infile: &Option<String>) -> Result<Input> {
+        match infile.as_deref().filter(|s| *s != "-") {
+            Some(infile) => self.open_input(infile),
+            None => {
+                let progress = self.progress(None, "stdin");
+                Input::new(progress.wrap_read(std::io::stdin()))
+                    .context("failed to read from stdin")
+            }
+        }
+    }
+
+    /// Opens a local file or an `http://`, `https://`, or `s3://` URL, with a progress bar.
+    fn open_input(&self, path: &str) -> Result<Input> {
+        let open = || -> Result<Input> {
+            if ["http://", "https://", "s3://"]
+                .iter()
+                .any(|scheme| path.starts_with(scheme))
+            {
+                let read = HttpReader::new(path)?;
+                let progress = self.progress(Some(read.len()), path);
+                Ok(Input::new(progress.wrap_read(read))?)
+            } else {
+                let file = File::open(path)?;
+                let progress = self.progress(Some(file.metadata()?.len()), path);
+                Ok(Input::new(progress.wrap_read(file))?)
+            }
+        };
+        open().with_context(|| format!("failed to open {}", path))
+    }
+
+    fn open_writer(&self, outfile: &Option<String>) -> Result<Output> {
+        match outfile.as_deref().filter(|s| *s != "-") {
+            Some(outfile) => Output::create(outfile, self.compress)
+                .with_context(|| format!("failed to create {}", outfile)),
+            None => {
+                Output::new(std::io::stdout(), self.compress).context("failed to write to stdout")
+            }
+        }
+    }
+
+    fn read_points(&self, path: &str) -> Result<Vec<sbet::Point>> {
+        let mut reader = Reader::new(self.open_input(path)?);
+        reader.set_strict(self.strict);
+        reader
+            .into_points()
+            .with_context(|| format!("failed to read {}", path))
+    }
+
+    /// Reads all points from a path, or from stdin if the path is `None` or `-`.
+    fn read_trajectory(&self, path: &Option<String>) -> Result<Trajectory> {
+        let points = self
+            .open_reader(path)?
+            .into_points()
+            .with_context(|| format!("failed to read {}", input_name(path)))?;
+        Ok(points.into())
+    }
+
+    /// Returns a progress bar over the bytes read from an input, or a spinner if its length is unknown.
+    ///
+    /// Progress is drawn to stderr, and is hidden if stderr isn't a terminal.
+    fn progress(&self, len: Option<u64>, name: &str) -> ProgressBar {
+        if self.quiet {
+            return ProgressBar::hidden();
+        }
+        let (progress, template) = match len {
+            Some(len) => (
+                ProgressBar::new(len),
+                "{prefix} [{bar:40}] {bytes}/{total_bytes} ({eta})",
+            ),
+            None => (ProgressBar::new_spinner(), "{prefix} {spinner} {bytes}"),
+        };
+        progress
+            .with_style(
+                ProgressStyle::with_template(template)
+                    .expect("progress templates are valid")
+                    .progress_chars("=> "),
+            )
+            .with_prefix(name.to_string())
+            .with_finish(ProgressFinish::AndClear)
+    }
+}
+
+/// Returns the name of an input for error messages.
+fn input_name(infile: &Option<String>) -> String {
+    match infile.as_deref() {
+        Some(infile) if infile != "-" => infile.to_string(),
+        _ => "stdin".to_string(),
+    }
+}
+
+/// Returns [EXIT_IO] if the error was caused by the file system or network, and [EXIT_DATA] otherwise.
+///
+/// A file that ends partway through a record is malformed, not unreadable.
+fn exit_code(err: &anyhow::Error) -> i32 {
+    for cause in err.chain() {
+        let io = match cause.downcast_ref::<sbet::Error>() {
+            Some(sbet::Error::Io(err)) => Some(err),
+            Some(sbet::Error::Http(_)) | Some(sbet::Error::RangeRequest(_)) => return EXIT_IO,
+            _ => cause.downcast_ref::<std::io::Error>(),
+        };
+        if let Some(io) = io {
+            return match io.kind() {
+                ErrorKind::UnexpectedEof | ErrorKind::InvalidData => EXIT_DATA,
+                _ => EXIT_IO,
+            };
+        }
+    }
+    EXIT_DATA
+}
+
+/// Returns true if stdout was closed early, e.g. by piping into `head`.
+fn is_broken_pipe(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        let io = match cause.downcast_ref::<sbet::Error>() {
+            Some(sbet::Error::Io(err)) => Some(err),
+            _ => cause.downcast_ref::<std::io::Error>(),
+        };
+        io.is_some_and(|io| io.kind() == ErrorKind::BrokenPipe)
+    })
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Mapping {
+    delimiter: Option<char>,
+    columns: Option<HashMap<String, Column>>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Column {
+    #[serde(deserialize_with = "deserialize_field")]
+    field: Field,
+    #[serde(default)]
+    units: Units,
+    #[serde(default = "default_scale")]
+    scale: f64,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Units {
+    #[default]
+    Radians,
+    Degrees,
+}
+
+impl Mapping {
+    /// Returns the mapped columns, or `None` to match columns by header name.
+    fn columns(&self) -> Option<Vec<csv::Column>> {
+        self.columns.as_ref().map(|columns| {
+            columns
+                .iter()
+                .map(|(name, column)| csv::Column {
+                    name: name.clone(),
+                    quantity: column.field.into(),
+                    units: match column.units {
+                        Units::Radians => csv::Units::Native,
+                        Units::Degrees => csv::Units::Degrees,
+                    },
+                    scale: column.scale,
+                })
+                .collect()
+        })
+    }
+}
+
+fn deserialize_field<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Field, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    name.parse().map_err(serde::de::Error::custom)
+}
+
+fn default_scale() -> f64 {
+    1.
 }
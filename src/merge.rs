@@ -0,0 +1,200 @@
+use crate::{
+    smooth::{wrap, wraps},
+    Error, Field, Interpolator, Point, Result, Trajectory,
+};
+use std::str::FromStr;
+
+/// How the points of two trajectories are blended where their times overlap.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::Weighting;
+///
+/// assert_eq!("linear".parse::<Weighting>().unwrap(), Weighting::Linear);
+/// assert_eq!("0.25".parse::<Weighting>().unwrap(), Weighting::Fixed(0.25));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Weighting {
+    /// Average the two trajectories.
+    #[default]
+    Equal,
+
+    /// Ramp from the trajectory that starts first to the other across the
+    /// overlap, so there's no step at either end of it.
+    Linear,
+
+    /// Weight the first trajectory by this fraction, from zero to one, and the second by the rest.
+    Fixed(f64),
+}
+
+impl FromStr for Weighting {
+    type Err = Error;
+
+    /// Parses "equal", "linear", or a fixed weight for the first trajectory from zero to one.
+    fn from_str(s: &str) -> Result<Weighting> {
+        match s {
+            "equal" => Ok(Weighting::Equal),
+            "linear" => Ok(Weighting::Linear),
+            _ => s
+                .parse()
+                .ok()
+                .filter(|weight| (0.0..=1.).contains(weight))
+                .map(Weighting::Fixed)
+                .ok_or_else(|| Error::UnknownWeighting(s.to_string())),
+        }
+    }
+}
+
+impl Trajectory {
+    /// Merges two trajectories into one, blending the points where their times overlap.
+    ///
+    /// Outside of the overlap, points are taken from whichever trajectory
+    /// covers that time. Inside of it, this trajectory's points are kept,
+    /// blended with the other trajectory interpolated at the same times, and
+    /// the other trajectory's points are dropped, so no time appears twice.
+    /// Longitude and attitude angles are blended across their wrap-around.
+    /// Both trajectories must be sorted by time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Point, Trajectory, Weighting};
+    ///
+    /// let forward: Trajectory = [0., 1., 2.]
+    ///     .into_iter()
+    ///     .map(|time| Point { time, altitude: 10., ..Default::default() })
+    ///     .collect::<Vec<_>>()
+    ///     .into();
+    /// let backward: Trajectory = [1., 2., 3.]
+    ///     .into_iter()
+    ///     .map(|time| Point { time, altitude: 20., ..Default::default() })
+    ///     .collect::<Vec<_>>()
+    ///     .into();
+    /// let merged = forward.merge(&backward, Weighting::Equal);
+    /// let altitudes: Vec<f64> = merged.points().iter().map(|point| point.altitude).collect();
+    /// assert_eq!(altitudes, [10., 15., 15., 20.]);
+    /// ```
+    pub fn merge(&self, other: &Trajectory, weighting: Weighting) -> Trajectory {
+        let (Some(first), Some(last), Some(other_first), Some(other_last)) = (
+            self.points().first(),
+            self.points().last(),
+            other.points().first(),
+            other.points().last(),
+        ) else {
+            return Trajectory::sorted([self.points(), other.points()].concat());
+        };
+        let start = first.time.max(other_first.time);
+        let end = last.time.min(other_last.time);
+        let Ok(interpolator) = Interpolator::new(other.points()) else {
+            return Trajectory::sorted([self.points(), other.points()].concat());
+        };
+        let overlaps = |point: &Point| point.time >= start && point.time <= end;
+        let mut points: Vec<Point> = other
+            .points()
+            .iter()
+            .filter(|point| !overlaps(point))
+            .copied()
+            .collect();
+        for point in self.points() {
+            if !overlaps(point) {
+                points.push(*point);
+                continue;
+            }
+            let Ok(other) = interpolator.interpolate(point.time) else {
+                points.push(*point);
+                continue;
+            };
+            let weight = match weighting {
+                Weighting::Equal => 0.5,
+                Weighting::Fixed(weight) => weight,
+                Weighting::Linear if end > start => {
+                    let fraction = (point.time - start) / (end - start);
+                    if first.time <= other_first.time {
+                        1. - fraction
+                    } else {
+                        fraction
+                    }
+                }
+                Weighting::Linear => 0.5,
+            };
+            points.push(blend(point, &other, weight));
+        }
+        Trajectory::sorted(points)
+    }
+}
+
+/// Returns a point with `weight` of `a` and the rest of `b`, at `a`'s time.
+fn blend(a: &Point, b: &Point, weight: f64) -> Point {
+    let mut point = *a;
+    for field in Field::ALL.into_iter().skip(1) {
+        let value = a.get(field);
+        let offset = b.get(field) - value;
+        let blended = if wraps(field) {
+            wrap(value + (1. - weight) * wrap(offset))
+        } else {
+            value + (1. - weight) * offset
+        };
+        point.set(field, blended);
+    }
+    point
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Point, Trajectory, Weighting};
+    use std::f64::consts::PI;
+
+    fn trajectory(times: &[f64], yaw: f64) -> Trajectory {
+        times
+            .iter()
+            .map(|&time| Point {
+                time,
+                yaw,
+                ..Default::default()
+            })
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    #[test]
+    fn linear_ramps_from_first() {
+        let forward = trajectory(&[0., 1., 2., 3.], 0.);
+        let backward = trajectory(&[1., 2., 3., 4.], 0.3);
+        for merged in [
+            forward.merge(&backward, Weighting::Linear),
+            backward.merge(&forward, Weighting::Linear),
+        ] {
+            let yaws: Vec<f64> = merged.points().iter().map(|point| point.yaw).collect();
+            assert_eq!(merged.len(), 5);
+            assert!((yaws[1] - 0.).abs() < 1e-12);
+            assert!((yaws[2] - 0.15).abs() < 1e-12);
+            assert!((yaws[3] - 0.3).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn blends_across_wrap() {
+        let a = trajectory(&[0., 1.], PI - 0.1);
+        let b = trajectory(&[0., 1.], -PI + 0.1);
+        let merged = a.merge(&b, Weighting::Equal);
+        assert_eq!(merged.len(), 2);
+        assert!((merged.points()[0].yaw.abs() - PI).abs() < 1e-12);
+    }
+
+    #[test]
+    fn no_overlap() {
+        let a = trajectory(&[2., 3.], 0.);
+        let b = trajectory(&[0., 1.], 0.);
+        let merged = a.merge(&b, Weighting::Equal);
+        let times: Vec<f64> = merged.points().iter().map(|point| point.time).collect();
+        assert_eq!(times, [0., 1., 2., 3.]);
+    }
+
+    #[test]
+    fn parse_weighting() {
+        assert_eq!("equal".parse::<Weighting>().unwrap(), Weighting::Equal);
+        assert!("1.5".parse::<Weighting>().is_err());
+        assert!("forward".parse::<Weighting>().is_err());
+    }
+}
@@ -0,0 +1,73 @@
+//! [nalgebra] conversions for kinematic quantities, behind the `nalgebra` feature.
+
+use crate::Point;
+use nalgebra::{UnitQuaternion, Vector3};
+
+impl Point {
+    /// Returns this point's earth-centered, earth-fixed position, in meters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Point;
+    ///
+    /// let position = Point::default().position_ecef();
+    /// assert_eq!(position.x, 6378137.);
+    /// ```
+    pub fn position_ecef(&self) -> Vector3<f64> {
+        let ecef = self.to_ecef();
+        Vector3::new(ecef.x, ecef.y, ecef.z)
+    }
+
+    /// Returns this point's velocity as north, east, and down components, in meters per second.
+    ///
+    /// See [Point::velocity_ned] for how the wander angle is removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Point;
+    ///
+    /// let point = Point { x_velocity: 1., ..Default::default() };
+    /// assert_eq!(point.velocity().x, 1.);
+    /// ```
+    pub fn velocity(&self) -> Vector3<f64> {
+        Vector3::from(self.velocity_ned())
+    }
+
+    /// Returns the rotation from the body frame (x forward, y right, z down) to north-east-down.
+    ///
+    /// Composed from roll, pitch, and true heading (yaw minus wander angle).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nalgebra::Vector3;
+    /// use sbet::Point;
+    ///
+    /// let point = Point { yaw: std::f64::consts::FRAC_PI_2, ..Default::default() };
+    /// let forward = point.attitude() * Vector3::x();
+    /// assert!((forward - Vector3::y()).norm() < 1e-12);
+    /// ```
+    pub fn attitude(&self) -> UnitQuaternion<f64> {
+        UnitQuaternion::from_euler_angles(self.roll, self.pitch, self.yaw - self.wander_angle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Point;
+    use nalgebra::Vector3;
+
+    #[test]
+    fn attitude_removes_wander() {
+        let point = Point {
+            yaw: 0.3,
+            wander_angle: 0.3,
+            pitch: 0.1,
+            ..Default::default()
+        };
+        let forward = point.attitude() * Vector3::x();
+        assert!((forward - Vector3::new(0.1f64.cos(), 0., -(0.1f64.sin()))).norm() < 1e-12);
+    }
+}
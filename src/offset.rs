@@ -0,0 +1,172 @@
+use crate::{Interpolator, Point, Quantity};
+
+/// A constant time offset between two trajectories, estimated by [time_offset].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TimeOffset {
+    /// The offset, in seconds, to subtract from the second trajectory's times to align it with the first.
+    pub offset: f64,
+
+    /// The correlation of the two signals at the offset, from -1 to 1.
+    ///
+    /// Values near one mean a confident match.
+    pub correlation: f64,
+}
+
+/// Estimates the constant time offset between two trajectories by cross-correlating a signal.
+///
+/// Both trajectories are interpolated every `resolution` seconds, and the
+/// signal's correlation is computed at every whole multiple of the
+/// resolution up to `max_offset` seconds in either direction. The best
+/// offset is then refined between samples with a parabola. Signals that
+/// change a lot, like [Derived::Speed](crate::Derived::Speed) or
+/// [Field::Altitude](crate::Field::Altitude), work best. Both trajectories
+/// must be sorted by time.
+///
+/// Returns `None` if the trajectories don't overlap at any offset, or a signal is constant.
+///
+/// # Panics
+///
+/// Panics if `resolution` isn't positive.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::{Field, Point};
+///
+/// let first: Vec<Point> = (0..100)
+///     .map(|i| {
+///         let time = f64::from(i) / 10.;
+///         Point { time, altitude: time.sin(), ..Default::default() }
+///     })
+///     .collect();
+/// let second: Vec<Point> = first
+///     .iter()
+///     .map(|point| Point { time: point.time + 0.5, ..*point })
+///     .collect();
+/// let offset = sbet::time_offset(&first, &second, Field::Altitude.into(), 0.1, 2.).unwrap();
+/// assert!((offset.offset - 0.5).abs() < 0.01);
+/// ```
+pub fn time_offset(
+    first: &[Point],
+    second: &[Point],
+    quantity: Quantity,
+    resolution: f64,
+    max_offset: f64,
+) -> Option<TimeOffset> {
+    assert!(resolution > 0., "resolution must be positive");
+    let (first_start, first_values) = sample(first, quantity, resolution)?;
+    let (second_start, second_values) = sample(second, quantity, resolution)?;
+    let max_shift = (max_offset / resolution).floor() as i64;
+    let correlations: Vec<(i64, f64)> = (-max_shift..=max_shift)
+        .filter_map(|shift| {
+            let pairs = (0..first_values.len()).filter_map(|i| {
+                let j = i as i64 + first_start + shift - second_start;
+                usize::try_from(j)
+                    .ok()
+                    .and_then(|j| second_values.get(j))
+                    .map(|&b| (first_values[i], b))
+            });
+            correlation(pairs).map(|correlation| (shift, correlation))
+        })
+        .collect();
+    let best =
+        (0..correlations.len()).max_by(|&a, &b| correlations[a].1.total_cmp(&correlations[b].1))?;
+    let (shift, correlation) = correlations[best];
+    let mut offset = shift as f64;
+    if let (Some(before), Some(after)) = (
+        best.checked_sub(1).and_then(|i| correlations.get(i)),
+        correlations.get(best + 1),
+    ) {
+        let curvature = before.1 - 2. * correlation + after.1;
+        if curvature < 0. {
+            offset += 0.5 * (before.1 - after.1) / curvature;
+        }
+    }
+    Some(TimeOffset {
+        offset: offset * resolution,
+        correlation,
+    })
+}
+
+/// Interpolates a signal at every whole multiple of `resolution`, returning the index of the first sample and the values.
+fn sample(points: &[Point], quantity: Quantity, resolution: f64) -> Option<(i64, Vec<f64>)> {
+    let interpolator = Interpolator::new(points).ok()?;
+    let start = (points[0].time / resolution).ceil() as i64;
+    let mut values = Vec::new();
+    while let Ok(point) =
+        interpolator.interpolate((start + values.len() as i64) as f64 * resolution)
+    {
+        values.push(point.quantity(quantity));
+    }
+    Some((start, values))
+}
+
+/// Returns the Pearson correlation of pairs of values, or `None` if there are fewer than three or either is constant.
+fn correlation<I: Iterator<Item = (f64, f64)>>(pairs: I) -> Option<f64> {
+    let pairs: Vec<(f64, f64)> = pairs.collect();
+    if pairs.len() < 3 {
+        return None;
+    }
+    let n = pairs.len() as f64;
+    let mean_a = pairs.iter().map(|(a, _)| a).sum::<f64>() / n;
+    let mean_b = pairs.iter().map(|(_, b)| b).sum::<f64>() / n;
+    let (mut covariance, mut variance_a, mut variance_b) = (0., 0., 0.);
+    for (a, b) in pairs {
+        covariance += (a - mean_a) * (b - mean_b);
+        variance_a += (a - mean_a) * (a - mean_a);
+        variance_b += (b - mean_b) * (b - mean_b);
+    }
+    if variance_a > 0. && variance_b > 0. {
+        Some(covariance / (variance_a * variance_b).sqrt())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Derived, Point};
+
+    fn trajectory(shift: f64) -> Vec<Point> {
+        (0..2000)
+            .map(|i| {
+                let time = 396000. + f64::from(i) * 0.005;
+                let speed = 2. + (0.7 * (time - shift)).sin() + 0.3 * (2.3 * (time - shift)).cos();
+                Point {
+                    time,
+                    x_velocity: speed,
+                    ..Default::default()
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sub_resolution() {
+        let offset = super::time_offset(
+            &trajectory(0.),
+            &trajectory(0.237),
+            Derived::Speed.into(),
+            0.01,
+            1.,
+        )
+        .unwrap();
+        assert!((offset.offset - 0.237).abs() < 0.002, "{}", offset.offset);
+        assert!(offset.correlation > 0.99);
+    }
+
+    #[test]
+    fn constant() {
+        let points = [
+            Point {
+                time: 0.,
+                ..Default::default()
+            },
+            Point {
+                time: 1.,
+                ..Default::default()
+            },
+        ];
+        assert!(super::time_offset(&points, &points, Derived::Speed.into(), 0.1, 1.).is_none());
+    }
+}
@@ -0,0 +1,191 @@
+use crate::{interpolator::lerp, Point, Trajectory};
+use std::f64::consts::{PI, TAU};
+
+/// How far a point may stray from where its neighbor's motion predicts.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::OutlierLimits;
+///
+/// let limits = OutlierLimits { position: 0.5, ..Default::default() };
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OutlierLimits {
+    /// The largest allowed difference between a point's position and the
+    /// position predicted from the previous point's velocity, in meters.
+    pub position: f64,
+
+    /// The largest allowed change in roll, pitch, or heading beyond what the
+    /// angular rates allow, in radians.
+    pub attitude: f64,
+}
+
+impl Default for OutlierLimits {
+    /// One meter and one degree.
+    fn default() -> OutlierLimits {
+        OutlierLimits {
+            position: 1.,
+            attitude: 1f64.to_radians(),
+        }
+    }
+}
+
+/// What to do with outliers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutlierRepair {
+    /// Remove outliers.
+    #[default]
+    Drop,
+
+    /// Replace outliers with points interpolated from the good points on
+    /// either side. Outliers at the start or end are removed.
+    Interpolate,
+}
+
+impl Trajectory {
+    /// Returns the indices of points whose position or attitude jumps farther than is kinematically plausible.
+    ///
+    /// Each point is checked against the last good point before it. The
+    /// expected displacement is the mean of the two points' velocities times
+    /// the time between them, and the expected attitude change is bounded by
+    /// the larger angular rate magnitude times the time between them. The
+    /// first point is assumed to be good.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{OutlierLimits, Point, Trajectory};
+    ///
+    /// let mut points: Vec<Point> = (0..5)
+    ///     .map(|i| Point { time: f64::from(i), ..Default::default() })
+    ///     .collect();
+    /// points[2].altitude = 100.;
+    /// let trajectory = Trajectory::from(points);
+    /// assert_eq!(trajectory.detect_outliers(OutlierLimits::default()), vec![2]);
+    /// ```
+    pub fn detect_outliers(&self, limits: OutlierLimits) -> Vec<usize> {
+        let points = self.points();
+        let mut outliers = Vec::new();
+        let Some(mut good) = points.first() else {
+            return outliers;
+        };
+        for (i, point) in points.iter().enumerate().skip(1) {
+            if is_plausible(good, point, limits) {
+                good = point;
+            } else {
+                outliers.push(i);
+            }
+        }
+        outliers
+    }
+
+    /// Returns a new trajectory with its outliers dropped or re-interpolated.
+    ///
+    /// See [Trajectory::detect_outliers] for how outliers are detected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{OutlierLimits, OutlierRepair, Point, Trajectory};
+    ///
+    /// let mut points: Vec<Point> = (0..5)
+    ///     .map(|i| Point { time: f64::from(i), ..Default::default() })
+    ///     .collect();
+    /// points[2].altitude = 100.;
+    /// let trajectory = Trajectory::from(points);
+    /// let repaired = trajectory.repair_outliers(OutlierLimits::default(), OutlierRepair::Interpolate);
+    /// assert_eq!(repaired.len(), 5);
+    /// assert_eq!(repaired.points()[2].altitude, 0.);
+    /// ```
+    pub fn repair_outliers(&self, limits: OutlierLimits, repair: OutlierRepair) -> Trajectory {
+        let outliers = self.detect_outliers(limits);
+        let points = self.points();
+        let mut is_outlier = vec![false; points.len()];
+        for &i in &outliers {
+            is_outlier[i] = true;
+        }
+        let mut repaired = Vec::with_capacity(points.len());
+        for (i, point) in points.iter().enumerate() {
+            if !is_outlier[i] {
+                repaired.push(*point);
+            } else if repair == OutlierRepair::Interpolate {
+                let before = points[..i].iter().zip(&is_outlier).rev().find(|(_, &o)| !o);
+                let after = points[i + 1..]
+                    .iter()
+                    .zip(&is_outlier[i + 1..])
+                    .find(|(_, &o)| !o);
+                if let (Some((before, _)), Some((after, _))) = (before, after) {
+                    repaired.push(lerp(before, after, point.time));
+                }
+            }
+        }
+        repaired.into()
+    }
+}
+
+fn is_plausible(good: &Point, point: &Point, limits: OutlierLimits) -> bool {
+    let elapsed = point.time - good.time;
+    let ned = point.to_ned(good);
+    let [north_velocity, east_velocity, down_velocity] = good.velocity_ned();
+    let velocity = point.velocity_ned();
+    let residual = [
+        ned.north - (north_velocity + velocity[0]) / 2. * elapsed,
+        ned.east - (east_velocity + velocity[1]) / 2. * elapsed,
+        ned.down - (down_velocity + velocity[2]) / 2. * elapsed,
+    ];
+    if residual.iter().map(|r| r * r).sum::<f64>().sqrt() > limits.position {
+        return false;
+    }
+    let rate = good
+        .angular_rate_magnitude()
+        .max(point.angular_rate_magnitude());
+    let allowed = rate * elapsed.abs() + limits.attitude;
+    [
+        point.roll - good.roll,
+        point.pitch - good.pitch,
+        point.true_heading() - good.true_heading(),
+    ]
+    .into_iter()
+    .all(|change| ((change + PI).rem_euclid(TAU) - PI).abs() <= allowed)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{OutlierLimits, OutlierRepair, Point, Trajectory};
+
+    fn moving(n: i32) -> Vec<Point> {
+        // Flying north at 100 m/s, turning at 0.1 rad/s.
+        (0..n)
+            .map(|i| {
+                let time = f64::from(i) * 0.1;
+                Point {
+                    time,
+                    latitude: 100. * time / 6_335_439.,
+                    x_velocity: 100.,
+                    yaw: 0.1 * time,
+                    z_angular_rate: 0.1,
+                    ..Default::default()
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn smooth_motion_is_plausible() {
+        let trajectory = Trajectory::from(moving(20));
+        assert!(trajectory
+            .detect_outliers(OutlierLimits::default())
+            .is_empty());
+    }
+
+    #[test]
+    fn attitude_spike() {
+        let mut points = moving(20);
+        points[10].roll = 0.2;
+        let trajectory = Trajectory::from(points);
+        assert_eq!(trajectory.detect_outliers(OutlierLimits::default()), [10]);
+        let repaired = trajectory.repair_outliers(OutlierLimits::default(), OutlierRepair::Drop);
+        assert_eq!(repaired.len(), 19);
+    }
+}
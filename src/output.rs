@@ -0,0 +1,228 @@
+use crate::{Error, Result};
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+    str::FromStr,
+};
+
+/// A compression format for [Output].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Compression {
+    /// No compression.
+    #[default]
+    None,
+
+    /// Gzip, behind the `gzip` feature.
+    #[cfg(feature = "gzip")]
+    Gzip,
+
+    /// Zstandard, behind the `zstd` feature.
+    #[cfg(feature = "zstd")]
+    Zstd,
+}
+
+impl FromStr for Compression {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Compression> {
+        match s {
+            "none" => Ok(Compression::None),
+            #[cfg(feature = "gzip")]
+            "gzip" => Ok(Compression::Gzip),
+            #[cfg(feature = "zstd")]
+            "zstd" => Ok(Compression::Zstd),
+            _ => Err(Error::UnknownCompression(s.to_string())),
+        }
+    }
+}
+
+impl std::fmt::Display for Compression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Compression::None => f.write_str("none"),
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => f.write_str("gzip"),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => f.write_str("zstd"),
+        }
+    }
+}
+
+/// An output stream that optionally compresses what's written to it.
+///
+/// The compressed stream is finished when the output is dropped, but any
+/// error is lost; call [Output::finish] to check for one.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::{Compression, Output, Point, Writer};
+///
+/// let output = Output::new(Vec::new(), Compression::None).unwrap();
+/// let mut writer = Writer::new(output);
+/// writer.write_one(Point::default()).unwrap();
+/// writer.finish().unwrap().finish().unwrap();
+/// ```
+pub struct Output {
+    inner: Option<Inner>,
+}
+
+enum Inner {
+    Plain(Box<dyn Write + Send>),
+    #[cfg(feature = "gzip")]
+    Gzip(flate2::write::GzEncoder<Box<dyn Write + Send>>),
+    #[cfg(feature = "zstd")]
+    Zstd(zstd::Encoder<'static, Box<dyn Write + Send>>),
+}
+
+impl Output {
+    /// Creates a file, compressing what's written to it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "zstd")] {
+    /// use sbet::{Compression, Output};
+    ///
+    /// let output = Output::create("outfile.sbet.zst", Compression::Zstd).unwrap();
+    /// # }
+    /// ```
+    pub fn create<P: AsRef<Path>>(path: P, compression: Compression) -> std::io::Result<Output> {
+        Output::new(BufWriter::new(File::create(path)?), compression)
+    }
+
+    /// Wraps a stream, compressing what's written to it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Compression, Output};
+    ///
+    /// let output = Output::new(std::io::stdout(), Compression::None).unwrap();
+    /// ```
+    pub fn new<W: Write + Send + 'static>(
+        write: W,
+        compression: Compression,
+    ) -> std::io::Result<Output> {
+        let write: Box<dyn Write + Send> = Box::new(write);
+        let inner = match compression {
+            Compression::None => Inner::Plain(write),
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => {
+                Inner::Gzip(flate2::write::GzEncoder::new(write, Default::default()))
+            }
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => Inner::Zstd(zstd::Encoder::new(write, 0)?),
+        };
+        Ok(Output { inner: Some(inner) })
+    }
+
+    /// Finishes the compressed stream and flushes the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Compression, Output};
+    ///
+    /// let output = Output::new(Vec::new(), Compression::None).unwrap();
+    /// output.finish().unwrap();
+    /// ```
+    pub fn finish(mut self) -> std::io::Result<()> {
+        self.finish_inner()
+    }
+
+    fn finish_inner(&mut self) -> std::io::Result<()> {
+        match self.inner.take() {
+            None => Ok(()),
+            Some(Inner::Plain(mut write)) => write.flush(),
+            #[cfg(feature = "gzip")]
+            Some(Inner::Gzip(encoder)) => encoder.finish()?.flush(),
+            #[cfg(feature = "zstd")]
+            Some(Inner::Zstd(encoder)) => encoder.finish()?.flush(),
+        }
+    }
+
+    fn write_mut(&mut self) -> &mut dyn Write {
+        match self.inner.as_mut().expect("output is not finished") {
+            Inner::Plain(write) => write,
+            #[cfg(feature = "gzip")]
+            Inner::Gzip(write) => write,
+            #[cfg(feature = "zstd")]
+            Inner::Zstd(write) => write,
+        }
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.write_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.write_mut().flush()
+    }
+}
+
+impl Drop for Output {
+    fn drop(&mut self) {
+        let _ = self.finish_inner();
+    }
+}
+
+impl std::fmt::Debug for Output {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let compression = match self.inner {
+            None => "finished",
+            Some(Inner::Plain(_)) => "none",
+            #[cfg(feature = "gzip")]
+            Some(Inner::Gzip(_)) => "gzip",
+            #[cfg(feature = "zstd")]
+            Some(Inner::Zstd(_)) => "zstd",
+        };
+        f.debug_struct("Output")
+            .field("compression", &compression)
+            .finish()
+    }
+}
+
+#[cfg(all(test, feature = "gzip", feature = "zstd"))]
+mod tests {
+    use super::{Compression, Output};
+    use crate::{Input, Reader, Writer};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let points = Reader::from_path("data/2-points.sbet")
+            .unwrap()
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap();
+        for compression in [Compression::Gzip, Compression::Zstd] {
+            let shared = Shared::default();
+            let mut writer = Writer::new(Output::new(shared.clone(), compression).unwrap());
+            writer.write_all(&points).unwrap();
+            writer.finish().unwrap().finish().unwrap();
+            let bytes = shared.0.lock().unwrap().clone();
+            let input = Input::new(std::io::Cursor::new(bytes)).unwrap();
+            assert!(input.is_compressed());
+            let read = Reader::new(input)
+                .collect::<crate::Result<Vec<_>>>()
+                .unwrap();
+            assert_eq!(read, points);
+        }
+    }
+}
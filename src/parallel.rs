@@ -0,0 +1,154 @@
+//! Parallel processing of SBET records, behind the `rayon` feature.
+
+use crate::{Point, Reader, Result};
+use rayon::prelude::*;
+use std::io::Read;
+
+/// Processes a reader's points in parallel chunks, handing the results to `sink` in order.
+///
+/// Record bytes are read in chunks of `chunk_size` on the calling thread,
+/// while the previous batch of chunks is decoded and passed to `process` on
+/// rayon's thread pool. Each chunk's result is then passed to `sink` in the
+/// same order as the chunks were read, so output order is preserved.
+///
+/// # Panics
+///
+/// Panics if `chunk_size` is zero.
+///
+/// # Examples
+///
+/// Count the points with positive altitude:
+///
+/// ```
+/// use sbet::Reader;
+///
+/// let mut reader = Reader::from_path("data/2-points.sbet").unwrap();
+/// let mut count = 0;
+/// sbet::parallel::par_process(
+///     &mut reader,
+///     1024,
+///     |points| points.iter().filter(|point| point.altitude > 0.).count(),
+///     |n| {
+///         count += n;
+///         Ok(())
+///     },
+/// )
+/// .unwrap();
+/// assert_eq!(count, 2);
+/// ```
+pub fn par_process<R, T, F, S>(
+    reader: &mut Reader<R>,
+    chunk_size: usize,
+    process: F,
+    mut sink: S,
+) -> Result<()>
+where
+    R: Read + Send,
+    T: Send,
+    F: Fn(Vec<Point>) -> T + Sync,
+    S: FnMut(T) -> Result<()>,
+{
+    assert!(chunk_size > 0, "chunk size must be positive");
+    let decoder = reader.decoder();
+    let batch_size = rayon::current_num_threads() * 2;
+    let process_batch = |batch: Vec<Vec<u8>>| -> Vec<T> {
+        batch
+            .into_par_iter()
+            .map(|bytes| {
+                process(
+                    bytes
+                        .chunks_exact(decoder.size())
+                        .map(|record| decoder.decode(record))
+                        .collect(),
+                )
+            })
+            .collect()
+    };
+    let mut batch = read_batch(reader, chunk_size, batch_size)?;
+    while !batch.is_empty() {
+        let (results, next) = rayon::join(
+            || process_batch(batch),
+            || read_batch(reader, chunk_size, batch_size),
+        );
+        for result in results {
+            sink(result)?;
+        }
+        batch = next?;
+    }
+    Ok(())
+}
+
+/// Reads a reader's points in parallel chunks and keeps those matching a predicate, in order.
+///
+/// See [par_process] for how the work is divided.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::Reader;
+///
+/// let mut reader = Reader::from_path("data/2-points.sbet").unwrap();
+/// let points = sbet::parallel::par_filter(&mut reader, 1024, |point| point.time > 151631.005)
+///     .unwrap();
+/// assert_eq!(points.len(), 1);
+/// ```
+pub fn par_filter<R, F>(
+    reader: &mut Reader<R>,
+    chunk_size: usize,
+    predicate: F,
+) -> Result<Vec<Point>>
+where
+    R: Read + Send,
+    F: Fn(&Point) -> bool + Sync,
+{
+    let mut points = Vec::new();
+    par_process(
+        reader,
+        chunk_size,
+        |mut chunk| {
+            chunk.retain(&predicate);
+            chunk
+        },
+        |chunk| {
+            points.extend(chunk);
+            Ok(())
+        },
+    )?;
+    Ok(points)
+}
+
+fn read_batch<R: Read>(
+    reader: &mut Reader<R>,
+    chunk_size: usize,
+    batch_size: usize,
+) -> Result<Vec<Vec<u8>>> {
+    let mut batch = Vec::with_capacity(batch_size);
+    for _ in 0..batch_size {
+        let mut bytes = Vec::new();
+        if reader.read_raw(chunk_size, &mut bytes)? == 0 {
+            break;
+        }
+        batch.push(bytes);
+    }
+    Ok(batch)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Point, Reader};
+
+    #[test]
+    fn preserves_order() {
+        let points: Vec<_> = (0..1000)
+            .map(|i| Point {
+                time: f64::from(i),
+                ..Default::default()
+            })
+            .collect();
+        let bytes = crate::to_vec(&points);
+        let mut reader = Reader::new(&bytes[..]);
+        let even = super::par_filter(&mut reader, 7, |point| point.time % 2. == 0.).unwrap();
+        assert_eq!(even.len(), 500);
+        assert!(even.windows(2).all(|pair| pair[0].time < pair[1].time));
+    }
+}
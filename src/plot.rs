@@ -0,0 +1,249 @@
+//! Plots of trajectories for quick visual checks, behind the `plot` feature.
+//!
+//! Plots are drawn with [plotters], to a PNG or SVG file or to an SVG
+//! string. Angular quantities are plotted in degrees.
+//!
+//! # Examples
+//!
+//! ```
+//! use sbet::{plot::Plot, Field, Reader};
+//!
+//! let points = Reader::from_path("data/2-points.sbet").unwrap().into_points().unwrap();
+//! let svg = Plot::Quantity(Field::Altitude.into()).to_svg(&points).unwrap();
+//! assert!(svg.starts_with("<svg"));
+//! ```
+
+use crate::{Error, Point, Quantity, Result};
+use plotters::{coord::Shift, prelude::*};
+use std::{ops::Range, path::Path};
+
+/// The width and height of a plot, in pixels.
+pub const SIZE: (u32, u32) = (800, 600);
+
+/// The blocks used by [sparkline], from lowest to highest.
+const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// What to plot.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Plot {
+    /// A field or derived value against time.
+    Quantity(Quantity),
+
+    /// Latitude against longitude, i.e. the path over the ground.
+    GroundTrack,
+}
+
+impl Plot {
+    /// Draws this plot of the points to a file.
+    ///
+    /// Files with an `.svg` extension are drawn as SVG, and others as
+    /// bitmaps in the format named by their extension, e.g. `.png`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [Error::NoPoints] if there are no points to plot.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use sbet::{plot::Plot, Reader};
+    ///
+    /// let points = Reader::from_path("trajectory.sbet").unwrap().into_points().unwrap();
+    /// Plot::GroundTrack.write_to_path(&points, "ground-track.png").unwrap();
+    /// ```
+    pub fn write_to_path<P: AsRef<Path>>(&self, points: &[Point], path: P) -> Result<()> {
+        let path = path.as_ref();
+        if path.extension().is_some_and(|extension| extension == "svg") {
+            self.draw(points, SVGBackend::new(path, SIZE).into_drawing_area())
+        } else {
+            self.draw(points, BitMapBackend::new(path, SIZE).into_drawing_area())
+        }
+    }
+
+    /// Draws this plot of the points as an SVG document.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{plot::Plot, Reader};
+    ///
+    /// let points = Reader::from_path("data/2-points.sbet").unwrap().into_points().unwrap();
+    /// let svg = Plot::GroundTrack.to_svg(&points).unwrap();
+    /// ```
+    pub fn to_svg(&self, points: &[Point]) -> Result<String> {
+        let mut svg = String::new();
+        self.draw(
+            points,
+            SVGBackend::with_string(&mut svg, SIZE).into_drawing_area(),
+        )?;
+        Ok(svg)
+    }
+
+    /// Returns the x and y values of this plot of the points, as they're plotted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{plot::Plot, Field, Point};
+    ///
+    /// let point = Point { time: 1., roll: 1f64.to_radians(), ..Default::default() };
+    /// let data = Plot::Quantity(Field::Roll.into()).data(&[point]);
+    /// assert!((data[0].1 - 1.).abs() < 1e-12);
+    /// ```
+    pub fn data(&self, points: &[Point]) -> Vec<(f64, f64)> {
+        match *self {
+            Plot::Quantity(quantity) => points
+                .iter()
+                .map(|point| {
+                    let value = point.quantity(quantity);
+                    if quantity.is_angular() {
+                        (point.time, value.to_degrees())
+                    } else {
+                        (point.time, value)
+                    }
+                })
+                .collect(),
+            Plot::GroundTrack => points
+                .iter()
+                .map(|point| (point.longitude.to_degrees(), point.latitude.to_degrees()))
+                .collect(),
+        }
+    }
+
+    fn draw<DB: DrawingBackend>(
+        &self,
+        points: &[Point],
+        root: DrawingArea<DB, Shift>,
+    ) -> Result<()> {
+        let (caption, x_desc, y_desc) = match *self {
+            Plot::Quantity(quantity) => {
+                (quantity.name(), "time (s)".to_string(), describe(quantity))
+            }
+            Plot::GroundTrack => (
+                "ground track",
+                "longitude (°)".to_string(),
+                "latitude (°)".to_string(),
+            ),
+        };
+        let data = self.data(points);
+        let x_range = range(data.iter().map(|&(x, _)| x)).ok_or(Error::NoPoints)?;
+        let y_range = range(data.iter().map(|&(_, y)| y)).ok_or(Error::NoPoints)?;
+        root.fill(&WHITE).map_err(plot_error)?;
+        let mut chart = ChartBuilder::on(&root)
+            .caption(caption, ("sans-serif", 24))
+            .margin(16)
+            .x_label_area_size(40)
+            .y_label_area_size(80)
+            .build_cartesian_2d(x_range, y_range)
+            .map_err(plot_error)?;
+        chart
+            .configure_mesh()
+            .x_desc(x_desc)
+            .y_desc(y_desc)
+            .draw()
+            .map_err(plot_error)?;
+        let _ = chart
+            .draw_series(LineSeries::new(data, &BLUE))
+            .map_err(plot_error)?;
+        root.present().map_err(plot_error)
+    }
+}
+
+/// Returns a one-line chart of the values, `width` characters wide, using block characters.
+///
+/// Values are averaged into `width` columns, and each column's block is
+/// scaled between the smallest and largest column, so a constant series is
+/// all low blocks. Non-finite values are skipped.
+///
+/// # Examples
+///
+/// ```
+/// let values = [0., 1., 2., 3., 4., 5., 6., 7.];
+/// assert_eq!(sbet::plot::sparkline(&values, 8), "▁▂▃▄▅▆▇█");
+/// assert_eq!(sbet::plot::sparkline(&values, 2), "▁█");
+/// ```
+pub fn sparkline(values: &[f64], width: usize) -> String {
+    let values: Vec<f64> = values.iter().copied().filter(|v| v.is_finite()).collect();
+    if values.is_empty() || width == 0 {
+        return String::new();
+    }
+    let width = width.min(values.len());
+    let columns: Vec<f64> = (0..width)
+        .map(|column| {
+            let start = column * values.len() / width;
+            let end = (column + 1) * values.len() / width;
+            values[start..end].iter().sum::<f64>() / (end - start) as f64
+        })
+        .collect();
+    let min = columns.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = columns.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    columns
+        .into_iter()
+        .map(|value| {
+            let scaled = if max > min {
+                (value - min) / (max - min) * (BLOCKS.len() - 1) as f64
+            } else {
+                0.
+            };
+            BLOCKS[(scaled.round() as usize).min(BLOCKS.len() - 1)]
+        })
+        .collect()
+}
+
+/// Returns a label for a quantity's axis.
+fn describe(quantity: Quantity) -> String {
+    if quantity.is_angular() {
+        format!("{} (°)", quantity.name())
+    } else {
+        quantity.name().to_string()
+    }
+}
+
+/// Returns the range of the finite values, widened if they're all the same, or `None` if there are none.
+fn range(values: impl Iterator<Item = f64>) -> Option<Range<f64>> {
+    let (min, max) = values.filter(|value| value.is_finite()).fold(
+        None,
+        |range: Option<(f64, f64)>, value| match range {
+            Some((min, max)) => Some((min.min(value), max.max(value))),
+            None => Some((value, value)),
+        },
+    )?;
+    if min == max {
+        Some(min - 0.5..max + 0.5)
+    } else {
+        Some(min..max)
+    }
+}
+
+fn plot_error<E: std::error::Error + Send + Sync>(err: DrawingAreaErrorKind<E>) -> Error {
+    Error::Plot(err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{sparkline, Plot};
+    use crate::{Error, Field, Point};
+
+    #[test]
+    fn empty() {
+        assert!(matches!(
+            Plot::GroundTrack.to_svg(&[]).unwrap_err(),
+            Error::NoPoints
+        ));
+        assert_eq!(sparkline(&[], 10), "");
+        assert_eq!(sparkline(&[1., f64::NAN, 1.], 10), "▁▁");
+    }
+
+    #[test]
+    fn quantity() {
+        let points: Vec<Point> = (0..10)
+            .map(|i| Point {
+                time: f64::from(i),
+                roll: f64::from(i).to_radians(),
+                ..Default::default()
+            })
+            .collect();
+        let svg = Plot::Quantity(Field::Roll.into()).to_svg(&points).unwrap();
+        assert!(svg.contains("roll (°)"));
+    }
+}
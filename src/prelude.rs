@@ -0,0 +1,19 @@
+//! The most commonly used types, for glob importing.
+//!
+//! # Examples
+//!
+//! ```
+//! use sbet::prelude::*;
+//!
+//! let points = [Point::default(), Point { time: 1., ..Default::default() }];
+//! let interpolator = Interpolator::new(&points).unwrap();
+//! ```
+
+pub use crate::{Error, Field, Interpolator, Point, Result};
+#[cfg(feature = "std")]
+pub use crate::{Reader, Trajectory, Writer};
+
+#[cfg(feature = "proj")]
+pub use crate::Transformer;
+#[cfg(feature = "utm")]
+pub use crate::Utm;
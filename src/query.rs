@@ -0,0 +1,125 @@
+use crate::{Error, Index, Point, Reader, Result};
+use std::{
+    fs::File,
+    io::{ErrorKind, Read, Seek},
+    path::Path,
+};
+
+impl<R: Read + Seek> Reader<R> {
+    /// Interpolates a point at a time, reading only a few records.
+    ///
+    /// The two records around the time are found by binary search over the
+    /// stream, narrowed to one stride by the index if there is one. The
+    /// records must be sorted by time and start at the beginning of the
+    /// stream. The reader is left after the records that were read.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the time is outside of the records, or there are fewer than two.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Reader;
+    /// use std::fs::File;
+    ///
+    /// let mut reader = Reader::new(File::open("data/2-points.sbet").unwrap());
+    /// let point = reader.query_time(151631.004, None).unwrap();
+    /// assert_eq!(point.time, 151631.004);
+    /// ```
+    pub fn query_time(&mut self, time: f64, index: Option<&Index>) -> Result<Point> {
+        let count = self.estimate_number_of_points()?;
+        let (mut low, mut high) = match index {
+            Some(index) => {
+                index.check_record_size(self)?;
+                let low = index.lookup(time);
+                (low, (low + index.stride()).min(count.saturating_sub(1)))
+            }
+            None => (0, count.saturating_sub(1)),
+        };
+        // Find the last record at or before the time, or the first record if there is none.
+        while low < high {
+            let middle = low + (high - low).div_ceil(2);
+            if self.read_at(middle)?.time <= time {
+                low = middle;
+            } else {
+                high = middle - 1;
+            }
+        }
+        let first = low.min(count.saturating_sub(2));
+        self.seek(first)?;
+        let mut points = Vec::with_capacity(2);
+        while points.len() < 2 {
+            match self.read_one()? {
+                Some(point) => points.push(point),
+                None => break,
+            }
+        }
+        crate::interpolate(&points, time)
+    }
+
+    fn read_at(&mut self, index: u64) -> Result<Point> {
+        self.seek(index)?;
+        self.read_one()?
+            .ok_or_else(|| Error::Io(ErrorKind::UnexpectedEof.into()))
+    }
+}
+
+/// Interpolates a point at a time in an SBET file, reading only a few records.
+///
+/// Uses the file's `.sbx` [Index] if it has a current one. The file must be
+/// uncompressed, little-endian records sorted by time. See
+/// [Reader::query_time].
+///
+/// # Examples
+///
+/// ```
+/// let point = sbet::query_time("data/2-points.sbet", 151631.004).unwrap();
+/// ```
+pub fn query_time<P: AsRef<Path>>(path: P, time: f64) -> Result<Point> {
+    let path = path.as_ref();
+    let index = Index::open(path).ok();
+    Reader::new(File::open(path)?).query_time(time, index.as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, Index, Point, Reader};
+    use std::io::Cursor;
+
+    fn reader() -> Reader<Cursor<Vec<u8>>> {
+        let points: Vec<Point> = (0..1001)
+            .map(|i| Point {
+                time: f64::from(i),
+                altitude: 2. * f64::from(i),
+                ..Default::default()
+            })
+            .collect();
+        Reader::new(Cursor::new(crate::to_vec(&points)))
+    }
+
+    #[test]
+    fn binary_search() {
+        let mut reader = reader();
+        for time in [0., 0.5, 333.25, 999.5, 1000.] {
+            let point = reader.query_time(time, None).unwrap();
+            assert_eq!(point.altitude, 2. * time);
+        }
+        assert!(matches!(
+            reader.query_time(1000.5, None).unwrap_err(),
+            Error::Extrapolation { .. }
+        ));
+        assert!(reader.query_time(-1., None).is_err());
+    }
+
+    #[test]
+    fn with_index() {
+        let mut reader = reader();
+        let bytes = reader.get_ref().get_ref().clone();
+        let index = Index::build(Reader::new(&bytes[..]), 64).unwrap();
+        for time in [0., 63.5, 64., 700.1, 1000.] {
+            let point = reader.query_time(time, Some(&index)).unwrap();
+            assert!((point.altitude - 2. * time).abs() < 1e-9);
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use crate::{Error, Point, Result, RECORD_SIZE};
+use bytemuck::{Pod, Zeroable};
+
+/// A raw SBET record that can be viewed directly in a byte buffer, behind the `bytemuck` feature.
+///
+/// The layout matches the on-disk record, so on little-endian targets a
+/// buffer of records can be reinterpreted as `&[RawPoint]` without copying
+/// or allocating.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::{Point, RawPoint};
+///
+/// let raw = RawPoint::from(Point { time: 1., ..Default::default() });
+/// assert_eq!(bytemuck::bytes_of(&raw), &Point::from(raw).to_bytes()[..]);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+#[allow(missing_docs)]
+pub struct RawPoint {
+    pub time: f64,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub altitude: f64,
+    pub x_velocity: f64,
+    pub y_velocity: f64,
+    pub z_velocity: f64,
+    pub roll: f64,
+    pub pitch: f64,
+    pub yaw: f64,
+    pub wander_angle: f64,
+    pub x_acceleration: f64,
+    pub y_acceleration: f64,
+    pub z_acceleration: f64,
+    pub x_angular_rate: f64,
+    pub y_angular_rate: f64,
+    pub z_angular_rate: f64,
+}
+
+const _: () = assert!(core::mem::size_of::<RawPoint>() == RECORD_SIZE);
+
+impl RawPoint {
+    /// Views the whole records in a byte buffer as raw points, without copying.
+    ///
+    /// Trailing bytes that don't make up a whole record are ignored. Only
+    /// available on little-endian targets.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffer isn't aligned to eight bytes. Memory
+    /// maps are page aligned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::RawPoint;
+    /// use std::io::Read;
+    ///
+    /// let mut buffer = vec![RawPoint::default(); 2];
+    /// std::fs::File::open("data/2-points.sbet")
+    ///     .unwrap()
+    ///     .read_exact(bytemuck::cast_slice_mut(&mut buffer))
+    ///     .unwrap();
+    /// let bytes: &[u8] = bytemuck::cast_slice(&buffer);
+    /// let points = RawPoint::cast_slice(bytes).unwrap();
+    /// assert_eq!(points.len(), 2);
+    /// ```
+    #[cfg(target_endian = "little")]
+    pub fn cast_slice(bytes: &[u8]) -> Result<&[RawPoint]> {
+        let len = bytes.len() - bytes.len() % RECORD_SIZE;
+        bytemuck::try_cast_slice(&bytes[..len]).map_err(Error::Cast)
+    }
+}
+
+impl From<RawPoint> for Point {
+    fn from(raw: RawPoint) -> Point {
+        Point {
+            time: raw.time,
+            latitude: raw.latitude,
+            longitude: raw.longitude,
+            altitude: raw.altitude,
+            x_velocity: raw.x_velocity,
+            y_velocity: raw.y_velocity,
+            z_velocity: raw.z_velocity,
+            roll: raw.roll,
+            pitch: raw.pitch,
+            yaw: raw.yaw,
+            wander_angle: raw.wander_angle,
+            x_acceleration: raw.x_acceleration,
+            y_acceleration: raw.y_acceleration,
+            z_acceleration: raw.z_acceleration,
+            x_angular_rate: raw.x_angular_rate,
+            y_angular_rate: raw.y_angular_rate,
+            z_angular_rate: raw.z_angular_rate,
+        }
+    }
+}
+
+impl From<Point> for RawPoint {
+    fn from(point: Point) -> RawPoint {
+        RawPoint {
+            time: point.time,
+            latitude: point.latitude,
+            longitude: point.longitude,
+            altitude: point.altitude,
+            x_velocity: point.x_velocity,
+            y_velocity: point.y_velocity,
+            z_velocity: point.z_velocity,
+            roll: point.roll,
+            pitch: point.pitch,
+            yaw: point.yaw,
+            wander_angle: point.wander_angle,
+            x_acceleration: point.x_acceleration,
+            y_acceleration: point.y_acceleration,
+            z_acceleration: point.z_acceleration,
+            x_angular_rate: point.x_angular_rate,
+            y_angular_rate: point.y_angular_rate,
+            z_angular_rate: point.z_angular_rate,
+        }
+    }
+}
+
+#[cfg(all(test, target_endian = "little"))]
+mod tests {
+    use super::RawPoint;
+    use crate::Point;
+
+    #[test]
+    fn matches_decoding() {
+        let bytes = std::fs::read("data/2-points.sbet").unwrap();
+        let mut buffer = vec![RawPoint::default(); 2];
+        bytemuck::cast_slice_mut::<RawPoint, u8>(&mut buffer).copy_from_slice(&bytes);
+        let raw = RawPoint::cast_slice(bytemuck::cast_slice(&buffer)).unwrap();
+        let points: Vec<Point> = crate::records(&bytes).collect();
+        assert_eq!(raw.len(), 2);
+        assert_eq!(Point::from(raw[1]), points[1]);
+    }
+}
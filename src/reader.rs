@@ -0,0 +1,1287 @@
+use crate::{Endianness, Error, Input, Point, RecordSchema, Result, TimeWindow, RECORD_SIZE};
+use std::{
+    io::{ErrorKind, Read, Seek, SeekFrom},
+    ops::Range,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+/// Use this structure to read sbet data from a source.
+///
+/// # Examples
+///
+/// [Reader] implements [Iterator]:
+///
+/// ```
+/// use sbet::Reader;
+///
+/// let reader = Reader::from_path("data/2-points.sbet").unwrap();
+/// for result in reader {
+///     let point = result.unwrap();
+///     dbg!(point);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Reader<R: Read> {
+    read: R,
+    endianness: Endianness,
+    schema: RecordSchema,
+    standard: bool,
+    buffer: Vec<u8>,
+    peeked: Vec<u8>,
+    peeked_position: usize,
+    index: u64,
+    offset: u64,
+    lenient: bool,
+    strict: bool,
+    skipped: Vec<Range<u64>>,
+    last_time: Option<f64>,
+    expected_records: Option<u64>,
+}
+
+/// The number of records inspected by [Reader::detect_endianness].
+const DETECTION_RECORDS: usize = 8;
+
+/// The number of records read at a time by [Reader::into_points].
+const COLLECT_CHUNK_SIZE: usize = 4096;
+
+impl<R: Read> Reader<R> {
+    /// Creates a reader for little-endian records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Reader;
+    ///
+    /// let bytes = std::fs::read("data/2-points.sbet").unwrap();
+    /// let reader = Reader::new(&bytes[..]);
+    /// assert_eq!(reader.count(), 2);
+    /// ```
+    pub fn new(read: R) -> Reader<R> {
+        Reader::with_endianness(read, Endianness::Little)
+    }
+
+    /// Creates a reader for records in the given byte order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Endianness, Reader};
+    ///
+    /// let reader = Reader::with_endianness(std::io::empty(), Endianness::Big);
+    /// assert_eq!(reader.endianness(), Endianness::Big);
+    /// ```
+    pub fn with_endianness(read: R, endianness: Endianness) -> Reader<R> {
+        Reader {
+            read,
+            endianness,
+            schema: RecordSchema::standard(),
+            standard: true,
+            buffer: Vec::new(),
+            peeked: Vec::new(),
+            peeked_position: 0,
+            index: 0,
+            offset: 0,
+            lenient: false,
+            strict: false,
+            skipped: Vec::new(),
+            last_time: None,
+            expected_records: None,
+        }
+    }
+
+    /// Creates a reader for little-endian records with a nonstandard layout.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Column, Field, Reader, RecordSchema};
+    ///
+    /// let schema = RecordSchema::new(vec![Column::F64(Field::Time), Column::Skip(8)]);
+    /// let bytes = std::fs::read("data/2-points.sbet").unwrap();
+    /// let reader = Reader::with_schema(&bytes[..], schema);
+    /// assert_eq!(reader.count(), 17);
+    /// ```
+    pub fn with_schema(read: R, schema: RecordSchema) -> Reader<R> {
+        let mut reader = Reader::new(read);
+        reader.set_schema(schema);
+        reader
+    }
+
+    /// Inspects the first few records and switches to the more plausible byte order.
+    ///
+    /// The inspected records are buffered, not consumed, so they're still
+    /// returned by subsequent reads. If detection is inconclusive, e.g. the
+    /// source is empty, the current byte order is kept. Call this before
+    /// reading any points; use [Reader::with_endianness] or
+    /// [Reader::set_endianness] to override with a known byte order
+    /// instead. See [Endianness::detect] for the heuristic, which assumes
+    /// the standard record layout.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Endianness, Reader};
+    ///
+    /// let mut reader = Reader::from_path("data/2-points.sbet").unwrap();
+    /// assert_eq!(reader.detect_endianness().unwrap(), Endianness::Little);
+    /// assert_eq!(reader.count(), 2);
+    /// ```
+    pub fn detect_endianness(&mut self) -> Result<Endianness> {
+        let start = self.peeked.len();
+        let mut limit = (&mut self.read).take((DETECTION_RECORDS * RECORD_SIZE) as u64);
+        limit.read_to_end(&mut self.peeked)?;
+        if let Some(endianness) = Endianness::detect(&self.peeked[start..]) {
+            debug!(%endianness, "detected byte order");
+            self.endianness = endianness;
+        }
+        Ok(self.endianness)
+    }
+
+    /// Returns this reader's byte order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Endianness, Reader};
+    ///
+    /// let reader = Reader::new(std::io::empty());
+    /// assert_eq!(reader.endianness(), Endianness::Little);
+    /// ```
+    pub fn endianness(&self) -> Endianness {
+        self.endianness
+    }
+
+    /// Sets this reader's byte order for subsequent reads.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Endianness, Reader};
+    ///
+    /// let mut reader = Reader::new(std::io::empty());
+    /// reader.set_endianness(Endianness::Big);
+    /// ```
+    pub fn set_endianness(&mut self, endianness: Endianness) {
+        self.endianness = endianness;
+    }
+
+    /// Returns this reader's record layout.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Reader, RecordSchema};
+    ///
+    /// let reader = Reader::new(std::io::empty());
+    /// assert_eq!(reader.schema(), &RecordSchema::standard());
+    /// ```
+    pub fn schema(&self) -> &RecordSchema {
+        &self.schema
+    }
+
+    /// Sets this reader's record layout for subsequent reads.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Reader, RecordSchema};
+    ///
+    /// let mut reader = Reader::new(std::io::empty());
+    /// reader.set_schema(RecordSchema::standard());
+    /// ```
+    pub fn set_schema(&mut self, schema: RecordSchema) {
+        self.standard = schema.is_standard();
+        self.schema = schema;
+    }
+
+    /// Enables or disables lenient reading, for recovering damaged files.
+    ///
+    /// In lenient mode, records with implausible values (e.g. a NaN time or
+    /// a latitude out of range) aren't returned. Instead, the reader scans
+    /// forward one byte at a time until it finds a plausible record, and
+    /// records the skipped byte range; see [Reader::skipped]. A truncated
+    /// trailing record is skipped instead of returning an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Reader;
+    ///
+    /// let mut bytes = vec![0xff; 10];
+    /// bytes.extend(std::fs::read("data/2-points.sbet").unwrap());
+    /// let mut reader = Reader::new(&bytes[..]);
+    /// reader.set_lenient(true);
+    /// let points = reader.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+    /// assert_eq!(points.len(), 2);
+    /// assert_eq!(reader.skipped(), [0..10]);
+    /// ```
+    pub fn set_lenient(&mut self, lenient: bool) {
+        self.lenient = lenient;
+    }
+
+    /// Returns true if this reader is in lenient mode.
+    pub fn is_lenient(&self) -> bool {
+        self.lenient
+    }
+
+    /// Enables or disables strict reading, which requires times to never go backwards.
+    ///
+    /// In strict mode, a record whose time is before the previous record's,
+    /// or is NaN, is returned as [Error::NonMonotonicTime] instead of a
+    /// point. Reading can continue after the error, and later records are
+    /// compared to the rejected one. Records are checked one at a time, so
+    /// [Reader::read_chunk] is slower in strict mode.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Error, Point, Reader};
+    ///
+    /// let points = [2., 1.].map(|time| Point { time, ..Default::default() });
+    /// let bytes = sbet::to_vec(&points);
+    /// let mut reader = Reader::new(&bytes[..]);
+    /// reader.set_strict(true);
+    /// assert!(reader.read_one().unwrap().is_some());
+    /// assert!(matches!(
+    ///     reader.read_one().unwrap_err(),
+    ///     Error::NonMonotonicTime { index: 1, .. }
+    /// ));
+    /// ```
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Returns true if this reader is in strict mode.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Returns the byte ranges skipped so far in lenient mode.
+    ///
+    /// Offsets are relative to where this reader started, like [Reader::offset].
+    pub fn skipped(&self) -> &[Range<u64>] {
+        &self.skipped
+    }
+
+    /// Returns the index of the next record to be read.
+    ///
+    /// Counts from zero at the position the underlying reader was in when
+    /// this reader was created.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Reader;
+    ///
+    /// let mut reader = Reader::from_path("data/2-points.sbet").unwrap();
+    /// let _ = reader.read_one().unwrap();
+    /// assert_eq!(reader.index(), 1);
+    /// ```
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+
+    /// Returns the byte offset of the next record to be read.
+    ///
+    /// Like [Reader::index], counts from the position the underlying reader
+    /// was in when this reader was created.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Reader;
+    ///
+    /// let mut reader = Reader::from_path("data/2-points.sbet").unwrap();
+    /// let _ = reader.read_one().unwrap();
+    /// assert_eq!(reader.offset(), 136);
+    /// ```
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.read
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.read
+    }
+
+    /// Consumes this reader, returning the underlying reader.
+    ///
+    /// Any records buffered by [Reader::detect_endianness] but not yet read are lost.
+    pub fn into_inner(self) -> R {
+        self.read
+    }
+
+    /// Reads one point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Reader;
+    ///
+    /// let mut reader = Reader::from_path("data/2-points.sbet").unwrap();
+    /// let point = reader.read_one().unwrap().unwrap();
+    /// ```
+    pub fn read_one(&mut self) -> Result<Option<Point>> {
+        let point = if self.lenient {
+            self.read_lenient()?
+        } else if self.standard {
+            let mut bytes = [0; RECORD_SIZE];
+            self.read_record(&mut bytes)?
+                .then(|| self.endianness.decode(&bytes))
+        } else {
+            let mut buffer = std::mem::take(&mut self.buffer);
+            buffer.resize(self.schema.size(), 0);
+            let result = self.read_record(&mut buffer);
+            let point =
+                result.map(|read| read.then(|| self.schema.decode(&buffer, self.endianness)));
+            self.buffer = buffer;
+            point?
+        };
+        if let Some(point) = &point {
+            let previous = self.last_time.replace(point.time);
+            if self.strict {
+                if let Some(previous) =
+                    previous.filter(|&previous| point.time < previous || point.time.is_nan())
+                {
+                    return Err(Error::NonMonotonicTime {
+                        index: self.index - 1,
+                        previous,
+                        current: point.time,
+                    });
+                }
+            }
+        }
+        Ok(point)
+    }
+
+    /// Reads up to `n` points, appending them to `points`.
+    ///
+    /// Reads all `n` records' bytes at once, then decodes them, which is
+    /// faster than reading points one at a time. Returns the number of
+    /// points read, which is less than `n` only at the end of the source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Reader;
+    ///
+    /// let mut reader = Reader::from_path("data/2-points.sbet").unwrap();
+    /// let mut points = Vec::new();
+    /// assert_eq!(reader.read_chunk(10, &mut points).unwrap(), 2);
+    /// assert_eq!(points.len(), 2);
+    /// assert_eq!(reader.read_chunk(10, &mut points).unwrap(), 0);
+    /// ```
+    pub fn read_chunk(&mut self, n: usize, points: &mut Vec<Point>) -> Result<usize> {
+        if self.lenient || self.strict {
+            let mut count = 0;
+            while count < n {
+                if let Some(point) = self.read_one()? {
+                    points.push(point);
+                    count += 1;
+                } else {
+                    break;
+                }
+            }
+            return Ok(count);
+        }
+        let mut buffer = std::mem::take(&mut self.buffer);
+        let result = self.read_raw_chunk(n, &mut buffer);
+        points.extend(
+            buffer
+                .chunks_exact(self.schema.size())
+                .map(|bytes| self.decode(bytes)),
+        );
+        self.buffer = buffer;
+        result
+    }
+
+    /// Reads up to `n` whole records' bytes, replacing the contents of `bytes`.
+    ///
+    /// In lenient and strict modes, the records are read one at a time and
+    /// re-encoded, so `bytes` never contains skipped or out-of-order data.
+    /// On a truncated trailing record, `bytes` holds the whole records
+    /// before it and an error is returned.
+    #[cfg(feature = "rayon")]
+    pub(crate) fn read_raw(&mut self, n: usize, bytes: &mut Vec<u8>) -> Result<usize> {
+        if self.lenient || self.strict {
+            let mut points = Vec::with_capacity(n);
+            let count = self.read_chunk(n, &mut points)?;
+            let size = self.schema.size();
+            bytes.clear();
+            bytes.resize(count * size, 0);
+            for (point, record) in points.iter().zip(bytes.chunks_exact_mut(size)) {
+                self.schema.encode(point, self.endianness, record);
+            }
+            Ok(count)
+        } else {
+            self.read_raw_chunk(n, bytes)
+        }
+    }
+
+    fn read_raw_chunk(&mut self, n: usize, bytes: &mut Vec<u8>) -> Result<usize> {
+        let size = self.schema.size();
+        bytes.clear();
+        bytes.resize(n * size, 0);
+        let filled = self.fill_up(bytes)?;
+        let count = filled / size;
+        bytes.truncate(count * size);
+        self.index += count as u64;
+        self.offset += (count * size) as u64;
+        if filled % size == 0 {
+            if count < n {
+                self.debug_end();
+            }
+            Ok(count)
+        } else {
+            Err(self.at_record(ErrorKind::UnexpectedEof.into()))
+        }
+    }
+
+    /// Returns a copy of this reader's record layout and byte order, for decoding on other threads.
+    #[cfg(feature = "rayon")]
+    pub(crate) fn decoder(&self) -> Decoder {
+        Decoder {
+            schema: self.schema.clone(),
+            standard: self.standard,
+            endianness: self.endianness,
+        }
+    }
+
+    /// Reads all of the remaining points, stopping at the first error.
+    ///
+    /// Records are read in chunks, which is faster than collecting the
+    /// iterator. When the reader was created with [Reader::from_path] for an
+    /// uncompressed file, the points are allocated up front.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Reader;
+    ///
+    /// let points = Reader::from_path("data/2-points.sbet").unwrap().into_points().unwrap();
+    /// assert_eq!(points.len(), 2);
+    /// ```
+    pub fn into_points(mut self) -> Result<Vec<Point>> {
+        let capacity = self
+            .expected_records
+            .map_or(0, |records| records.saturating_sub(self.index));
+        let mut points = Vec::with_capacity(usize::try_from(capacity).unwrap_or(0));
+        while self.read_chunk(COLLECT_CHUNK_SIZE, &mut points)? > 0 {}
+        Ok(points)
+    }
+
+    /// Returns an iterator over chunks of up to `n` points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Reader;
+    ///
+    /// let mut reader = Reader::from_path("data/2-points.sbet").unwrap();
+    /// for chunk in reader.chunks(1) {
+    ///     assert_eq!(chunk.unwrap().len(), 1);
+    /// }
+    /// ```
+    pub fn chunks(&mut self, n: usize) -> Chunks<'_, R> {
+        assert!(n > 0, "chunk size must be positive");
+        Chunks { reader: self, n }
+    }
+
+    /// Returns an iterator over the points within a time window.
+    ///
+    /// Points before the window are skipped, and reading stops at the first
+    /// point after it, so the rest of the source is never read. The points
+    /// must be sorted by time, or later points within the window are missed;
+    /// for unsorted sources, filter with [TimeWindow::contains] instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Reader, TimeWindow};
+    ///
+    /// let reader = Reader::from_path("data/2-points.sbet").unwrap();
+    /// let points: Vec<_> = reader
+    ///     .filter_by_time(TimeWindow::new(151631., 151631.004))
+    ///     .collect::<Result<_, _>>()
+    ///     .unwrap();
+    /// assert_eq!(points.len(), 1);
+    /// ```
+    pub fn filter_by_time(self, window: TimeWindow) -> FilterByTime<R> {
+        FilterByTime {
+            reader: self,
+            window,
+            done: false,
+        }
+    }
+
+    /// Returns an iterator that waits for records to be appended, like `tail -f`.
+    ///
+    /// At the end of the source, the iterator sleeps for `interval` and
+    /// tries again, so it never ends unless a [timeout](Follow::timeout) is
+    /// set. A partial record at the end is kept until the rest of it is
+    /// written. Lenient reading is not supported while following.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use sbet::Reader;
+    /// use std::{fs::File, time::Duration};
+    ///
+    /// let reader = Reader::new(File::open("live.sbet").unwrap());
+    /// for result in reader.follow(Duration::from_millis(200)) {
+    ///     println!("{}", result.unwrap().time);
+    /// }
+    /// ```
+    pub fn follow(self, interval: Duration) -> Follow<R> {
+        Follow {
+            reader: self,
+            interval,
+            timeout: None,
+        }
+    }
+
+    /// Reads one point if a whole record is available, keeping a partial record for the next read.
+    fn read_available(&mut self) -> Result<Option<Point>> {
+        let size = self.schema.size();
+        let mut bytes = std::mem::take(&mut self.buffer);
+        bytes.resize(size, 0);
+        let result = self.fill_up(&mut bytes).map(|filled| {
+            if filled == size {
+                self.index += 1;
+                self.offset += size as u64;
+                Some(self.decode(&bytes))
+            } else {
+                let position = self.peeked_position;
+                self.peeked
+                    .splice(position..position, bytes[..filled].iter().copied());
+                None
+            }
+        });
+        self.buffer = bytes;
+        result
+    }
+
+    fn read_record(&mut self, bytes: &mut [u8]) -> Result<bool> {
+        match self.fill_up(bytes)? {
+            0 => {
+                self.debug_end();
+                Ok(false)
+            }
+            n if n < bytes.len() => Err(self.at_record(ErrorKind::UnexpectedEof.into())),
+            _ => {
+                self.index += 1;
+                self.offset += bytes.len() as u64;
+                Ok(true)
+            }
+        }
+    }
+
+    fn read_lenient(&mut self) -> Result<Option<Point>> {
+        let mut window = std::mem::take(&mut self.buffer);
+        window.resize(2 * self.schema.size(), 0);
+        let result = self.resynchronize(&mut window);
+        self.buffer = window;
+        result
+    }
+
+    /// Reads the next plausible record, scanning forward byte by byte past implausible ones.
+    ///
+    /// The window holds a candidate record and the one after it. Once
+    /// scanning, a candidate is only accepted if its time doesn't go
+    /// backwards and the following record, if there is one, is also
+    /// plausible and later. Eight-byte shifts of valid records decode to
+    /// plausible-looking values, so range checks alone aren't enough.
+    fn resynchronize(&mut self, window: &mut [u8]) -> Result<Option<Point>> {
+        let size = self.schema.size();
+        let start = self.offset;
+        let mut skipped = 0;
+        let mut filled = self.fill_up(window)?;
+        loop {
+            if filled < size {
+                let end = start + skipped + filled as u64;
+                if end > start {
+                    debug!(start, end, "skipped implausible bytes at the end");
+                    self.skipped.push(start..end);
+                }
+                self.offset = end;
+                self.debug_end();
+                return Ok(None);
+            }
+            let point = self.decode(&window[..size]);
+            let accept = crate::endianness::is_plausible(&point)
+                && (skipped == 0
+                    || (self.last_time.is_none_or(|last| {
+                        point.time >= last || crate::time::is_rollover(last, point.time)
+                    }) && (filled < window.len() || {
+                        let next = self.decode(&window[size..]);
+                        crate::endianness::is_plausible(&next) && next.time > point.time
+                    })));
+            if accept {
+                if skipped > 0 {
+                    debug!(start, end = start + skipped, "skipped implausible bytes");
+                    self.skipped.push(start..start + skipped);
+                }
+                let position = self.peeked_position;
+                self.peeked
+                    .splice(position..position, window[size..filled].iter().copied());
+                self.index += 1;
+                self.offset = start + skipped + size as u64;
+                return Ok(Some(point));
+            }
+            window.copy_within(1..filled, 0);
+            filled -= 1;
+            skipped += 1;
+            filled += self.fill_up(&mut window[filled..])?;
+        }
+    }
+
+    fn debug_end(&self) {
+        debug!(
+            records = self.index,
+            bytes = self.offset,
+            "reached the end of the records"
+        );
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Point {
+        decode(&self.schema, self.standard, self.endianness, bytes)
+    }
+
+    /// Reads until `bytes` is full or the source is exhausted, returning the number of bytes read.
+    fn fill_up(&mut self, bytes: &mut [u8]) -> Result<usize> {
+        let mut filled = 0;
+        while filled < bytes.len() {
+            match self.fill(&mut bytes[filled..]) {
+                Ok(0) => break,
+                Ok(n) => filled += n,
+                Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(err) => return Err(self.at_record(err)),
+            }
+        }
+        Ok(filled)
+    }
+
+    fn at_record(&self, err: std::io::Error) -> Error {
+        Error::AtRecord {
+            index: self.index,
+            offset: self.offset,
+            source: Box::new(err.into()),
+        }
+    }
+
+    fn fill(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.peeked_position < self.peeked.len() {
+            let n = (&self.peeked[self.peeked_position..]).read(buf)?;
+            self.peeked_position += n;
+            if self.peeked_position == self.peeked.len() {
+                self.peeked.clear();
+                self.peeked_position = 0;
+            }
+            Ok(n)
+        } else {
+            self.read.read(buf)
+        }
+    }
+}
+
+impl<R: Read + Seek> Reader<R> {
+    /// Seeks to a record by index.
+    ///
+    /// The underlying stream is seeked to `index` times the record size
+    /// from its start, so this assumes the records start at the beginning
+    /// of the stream. Any records buffered by [Reader::detect_endianness]
+    /// are discarded.
+    ///
+    /// # Errors
+    ///
+    /// Returns [Error::SeekOutOfRange] if the record's byte offset doesn't fit in a `u64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Reader;
+    /// use std::fs::File;
+    ///
+    /// let mut reader = Reader::new(File::open("data/2-points.sbet").unwrap());
+    /// reader.seek(1).unwrap();
+    /// assert_eq!(reader.offset(), 136);
+    /// assert!(reader.seek(u64::MAX).is_err());
+    /// reader.seek(1).unwrap();
+    /// assert_eq!(reader.count(), 1);
+    /// ```
+    pub fn seek(&mut self, index: u64) -> Result<()> {
+        let offset = index
+            .checked_mul(self.schema.size() as u64)
+            .ok_or(Error::SeekOutOfRange(index))?;
+        let _ = self.read.seek(SeekFrom::Start(offset))?;
+        self.peeked.clear();
+        self.peeked_position = 0;
+        self.index = index;
+        self.offset = offset;
+        self.last_time = None;
+        Ok(())
+    }
+
+    /// Returns an iterator over the points from the last to the first.
+    ///
+    /// Records are read in chunks, seeking backwards from the end, so only
+    /// the records that are iterated over are read. The number of records
+    /// is taken from the length of the stream, and a trailing partial record
+    /// is ignored. The reader's position is undefined afterwards; use
+    /// [Reader::seek] before reading forwards again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Reader;
+    /// use std::fs::File;
+    ///
+    /// let mut reader = Reader::new(File::open("data/2-points.sbet").unwrap());
+    /// let times: Vec<f64> = reader
+    ///     .iter_rev()
+    ///     .unwrap()
+    ///     .map(|result| result.unwrap().time)
+    ///     .collect();
+    /// assert!(times[0] > times[1]);
+    /// ```
+    pub fn iter_rev(&mut self) -> Result<IterRev<'_, R>> {
+        let remaining = self.estimate_number_of_points()?;
+        Ok(IterRev {
+            reader: self,
+            remaining,
+            points: Vec::new(),
+        })
+    }
+
+    /// Estimates the number of records from the length of the stream.
+    ///
+    /// Like [Reader::seek], this assumes the records start at the beginning
+    /// of the stream. A trailing partial record is ignored. The stream is
+    /// returned to its position, so reading can continue where it was.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Reader;
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = std::fs::read("data/2-points.sbet").unwrap();
+    /// let mut reader = Reader::new(Cursor::new(bytes));
+    /// assert_eq!(reader.estimate_number_of_points().unwrap(), 2);
+    /// assert_eq!(reader.count(), 2);
+    /// ```
+    pub fn estimate_number_of_points(&mut self) -> Result<u64> {
+        Ok(self.stream_len()? / self.schema.size() as u64)
+    }
+
+    /// Returns the number of records from the length of the stream, requiring it to be a whole number of records.
+    ///
+    /// # Errors
+    ///
+    /// Returns [Error::UnalignedLength] if the stream ends with a partial record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Reader;
+    /// use std::io::Cursor;
+    ///
+    /// let mut bytes = std::fs::read("data/2-points.sbet").unwrap();
+    /// bytes.push(0);
+    /// let mut reader = Reader::new(Cursor::new(bytes));
+    /// assert!(reader.exact_number_of_points().is_err());
+    /// ```
+    pub fn exact_number_of_points(&mut self) -> Result<u64> {
+        let len = self.stream_len()?;
+        let size = self.schema.size() as u64;
+        match len % size {
+            0 => Ok(len / size),
+            remainder => Err(Error::UnalignedLength { len, remainder }),
+        }
+    }
+
+    fn stream_len(&mut self) -> Result<u64> {
+        let position = self.read.stream_position()?;
+        let len = self.read.seek(SeekFrom::End(0))?;
+        if position != len {
+            let _ = self.read.seek(SeekFrom::Start(position))?;
+        }
+        Ok(len)
+    }
+
+    /// Seeks to and reads the first record.
+    ///
+    /// Returns `None` if there are no records. The reader is left after the first record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Reader;
+    /// use std::fs::File;
+    ///
+    /// let mut reader = Reader::new(File::open("data/2-points.sbet").unwrap());
+    /// let first = reader.read_first().unwrap().unwrap();
+    /// assert_eq!(reader.index(), 1);
+    /// ```
+    pub fn read_first(&mut self) -> Result<Option<Point>> {
+        self.seek(0)?;
+        self.read_one()
+    }
+
+    /// Seeks to and reads the last whole record, without reading the ones before it.
+    ///
+    /// The number of records is taken from the length of the stream, and a
+    /// trailing partial record is ignored. Returns `None` if there are no
+    /// whole records. The reader is left after the last record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Reader;
+    /// use std::fs::File;
+    ///
+    /// let mut reader = Reader::new(File::open("data/2-points.sbet").unwrap());
+    /// let last = reader.read_last().unwrap().unwrap();
+    /// assert_eq!(reader.index(), 2);
+    /// assert!(reader.read_one().unwrap().is_none());
+    /// ```
+    pub fn read_last(&mut self) -> Result<Option<Point>> {
+        match self.estimate_number_of_points()? {
+            0 => {
+                self.seek(0)?;
+                Ok(None)
+            }
+            count => {
+                self.seek(count - 1)?;
+                self.read_one()
+            }
+        }
+    }
+}
+
+impl Reader<Input> {
+    /// Creates a reader for the file at the path.
+    ///
+    /// Gzip and zstd compressed files are decompressed transparently, with
+    /// the `gzip` and `zstd` features; see [Input].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Reader;
+    ///
+    /// let reader = Reader::from_path("data/2-points.sbet").unwrap();
+    /// ```
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Reader<Input>> {
+        let path = path.as_ref();
+        let input = Input::open(path)?;
+        let expected_records = if input.is_compressed() {
+            None
+        } else {
+            Some(crate::estimate_number_of_points(path)?)
+        };
+        let mut reader = Reader::new(input);
+        reader.expected_records = expected_records;
+        Ok(reader)
+    }
+}
+
+impl<R: Read> Iterator for Reader<R> {
+    type Item = Result<Point>;
+
+    fn next(&mut self) -> Option<Result<Point>> {
+        match self.read_one() {
+            Ok(option) => option.map(Ok),
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+/// Decodes records on behalf of a [Reader].
+#[cfg(feature = "rayon")]
+#[derive(Clone, Debug)]
+pub(crate) struct Decoder {
+    schema: RecordSchema,
+    standard: bool,
+    endianness: Endianness,
+}
+
+#[cfg(feature = "rayon")]
+impl Decoder {
+    pub(crate) fn size(&self) -> usize {
+        self.schema.size()
+    }
+
+    pub(crate) fn decode(&self, bytes: &[u8]) -> Point {
+        decode(&self.schema, self.standard, self.endianness, bytes)
+    }
+}
+
+fn decode(schema: &RecordSchema, standard: bool, endianness: Endianness, bytes: &[u8]) -> Point {
+    if standard {
+        endianness.decode(bytes.try_into().expect("bytes are record sized"))
+    } else {
+        schema.decode(bytes, endianness)
+    }
+}
+
+/// An iterator over chunks of points, created by [Reader::chunks].
+#[derive(Debug)]
+pub struct Chunks<'a, R: Read> {
+    reader: &'a mut Reader<R>,
+    n: usize,
+}
+
+impl<R: Read> Iterator for Chunks<'_, R> {
+    type Item = Result<Vec<Point>>;
+
+    fn next(&mut self) -> Option<Result<Vec<Point>>> {
+        let mut points = Vec::with_capacity(self.n);
+        match self.reader.read_chunk(self.n, &mut points) {
+            Ok(0) => None,
+            Ok(_) => Some(Ok(points)),
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+/// An iterator over the points within a time window, created by [Reader::filter_by_time].
+#[derive(Debug)]
+pub struct FilterByTime<R: Read> {
+    reader: Reader<R>,
+    window: TimeWindow,
+    done: bool,
+}
+
+impl<R: Read> FilterByTime<R> {
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> Reader<R> {
+        self.reader
+    }
+}
+
+impl<R: Read> Iterator for FilterByTime<R> {
+    type Item = Result<Point>;
+
+    fn next(&mut self) -> Option<Result<Point>> {
+        while !self.done {
+            match self.reader.read_one() {
+                Ok(Some(point)) if point.time > self.window.stop => self.done = true,
+                Ok(Some(point)) if self.window.contains(point.time) => return Some(Ok(point)),
+                Ok(Some(_)) => {}
+                Ok(None) => self.done = true,
+                Err(err) => return Some(Err(err)),
+            }
+        }
+        None
+    }
+}
+
+/// The number of records read at a time by [IterRev].
+const REVERSE_CHUNK_SIZE: u64 = 1024;
+
+/// An iterator over points from last to first, created by [Reader::iter_rev].
+#[derive(Debug)]
+pub struct IterRev<'a, R: Read + Seek> {
+    reader: &'a mut Reader<R>,
+    remaining: u64,
+    points: Vec<Point>,
+}
+
+impl<R: Read + Seek> Iterator for IterRev<'_, R> {
+    type Item = Result<Point>;
+
+    fn next(&mut self) -> Option<Result<Point>> {
+        if self.points.is_empty() && self.remaining > 0 {
+            let n = self.remaining.min(REVERSE_CHUNK_SIZE);
+            self.remaining -= n;
+            if let Err(err) = self
+                .reader
+                .seek(self.remaining)
+                .and_then(|()| self.reader.read_chunk(n as usize, &mut self.points))
+            {
+                self.remaining = 0;
+                return Some(Err(err));
+            }
+        }
+        self.points.pop().map(Ok)
+    }
+}
+
+/// An iterator that waits for records to be appended to a source, created by [Reader::follow].
+#[derive(Debug)]
+pub struct Follow<R: Read> {
+    reader: Reader<R>,
+    interval: Duration,
+    timeout: Option<Duration>,
+}
+
+impl<R: Read> Follow<R> {
+    /// Ends the iteration after no new records arrive for this long.
+    ///
+    /// Calling [Iterator::next] again after the end resumes waiting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Reader;
+    /// use std::{fs::File, time::Duration};
+    ///
+    /// let reader = Reader::new(File::open("data/2-points.sbet").unwrap());
+    /// let follow = reader.follow(Duration::from_millis(10)).timeout(Duration::ZERO);
+    /// assert_eq!(follow.count(), 2);
+    /// ```
+    pub fn timeout(mut self, timeout: Duration) -> Follow<R> {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> Reader<R> {
+        self.reader
+    }
+}
+
+impl<R: Read> Iterator for Follow<R> {
+    type Item = Result<Point>;
+
+    fn next(&mut self) -> Option<Result<Point>> {
+        let start = Instant::now();
+        loop {
+            match self.reader.read_available() {
+                Ok(Some(point)) => return Some(Ok(point)),
+                Ok(None) => {
+                    if self
+                        .timeout
+                        .is_some_and(|timeout| start.elapsed() >= timeout)
+                    {
+                        return None;
+                    }
+                    std::thread::sleep(self.interval);
+                }
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Reader;
+    use crate::{Endianness, Error, Writer};
+
+    #[test]
+    fn truncated() {
+        let bytes = std::fs::read("data/2-points.sbet").unwrap();
+        let mut reader = Reader::new(&bytes[..200]);
+        assert!(reader.read_one().unwrap().is_some());
+        match reader.read_one().unwrap_err() {
+            Error::AtRecord {
+                index,
+                offset,
+                source,
+            } => {
+                assert_eq!(index, 1);
+                assert_eq!(offset, 136);
+                assert!(matches!(*source, Error::Io(_)));
+            }
+            err => panic!("unexpected error: {}", err),
+        }
+    }
+
+    #[test]
+    fn strict_chunks() {
+        let points = [1., 2., 2., 1.5, 3.].map(|time| crate::Point {
+            time,
+            ..Default::default()
+        });
+        let bytes = crate::to_vec(&points);
+        let mut reader = Reader::new(&bytes[..]);
+        reader.set_strict(true);
+        let mut read = Vec::new();
+        match reader.read_chunk(10, &mut read).unwrap_err() {
+            Error::NonMonotonicTime {
+                index,
+                previous,
+                current,
+            } => assert_eq!((index, previous, current), (3, 2., 1.5)),
+            err => panic!("unexpected error: {}", err),
+        }
+        assert_eq!(read.len(), 3);
+        assert_eq!(reader.read_chunk(10, &mut read).unwrap(), 1);
+    }
+
+    #[test]
+    fn into_points() {
+        let reader = Reader::from_path("data/2-points.sbet").unwrap();
+        assert_eq!(reader.expected_records, Some(2));
+        assert_eq!(reader.into_points().unwrap().len(), 2);
+        let bytes = std::fs::read("data/2-points.sbet").unwrap();
+        assert!(Reader::new(&bytes[..200]).into_points().is_err());
+    }
+
+    #[test]
+    fn resynchronize() {
+        let points: Vec<_> = (0..10)
+            .map(|i| {
+                crate::Point::builder()
+                    .time(151631. + f64::from(i) * 0.005)
+                    .latitude_degrees(32.5)
+                    .longitude_degrees(-117.)
+                    .altitude(100. + f64::from(i))
+                    .x_velocity(50.)
+                    .yaw(1.)
+                    .z_acceleration(-9.8)
+                    .x_angular_rate(if i % 2 == 0 { 0.01 } else { -0.01 })
+                    .build()
+            })
+            .collect();
+        let mut bytes = crate::to_vec(&points);
+        bytes[2 * 136 + 8..2 * 136 + 16].copy_from_slice(&f64::NAN.to_le_bytes());
+        let _ = bytes.drain(5 * 136 + 20..5 * 136 + 30);
+        bytes.extend([1, 2, 3]);
+
+        let mut reader = Reader::new(&bytes[..]);
+        reader.set_lenient(true);
+        let read = reader.by_ref().collect::<crate::Result<Vec<_>>>().unwrap();
+        let expected: Vec<_> = [0, 1, 3, 4, 6, 7, 8, 9].map(|i| points[i]).into();
+        assert_eq!(read, expected);
+        assert_eq!(
+            reader.skipped(),
+            [2 * 136..3 * 136, 5 * 136..6 * 136 - 10, 1350..1353]
+        );
+        assert_eq!(reader.index(), 8);
+        assert_eq!(reader.offset(), 1353);
+    }
+
+    #[test]
+    fn filter_by_time_stops_early() {
+        let points: Vec<_> = (0..10)
+            .map(|i| crate::Point {
+                time: f64::from(i),
+                ..Default::default()
+            })
+            .collect();
+        let bytes = crate::to_vec(&points);
+        let mut filter = Reader::new(&bytes[..]).filter_by_time(crate::TimeWindow::new(2.5, 5.));
+        let times: Vec<f64> = filter.by_ref().map(|result| result.unwrap().time).collect();
+        assert_eq!(times, [3., 4., 5.]);
+        assert_eq!(filter.into_inner().index(), 7);
+    }
+
+    #[test]
+    fn first_and_last() {
+        let points: Vec<_> = (0..3)
+            .map(|i| crate::Point {
+                time: f64::from(i),
+                ..Default::default()
+            })
+            .collect();
+        let mut bytes = crate::to_vec(&points);
+        bytes.extend([0; 10]);
+        let mut reader = Reader::new(std::io::Cursor::new(bytes));
+        assert_eq!(reader.read_last().unwrap().unwrap().time, 2.);
+        assert_eq!(reader.read_first().unwrap().unwrap().time, 0.);
+        assert_eq!(reader.read_one().unwrap().unwrap().time, 1.);
+        let mut reader = Reader::new(std::io::Cursor::new(vec![0; 10]));
+        assert!(reader.read_last().unwrap().is_none());
+        assert!(reader.read_first().is_err());
+    }
+
+    #[test]
+    fn follow_partial_records() {
+        use std::{io::Write, time::Duration};
+
+        let points: Vec<_> = (0..3)
+            .map(|i| crate::Point {
+                time: f64::from(i),
+                ..Default::default()
+            })
+            .collect();
+        let bytes = crate::to_vec(&points);
+        let path = std::env::temp_dir().join(format!("sbet-follow-{}.sbet", std::process::id()));
+        std::fs::write(&path, &bytes[..200]).unwrap();
+        let mut follow = Reader::new(std::fs::File::open(&path).unwrap())
+            .follow(Duration::from_millis(1))
+            .timeout(Duration::from_millis(5));
+        assert_eq!(follow.next().unwrap().unwrap().time, 0.);
+        assert!(follow.next().is_none());
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        file.write_all(&bytes[200..]).unwrap();
+        let times: Vec<f64> = follow.by_ref().map(|result| result.unwrap().time).collect();
+        assert_eq!(times, [1., 2.]);
+        assert_eq!(follow.into_inner().offset(), 408);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn iter_rev() {
+        let points: Vec<_> = (0..2500)
+            .map(|i| crate::Point {
+                time: f64::from(i),
+                ..Default::default()
+            })
+            .collect();
+        let mut bytes = crate::to_vec(&points);
+        bytes.extend([0; 20]);
+        let mut reader = Reader::new(std::io::Cursor::new(bytes));
+        let times: Vec<f64> = reader
+            .iter_rev()
+            .unwrap()
+            .map(|result| result.unwrap().time)
+            .collect();
+        let expected: Vec<f64> = (0..2500).rev().map(f64::from).collect();
+        assert_eq!(times, expected);
+        let mut reader = Reader::new(std::io::Cursor::new(Vec::new()));
+        assert_eq!(reader.iter_rev().unwrap().count(), 0);
+    }
+
+    #[test]
+    fn chunks() {
+        let points: Vec<_> = (0..5)
+            .map(|i| crate::Point {
+                time: f64::from(i),
+                ..Default::default()
+            })
+            .collect();
+        let mut bytes = crate::to_vec(&points);
+        let mut reader = Reader::new(&bytes[..]);
+        let chunks = reader.chunks(2).collect::<crate::Result<Vec<_>>>().unwrap();
+        assert_eq!(chunks, [&points[..2], &points[2..4], &points[4..]]);
+
+        bytes.push(0);
+        let mut reader = Reader::new(&bytes[..]);
+        let mut read = Vec::new();
+        assert!(reader.read_chunk(10, &mut read).is_err());
+        assert_eq!(read, points);
+        assert_eq!(reader.index(), 5);
+    }
+
+    #[test]
+    fn big_endian_round_trip() {
+        let points = Reader::from_path("data/2-points.sbet")
+            .unwrap()
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap();
+        let mut writer = Writer::with_endianness(Vec::new(), Endianness::Big);
+        for &point in &points {
+            writer.write_one(point).unwrap();
+        }
+        let bytes = writer.into_inner();
+        let reader = Reader::with_endianness(&bytes[..], Endianness::Big);
+        assert_eq!(reader.collect::<crate::Result<Vec<_>>>().unwrap(), points);
+    }
+}
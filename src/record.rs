@@ -0,0 +1,132 @@
+use crate::{Endianness, Point};
+use alloc::vec::Vec;
+
+/// The size of one SBET record in bytes: seventeen little-endian `f64`s, in [Field::ALL](crate::Field::ALL) order.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(sbet::RECORD_SIZE, 17 * 8);
+/// ```
+pub const RECORD_SIZE: usize = 136;
+
+impl Point {
+    /// Decodes a point from one raw record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Point, RECORD_SIZE};
+    ///
+    /// let bytes = std::fs::read("data/2-points.sbet").unwrap();
+    /// let point = Point::from_bytes(bytes[..RECORD_SIZE].try_into().unwrap());
+    /// assert!((point.time - 151631.003).abs() < 1e-3);
+    /// ```
+    pub fn from_bytes(bytes: &[u8; RECORD_SIZE]) -> Point {
+        Endianness::Little.decode(bytes)
+    }
+
+    /// Encodes this point as one raw record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Point;
+    ///
+    /// let point = Point { time: 1., ..Default::default() };
+    /// assert_eq!(Point::from_bytes(&point.to_bytes()), point);
+    /// ```
+    pub fn to_bytes(&self) -> [u8; RECORD_SIZE] {
+        Endianness::Little.encode(self)
+    }
+}
+
+/// Decodes the points in a byte slice, without the standard library.
+///
+/// Trailing bytes that don't make up a whole record are skipped; see
+/// [Records::remainder].
+///
+/// # Examples
+///
+/// ```
+/// let bytes = std::fs::read("data/2-points.sbet").unwrap();
+/// let points: Vec<_> = sbet::records(&bytes).collect();
+/// assert_eq!(points.len(), 2);
+/// ```
+pub fn records(bytes: &[u8]) -> Records<'_> {
+    Records {
+        chunks: bytes.chunks_exact(RECORD_SIZE),
+    }
+}
+
+/// Encodes points into a byte vector, without the standard library.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::Point;
+///
+/// let bytes = sbet::to_vec(&[Point::default(), Point::default()]);
+/// assert_eq!(sbet::records(&bytes).count(), 2);
+/// ```
+pub fn to_vec(points: &[Point]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(points.len() * RECORD_SIZE);
+    for point in points {
+        bytes.extend_from_slice(&point.to_bytes());
+    }
+    bytes
+}
+
+/// An iterator over the points in a byte slice, created by [records].
+#[derive(Clone, Debug)]
+pub struct Records<'a> {
+    chunks: core::slice::ChunksExact<'a, u8>,
+}
+
+impl<'a> Records<'a> {
+    /// Returns the trailing bytes that don't make up a whole record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let bytes = std::fs::read("data/2-points.sbet").unwrap();
+    /// let records = sbet::records(&bytes[..200]);
+    /// assert_eq!(records.remainder().len(), 64);
+    /// ```
+    pub fn remainder(&self) -> &'a [u8] {
+        self.chunks.remainder()
+    }
+}
+
+impl Iterator for Records<'_> {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Point> {
+        self.chunks
+            .next()
+            .map(|chunk| Point::from_bytes(chunk.try_into().expect("chunks are record sized")))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Records<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::Point;
+
+    #[test]
+    fn round_trip() {
+        let point = Point::builder()
+            .time(1.)
+            .latitude(0.5)
+            .z_angular_rate(-3.)
+            .build();
+        let bytes = point.to_bytes();
+        assert_eq!(bytes[..8], 1f64.to_le_bytes());
+        assert_eq!(Point::from_bytes(&bytes), point);
+    }
+}
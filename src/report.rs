@@ -0,0 +1,463 @@
+//! Quality control reports of a trajectory, as Markdown or HTML documents.
+//!
+//! A report has the time span, bounds, sample rate and interval histogram,
+//! gaps, and statistics of every field and derived value. With the `plot`
+//! feature, it also has an image of the ground track.
+//!
+//! # Examples
+//!
+//! ```
+//! use sbet::report::{Builder, Format};
+//! use sbet::Trajectory;
+//!
+//! let trajectory = Trajectory::from_path("data/2-points.sbet").unwrap();
+//! let report = Builder::new()
+//!     .title("Flight 1")
+//!     .format(Format::Html)
+//!     .to_string(&trajectory)
+//!     .unwrap();
+//! assert!(report.contains("<h1>Flight 1</h1>"));
+//! ```
+
+use crate::{kml::escape, Error, Result, SpeedUnit, Stats, Trajectory};
+use std::{fmt::Write as _, io::Write, str::FromStr};
+
+/// The number of buckets in the report's interval histogram.
+const HISTOGRAM_BUCKETS: usize = 10;
+
+/// The document format of a report.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Markdown, with the ground track as a data URI image.
+    #[default]
+    Markdown,
+
+    /// A standalone HTML page, with the ground track as inline SVG.
+    Html,
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    /// Parses "md", "markdown", or "html".
+    fn from_str(s: &str) -> Result<Format> {
+        match s {
+            "md" | "markdown" => Ok(Format::Markdown),
+            "html" => Ok(Format::Html),
+            _ => Err(Error::UnknownReportFormat(s.to_string())),
+        }
+    }
+}
+
+/// Builds a quality control report of a trajectory.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::report::Builder;
+/// use sbet::Trajectory;
+///
+/// let trajectory = Trajectory::from_path("data/2-points.sbet").unwrap();
+/// let mut markdown = Vec::new();
+/// Builder::new().write(&trajectory, &mut markdown).unwrap();
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Builder {
+    title: Option<String>,
+    format: Format,
+    gap: Option<f64>,
+    speed_units: SpeedUnit,
+}
+
+/// A part of a report, rendered the same way in every format.
+enum Block {
+    Heading(String),
+    Paragraph(String),
+    Table(Vec<&'static str>, Vec<Vec<String>>),
+    #[cfg(feature = "plot")]
+    Image(String, String),
+}
+
+impl Builder {
+    /// Creates a builder for a Markdown report.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::report::Builder;
+    ///
+    /// let builder = Builder::new();
+    /// ```
+    pub fn new() -> Builder {
+        Builder::default()
+    }
+
+    /// Sets the report title, which defaults to "Trajectory report".
+    pub fn title(mut self, title: &str) -> Builder {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    /// Sets the document format.
+    pub fn format(mut self, format: Format) -> Builder {
+        self.format = format;
+        self
+    }
+
+    /// Lists gaps where points are more than this many seconds apart.
+    ///
+    /// Defaults to one and a half nominal time steps, so every dropped epoch is in a gap.
+    pub fn gap(mut self, seconds: f64) -> Builder {
+        self.gap = Some(seconds);
+        self
+    }
+
+    /// Sets the units of velocities and speeds in the statistics, which default to meters per second.
+    pub fn speed_units(mut self, speed_units: SpeedUnit) -> Builder {
+        self.speed_units = speed_units;
+        self
+    }
+
+    /// Writes the report of the trajectory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the trajectory has no points, or, with the `plot`
+    /// feature, if the ground track can't be drawn.
+    pub fn write<W: Write>(&self, trajectory: &Trajectory, mut write: W) -> Result<()> {
+        let blocks = self.blocks(trajectory)?;
+        let title = self.title.as_deref().unwrap_or("Trajectory report");
+        let document = match self.format {
+            Format::Markdown => markdown(title, &blocks),
+            Format::Html => html(title, &blocks),
+        };
+        write.write_all(document.as_bytes())?;
+        Ok(())
+    }
+
+    /// Returns the report of the trajectory as a string.
+    pub fn to_string(&self, trajectory: &Trajectory) -> Result<String> {
+        let mut bytes = Vec::new();
+        self.write(trajectory, &mut bytes)?;
+        Ok(String::from_utf8(bytes).expect("reports are UTF-8"))
+    }
+
+    fn blocks(&self, trajectory: &Trajectory) -> Result<Vec<Block>> {
+        let points = trajectory.points();
+        let (first, last) = match (points.first(), points.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Err(Error::NoPoints),
+        };
+        let mut blocks = vec![
+            Block::Heading("Summary".to_string()),
+            Block::Table(
+                vec!["", "value"],
+                vec![
+                    row("points", points.len()),
+                    row("start time (s)", first.time),
+                    row("end time (s)", last.time),
+                    row("duration (s)", last.time - first.time),
+                    row("length (m)", format!("{:.3}", trajectory.length_meters())),
+                ],
+            ),
+        ];
+
+        let bounds = trajectory.bounds().ok_or(Error::NoPoints)?.to_degrees();
+        blocks.push(Block::Heading("Bounds".to_string()));
+        blocks.push(Block::Table(
+            vec!["", "min", "max"],
+            vec![
+                vec![
+                    "latitude (°)".to_string(),
+                    format!("{:.9}", bounds.min_latitude),
+                    format!("{:.9}", bounds.max_latitude),
+                ],
+                vec![
+                    "longitude (°)".to_string(),
+                    format!("{:.9}", bounds.min_longitude),
+                    format!("{:.9}", bounds.max_longitude),
+                ],
+                vec![
+                    "altitude (m)".to_string(),
+                    format!("{:.3}", bounds.min_altitude),
+                    format!("{:.3}", bounds.max_altitude),
+                ],
+            ],
+        ));
+
+        blocks.push(Block::Heading("Sample rate".to_string()));
+        let gap = match (
+            trajectory.sample_rate(),
+            trajectory.interval_histogram(HISTOGRAM_BUCKETS),
+        ) {
+            (Some(sample_rate), Some(histogram)) => {
+                blocks.push(Block::Table(
+                    vec!["", "value"],
+                    vec![
+                        row("nominal (Hz)", sample_rate.nominal),
+                        row("detected (Hz)", format!("{:.3}", sample_rate.detected)),
+                        row("jitter rms (s)", format!("{:e}", sample_rate.jitter_rms)),
+                        row("jitter max (s)", format!("{:e}", sample_rate.jitter_max)),
+                        row("dropped epochs", histogram.dropped()),
+                        row("repeated or backwards times", histogram.non_positive),
+                    ],
+                ));
+                let last = histogram.buckets.len() - 1;
+                blocks.push(Block::Table(
+                    vec!["steps", "intervals", "dropped"],
+                    histogram
+                        .buckets
+                        .iter()
+                        .enumerate()
+                        .map(|(i, bucket)| {
+                            let plus = if i == last { "+" } else { "" };
+                            vec![
+                                format!("{}{}", bucket.steps, plus),
+                                bucket.intervals.to_string(),
+                                bucket.dropped.to_string(),
+                            ]
+                        })
+                        .collect(),
+                ));
+                self.gap.unwrap_or(1.5 * histogram.step)
+            }
+            _ => {
+                blocks.push(Block::Paragraph(
+                    "The sample rate can't be detected.".to_string(),
+                ));
+                self.gap.unwrap_or(f64::INFINITY)
+            }
+        };
+
+        blocks.push(Block::Heading("Gaps".to_string()));
+        let gaps = trajectory.gaps(gap);
+        if gaps.is_empty() {
+            blocks.push(Block::Paragraph(format!(
+                "No points are more than {} s apart.",
+                gap
+            )));
+        } else {
+            blocks.push(Block::Table(
+                vec!["start (s)", "stop (s)", "duration (s)"],
+                gaps.iter()
+                    .map(|window| {
+                        vec![
+                            window.start.to_string(),
+                            window.stop.to_string(),
+                            format!("{:.6}", window.duration()),
+                        ]
+                    })
+                    .collect(),
+            ));
+        }
+
+        let mut stats = Stats::with_percentiles();
+        stats.extend(points);
+        blocks.push(Block::Heading("Statistics".to_string()));
+        blocks.push(Block::Paragraph(format!(
+            "Angles are in degrees, velocities and speeds are in {}, and other values are in meters and seconds.",
+            self.speed_units.abbreviation()
+        )));
+        blocks.push(Block::Table(
+            vec![
+                "quantity", "min", "max", "max_abs", "mean", "std_dev", "p5", "p50", "p95",
+            ],
+            stats
+                .summaries()
+                .into_iter()
+                .map(|summary| {
+                    let scale = if summary.quantity.is_angular() {
+                        1f64.to_degrees()
+                    } else if summary.quantity.is_speed() {
+                        self.speed_units.convert(1.)
+                    } else {
+                        1.
+                    };
+                    let mut values = vec![
+                        summary.min,
+                        summary.max,
+                        summary.max_abs,
+                        summary.mean,
+                        summary.std_dev,
+                    ];
+                    if let Some(percentiles) = summary.percentiles {
+                        values.extend([percentiles.p5, percentiles.p50, percentiles.p95]);
+                    }
+                    std::iter::once(summary.quantity.name().to_string())
+                        .chain(
+                            values
+                                .into_iter()
+                                .map(|value| format!("{:.6}", value * scale)),
+                        )
+                        .collect()
+                })
+                .collect(),
+        ));
+
+        #[cfg(feature = "plot")]
+        {
+            let svg = crate::plot::Plot::GroundTrack.to_svg(points)?;
+            blocks.push(Block::Heading("Ground track".to_string()));
+            blocks.push(Block::Image("ground track".to_string(), svg));
+        }
+        Ok(blocks)
+    }
+}
+
+fn row<T: ToString>(name: &str, value: T) -> Vec<String> {
+    vec![name.to_string(), value.to_string()]
+}
+
+fn markdown(title: &str, blocks: &[Block]) -> String {
+    let mut document = format!("# {}\n", title);
+    for block in blocks {
+        document.push('\n');
+        match block {
+            Block::Heading(heading) => {
+                let _ = writeln!(document, "## {}", heading);
+            }
+            Block::Paragraph(paragraph) => {
+                let _ = writeln!(document, "{}", paragraph);
+            }
+            Block::Table(header, rows) => {
+                let _ = writeln!(document, "| {} |", header.join(" | "));
+                let _ = writeln!(document, "|{}", "---|".repeat(header.len()));
+                for row in rows {
+                    let _ = writeln!(document, "| {} |", row.join(" | "));
+                }
+            }
+            #[cfg(feature = "plot")]
+            Block::Image(alt, svg) => {
+                let _ = writeln!(
+                    document,
+                    "![{}](data:image/svg+xml,{})",
+                    alt,
+                    percent_encode(svg)
+                );
+            }
+        }
+    }
+    document
+}
+
+fn html(title: &str, blocks: &[Block]) -> String {
+    let title = escape(title);
+    let mut document = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n<h1>{}</h1>\n",
+        title, title
+    );
+    for block in blocks {
+        match block {
+            Block::Heading(heading) => {
+                let _ = writeln!(document, "<h2>{}</h2>", escape(heading));
+            }
+            Block::Paragraph(paragraph) => {
+                let _ = writeln!(document, "<p>{}</p>", escape(paragraph));
+            }
+            Block::Table(header, rows) => {
+                document.push_str("<table>\n<tr>");
+                for cell in header {
+                    let _ = write!(document, "<th>{}</th>", escape(cell));
+                }
+                document.push_str("</tr>\n");
+                for row in rows {
+                    document.push_str("<tr>");
+                    for cell in row {
+                        let _ = write!(document, "<td>{}</td>", escape(cell));
+                    }
+                    document.push_str("</tr>\n");
+                }
+                document.push_str("</table>\n");
+            }
+            #[cfg(feature = "plot")]
+            Block::Image(_, svg) => {
+                let _ = writeln!(document, "{}", svg);
+            }
+        }
+    }
+    document.push_str("</body>\n</html>\n");
+    document
+}
+
+/// Percent-encodes everything but unreserved characters, for a data URI.
+#[cfg(feature = "plot")]
+fn percent_encode(s: &str) -> String {
+    let mut encoded = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            let _ = write!(encoded, "%{:02X}", byte);
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Builder, Format};
+    use crate::{Error, Point, SpeedUnit, Trajectory};
+
+    fn trajectory() -> Trajectory {
+        [0., 1., 2., 5., 6.]
+            .into_iter()
+            .map(|time| Point {
+                time,
+                roll: 0.1,
+                x_velocity: 1.,
+                ..Default::default()
+            })
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    #[test]
+    fn markdown() {
+        let report = Builder::new().to_string(&trajectory()).unwrap();
+        assert!(report.starts_with("# Trajectory report\n"));
+        assert!(report.contains("| dropped epochs | 2 |"));
+        assert!(report.contains("| 2 | 5 | 3.000000 |"));
+        assert!(report.contains("| roll | 5.729578 |"));
+        assert!(report.contains("| speed | 1.000000 |"));
+    }
+
+    #[test]
+    fn speed_units() {
+        let report = Builder::new()
+            .speed_units(SpeedUnit::Knots)
+            .to_string(&trajectory())
+            .unwrap();
+        assert!(report.contains("velocities and speeds are in kn"));
+        assert!(report.contains("| speed | 1.943844 |"));
+    }
+
+    #[test]
+    fn html() {
+        let report = Builder::new()
+            .title("<Flight>")
+            .format(Format::Html)
+            .gap(5.)
+            .to_string(&trajectory())
+            .unwrap();
+        assert!(report.contains("<h1>&lt;Flight&gt;</h1>"));
+        assert!(report.contains("<p>No points are more than 5 s apart.</p>"));
+        assert!(report.ends_with("</html>\n"));
+    }
+
+    #[test]
+    fn empty() {
+        assert!(matches!(
+            Builder::new()
+                .to_string(&Trajectory::from(Vec::new()))
+                .unwrap_err(),
+            Error::NoPoints
+        ));
+    }
+
+    #[test]
+    fn parse_format() {
+        assert_eq!("md".parse::<Format>().unwrap(), Format::Markdown);
+        assert_eq!("html".parse::<Format>().unwrap(), Format::Html);
+        assert!("pdf".parse::<Format>().is_err());
+    }
+}
@@ -0,0 +1,205 @@
+use crate::{interpolator::lerp, Error, Result, Trajectory};
+use std::str::FromStr;
+
+/// How [Trajectory::sample] picks the point for each sample time.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::SampleMethod;
+///
+/// let method: SampleMethod = "nearest".parse().unwrap();
+/// assert_eq!(method, SampleMethod::Nearest);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SampleMethod {
+    /// Use the record nearest to the sample time, unchanged.
+    Nearest,
+
+    /// Interpolate a point at the sample time.
+    #[default]
+    Interpolate,
+}
+
+impl FromStr for SampleMethod {
+    type Err = Error;
+
+    /// Parses "nearest" or "interpolate".
+    fn from_str(s: &str) -> Result<SampleMethod> {
+        match s {
+            "nearest" => Ok(SampleMethod::Nearest),
+            "interpolate" => Ok(SampleMethod::Interpolate),
+            _ => Err(Error::UnknownSampleMethod(s.to_string())),
+        }
+    }
+}
+
+impl Trajectory {
+    /// Returns a new trajectory interpolated at a fixed rate.
+    ///
+    /// Output times are whole multiples of `1 / rate_hz` seconds, so
+    /// trajectories resampled at the same rate share times. No points are
+    /// interpolated between two input points more than `max_gap` seconds
+    /// apart; use [f64::INFINITY] to interpolate across every gap. The
+    /// points must be sorted by time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate_hz` isn't positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Point, Trajectory};
+    ///
+    /// let trajectory: Trajectory = [0.05, 0.3, 2., 2.25]
+    ///     .into_iter()
+    ///     .map(|time| Point { time, altitude: time, ..Default::default() })
+    ///     .collect::<Vec<_>>()
+    ///     .into();
+    /// let resampled = trajectory.resample(10., 1.);
+    /// let times: Vec<f64> = resampled.points().iter().map(|point| point.time).collect();
+    /// assert_eq!(times, [0.1, 0.2, 0.3, 2., 2.1, 2.2]);
+    /// ```
+    pub fn resample(&self, rate_hz: f64, max_gap: f64) -> Trajectory {
+        assert!(rate_hz > 0., "rate must be positive");
+        let points = self.points();
+        let (Some(first), Some(last)) = (points.first(), points.last()) else {
+            return Trajectory::default();
+        };
+        let mut resampled = Vec::new();
+        let mut index = (first.time * rate_hz).ceil() as i64;
+        let mut after = 0;
+        loop {
+            let time = index as f64 / rate_hz;
+            if time > last.time {
+                break;
+            }
+            while points[after].time < time {
+                after += 1;
+            }
+            if points[after].time == time {
+                resampled.push(points[after]);
+            } else if after > 0 {
+                let (before, after) = (&points[after - 1], &points[after]);
+                if after.time - before.time <= max_gap {
+                    resampled.push(lerp(before, after, time));
+                }
+            }
+            index += 1;
+        }
+        resampled.into()
+    }
+
+    /// Returns one point every `every` seconds, independent of the input rate.
+    ///
+    /// Sample times are whole multiples of `every`, like
+    /// [Trajectory::resample]. With [SampleMethod::Nearest], each sample is
+    /// the record nearest to its time, and a record nearest to several
+    /// sample times is only used once. With [SampleMethod::Interpolate],
+    /// this is [Trajectory::resample] at `1 / every` hertz, interpolating
+    /// across every gap. The points must be sorted by time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `every` isn't positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Point, SampleMethod, Trajectory};
+    ///
+    /// let trajectory: Trajectory = [0.05, 0.3, 0.9, 1.2, 2.05]
+    ///     .into_iter()
+    ///     .map(|time| Point { time, ..Default::default() })
+    ///     .collect::<Vec<_>>()
+    ///     .into();
+    /// let sampled = trajectory.sample(1., SampleMethod::Nearest);
+    /// let times: Vec<f64> = sampled.points().iter().map(|point| point.time).collect();
+    /// assert_eq!(times, [0.9, 2.05]);
+    /// ```
+    pub fn sample(&self, every: f64, method: SampleMethod) -> Trajectory {
+        assert!(every > 0., "sample interval must be positive");
+        if method == SampleMethod::Interpolate {
+            return self.resample(1. / every, f64::INFINITY);
+        }
+        let points = self.points();
+        let (Some(first), Some(last)) = (points.first(), points.last()) else {
+            return Trajectory::default();
+        };
+        let mut sampled = Vec::new();
+        let mut previous = None;
+        let mut index = (first.time / every).ceil() as i64;
+        let mut after = 0;
+        loop {
+            let time = index as f64 * every;
+            if time > last.time {
+                break;
+            }
+            while points[after].time < time {
+                after += 1;
+            }
+            let nearest = if after > 0 && time - points[after - 1].time < points[after].time - time
+            {
+                after - 1
+            } else {
+                after
+            };
+            if previous != Some(nearest) {
+                sampled.push(points[nearest]);
+                previous = Some(nearest);
+            }
+            index += 1;
+        }
+        sampled.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Point, SampleMethod, Trajectory};
+
+    #[test]
+    fn interpolates() {
+        let trajectory: Trajectory = [1., 2.]
+            .into_iter()
+            .map(|time| Point {
+                time,
+                altitude: time * 10.,
+                ..Default::default()
+            })
+            .collect::<Vec<_>>()
+            .into();
+        let resampled = trajectory.resample(4., f64::INFINITY);
+        let altitudes: Vec<f64> = resampled
+            .points()
+            .iter()
+            .map(|point| point.altitude)
+            .collect();
+        assert_eq!(altitudes, [10., 12.5, 15., 17.5, 20.]);
+        assert!(Trajectory::default().resample(1., f64::INFINITY).is_empty());
+    }
+
+    #[test]
+    fn nearest() {
+        let trajectory: Trajectory = [0., 0.4, 0.45, 3.]
+            .into_iter()
+            .map(|time| Point {
+                time,
+                ..Default::default()
+            })
+            .collect::<Vec<_>>()
+            .into();
+        let times: Vec<f64> = trajectory
+            .sample(0.5, SampleMethod::Nearest)
+            .points()
+            .iter()
+            .map(|point| point.time)
+            .collect();
+        assert_eq!(times, [0., 0.45, 3.]);
+        assert_eq!(
+            trajectory.sample(1., SampleMethod::Interpolate).len(),
+            trajectory.resample(1., f64::INFINITY).len()
+        );
+    }
+}
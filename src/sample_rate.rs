@@ -0,0 +1,242 @@
+use crate::{Point, TimeWindow, Trajectory};
+
+/// How often a trajectory's points were sampled.
+///
+/// Created by [Trajectory::sample_rate].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SampleRate {
+    /// The rate the system was configured at, in hertz.
+    ///
+    /// This is the inverse of the median time between points, rounded to
+    /// the nearest hertz if it's at least one hertz.
+    pub nominal: f64,
+
+    /// The average rate over the whole trajectory, in hertz.
+    ///
+    /// This is less than the nominal rate if points were dropped.
+    pub detected: f64,
+
+    /// The root-mean-square difference between each time step and the nominal time step, in seconds.
+    pub jitter_rms: f64,
+
+    /// The largest difference between a time step and the nominal time step, in seconds.
+    ///
+    /// Gaps and times that go backwards count as jitter.
+    pub jitter_max: f64,
+}
+
+/// A histogram of the times between consecutive points, in nominal time steps.
+///
+/// Created by [Trajectory::interval_histogram].
+#[derive(Clone, Debug, PartialEq)]
+pub struct IntervalHistogram {
+    /// The nominal time step, in seconds; see [SampleRate::nominal].
+    pub step: f64,
+
+    /// The intervals by their length in steps, from one step up.
+    ///
+    /// The last bucket also counts every longer interval.
+    pub buckets: Vec<IntervalBucket>,
+
+    /// The number of intervals where time repeats or goes backwards.
+    pub non_positive: u64,
+}
+
+/// One bucket of an [IntervalHistogram].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IntervalBucket {
+    /// The interval length, in nominal steps, rounded to the nearest step.
+    pub steps: u64,
+
+    /// The number of intervals.
+    pub intervals: u64,
+
+    /// The number of epochs missing from these intervals, one fewer than the steps of each.
+    pub dropped: u64,
+}
+
+impl IntervalHistogram {
+    /// Returns the total number of missing epochs.
+    pub fn dropped(&self) -> u64 {
+        self.buckets.iter().map(|bucket| bucket.dropped).sum()
+    }
+}
+
+impl Trajectory {
+    /// Returns a histogram of the times between consecutive points, for checking logging health.
+    ///
+    /// Each interval is divided by the nominal time step and rounded, with
+    /// a minimum of one step, so an interval of three steps means two
+    /// epochs were dropped. There are `buckets` buckets, and the last one
+    /// also counts every longer interval. Returns `None` if the sample rate
+    /// can't be detected; see [Trajectory::sample_rate].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buckets` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Point, Trajectory};
+    ///
+    /// let trajectory: Trajectory = [0., 1., 2., 4., 5., 15.]
+    ///     .into_iter()
+    ///     .map(|time| Point { time, ..Default::default() })
+    ///     .collect::<Vec<_>>()
+    ///     .into();
+    /// let histogram = trajectory.interval_histogram(3).unwrap();
+    /// assert_eq!(histogram.step, 1.);
+    /// let intervals: Vec<u64> = histogram.buckets.iter().map(|bucket| bucket.intervals).collect();
+    /// assert_eq!(intervals, [3, 1, 1]);
+    /// assert_eq!(histogram.dropped(), 10);
+    /// ```
+    pub fn interval_histogram(&self, buckets: usize) -> Option<IntervalHistogram> {
+        assert!(buckets > 0, "there must be at least one bucket");
+        let step = 1. / self.sample_rate()?.nominal;
+        let mut histogram = IntervalHistogram {
+            step,
+            buckets: (1..=buckets as u64)
+                .map(|steps| IntervalBucket {
+                    steps,
+                    intervals: 0,
+                    dropped: 0,
+                })
+                .collect(),
+            non_positive: 0,
+        };
+        for pair in self.points().windows(2) {
+            let interval = pair[1].time - pair[0].time;
+            if interval > 0. {
+                let steps = ((interval / step).round() as u64).max(1);
+                let bucket = &mut histogram.buckets[(steps as usize).min(buckets) - 1];
+                bucket.intervals += 1;
+                bucket.dropped += steps - 1;
+            } else {
+                histogram.non_positive += 1;
+            }
+        }
+        Some(histogram)
+    }
+
+    /// Returns the time windows between consecutive points that are more than `min_gap` seconds apart.
+    ///
+    /// Each window starts at the point before the gap and stops at the point
+    /// after it. The points must be sorted by time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Point, TimeWindow, Trajectory};
+    ///
+    /// let trajectory: Trajectory = [0., 1., 2., 5., 6.]
+    ///     .into_iter()
+    ///     .map(|time| Point { time, ..Default::default() })
+    ///     .collect::<Vec<_>>()
+    ///     .into();
+    /// assert_eq!(trajectory.gaps(1.5), [TimeWindow::new(2., 5.)]);
+    /// ```
+    pub fn gaps(&self, min_gap: f64) -> Vec<TimeWindow> {
+        self.points()
+            .windows(2)
+            .filter(|pair| pair[1].time - pair[0].time > min_gap)
+            .map(|pair| TimeWindow::new(pair[0].time, pair[1].time))
+            .collect()
+    }
+
+    /// Detects this trajectory's sample rate and timing jitter.
+    ///
+    /// Returns `None` if there are fewer than two points, or time never increases.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Point, Trajectory};
+    ///
+    /// let trajectory: Trajectory = [0., 0.01, 0.02, 0.04]
+    ///     .into_iter()
+    ///     .map(|time| Point { time, ..Default::default() })
+    ///     .collect::<Vec<_>>()
+    ///     .into();
+    /// let sample_rate = trajectory.sample_rate().unwrap();
+    /// assert_eq!(sample_rate.nominal, 100.);
+    /// assert!((sample_rate.detected - 75.).abs() < 1e-9);
+    /// ```
+    pub fn sample_rate(&self) -> Option<SampleRate> {
+        let points = self.points();
+        let median = median_interval(points)?;
+        let rate = 1. / median;
+        let nominal = if rate >= 1. { rate.round() } else { rate };
+        let step = 1. / nominal;
+        let (first, last) = (points.first()?, points.last()?);
+        let detected = (points.len() - 1) as f64 / (last.time - first.time);
+        let (sum_of_squares, jitter_max) = points
+            .windows(2)
+            .map(|pair| (pair[1].time - pair[0].time - step).abs())
+            .fold((0., 0f64), |(sum_of_squares, max), jitter| {
+                (sum_of_squares + jitter * jitter, max.max(jitter))
+            });
+        Some(SampleRate {
+            nominal,
+            detected,
+            jitter_rms: (sum_of_squares / (points.len() - 1) as f64).sqrt(),
+            jitter_max,
+        })
+    }
+}
+
+/// Returns the median of the positive times between consecutive points.
+pub(crate) fn median_interval(points: &[Point]) -> Option<f64> {
+    let mut intervals: Vec<f64> = points
+        .windows(2)
+        .map(|pair| pair[1].time - pair[0].time)
+        .filter(|interval| *interval > 0.)
+        .collect();
+    intervals.sort_by(f64::total_cmp);
+    intervals.get(intervals.len() / 2).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Point, Trajectory};
+
+    fn trajectory(times: &[f64]) -> Trajectory {
+        times
+            .iter()
+            .map(|&time| Point {
+                time,
+                ..Default::default()
+            })
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    #[test]
+    fn jitter() {
+        let sample_rate = trajectory(&[0., 0.5, 1.25, 1.75, 2.25])
+            .sample_rate()
+            .unwrap();
+        assert_eq!(sample_rate.nominal, 2.);
+        assert_eq!(sample_rate.jitter_max, 0.25);
+        assert_eq!(sample_rate.jitter_rms, 0.125);
+    }
+
+    #[test]
+    fn histogram() {
+        let histogram = trajectory(&[0., 0.5, 1., 1., 2.6, 3.])
+            .interval_histogram(4)
+            .unwrap();
+        assert_eq!(histogram.step, 0.5);
+        assert_eq!(histogram.non_positive, 1);
+        assert_eq!(histogram.buckets[0].intervals, 3);
+        assert_eq!(histogram.buckets[2].intervals, 1);
+        assert_eq!(histogram.buckets[2].dropped, 2);
+        assert!(trajectory(&[0.]).interval_histogram(1).is_none());
+    }
+
+    #[test]
+    fn too_few_points() {
+        assert!(trajectory(&[0.]).sample_rate().is_none());
+        assert!(trajectory(&[1., 1.]).sample_rate().is_none());
+    }
+}
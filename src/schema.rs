@@ -0,0 +1,240 @@
+use crate::{Endianness, Field, Point};
+use alloc::vec::Vec;
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+
+/// One column of a [RecordSchema].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Column {
+    /// A field stored as an eight-byte float.
+    F64(Field),
+
+    /// A field stored as a four-byte float.
+    F32(Field),
+
+    /// Bytes that don't map onto a [Point] field, e.g. GNSS status columns.
+    ///
+    /// Skipped when reading and written as zeros.
+    Skip(usize),
+}
+
+impl Column {
+    /// Returns the size of this column in bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Column, Field};
+    ///
+    /// assert_eq!(Column::F64(Field::Time).size(), 8);
+    /// assert_eq!(Column::Skip(3).size(), 3);
+    /// ```
+    pub fn size(&self) -> usize {
+        match self {
+            Column::F64(_) => 8,
+            Column::F32(_) => 4,
+            Column::Skip(size) => *size,
+        }
+    }
+}
+
+/// The layout of one record, for SBET-like files with nonstandard fields.
+///
+/// Fields that aren't in the schema are zero when read.
+///
+/// # Examples
+///
+/// Standard records followed by two extra eight-byte status columns:
+///
+/// ```
+/// use sbet::{Column, Field, RecordSchema};
+///
+/// let mut columns: Vec<_> = Field::ALL.into_iter().map(Column::F64).collect();
+/// columns.push(Column::Skip(16));
+/// let schema = RecordSchema::new(columns);
+/// assert_eq!(schema.size(), 152);
+///
+/// # #[cfg(feature = "std")]
+/// let reader = sbet::Reader::with_schema(std::io::empty(), schema);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RecordSchema {
+    columns: Vec<Column>,
+}
+
+impl RecordSchema {
+    /// Creates a schema from its columns, in record order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Column, Field, RecordSchema};
+    ///
+    /// let schema = RecordSchema::new(vec![
+    ///     Column::F64(Field::Time),
+    ///     Column::F64(Field::Latitude),
+    ///     Column::F64(Field::Longitude),
+    ///     Column::F32(Field::Altitude),
+    /// ]);
+    /// assert_eq!(schema.size(), 28);
+    /// ```
+    pub fn new(columns: Vec<Column>) -> RecordSchema {
+        RecordSchema { columns }
+    }
+
+    /// Returns the standard SBET schema, seventeen eight-byte fields in [Field::ALL] order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::RecordSchema;
+    ///
+    /// assert_eq!(RecordSchema::standard().size(), sbet::RECORD_SIZE);
+    /// ```
+    pub fn standard() -> RecordSchema {
+        RecordSchema::new(Field::ALL.into_iter().map(Column::F64).collect())
+    }
+
+    /// Returns this schema's columns.
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    /// Returns the size of one record in bytes.
+    pub fn size(&self) -> usize {
+        self.columns.iter().map(Column::size).sum()
+    }
+
+    /// Decodes a point from one record.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is shorter than [RecordSchema::size].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Column, Endianness, Field, RecordSchema};
+    ///
+    /// let schema = RecordSchema::new(vec![Column::Skip(4), Column::F32(Field::Altitude)]);
+    /// let mut bytes = vec![0; 4];
+    /// bytes.extend_from_slice(&42f32.to_le_bytes());
+    /// let point = schema.decode(&bytes, Endianness::Little);
+    /// assert_eq!(point.altitude, 42.);
+    /// ```
+    pub fn decode(&self, bytes: &[u8], endianness: Endianness) -> Point {
+        let mut point = Point::default();
+        let mut offset = 0;
+        for column in &self.columns {
+            let bytes = &bytes[offset..offset + column.size()];
+            match (*column, endianness) {
+                (Column::F64(field), Endianness::Little) => {
+                    point.set(field, LittleEndian::read_f64(bytes))
+                }
+                (Column::F64(field), Endianness::Big) => {
+                    point.set(field, BigEndian::read_f64(bytes))
+                }
+                (Column::F32(field), Endianness::Little) => {
+                    point.set(field, LittleEndian::read_f32(bytes).into())
+                }
+                (Column::F32(field), Endianness::Big) => {
+                    point.set(field, BigEndian::read_f32(bytes).into())
+                }
+                (Column::Skip(_), _) => {}
+            }
+            offset += column.size();
+        }
+        point
+    }
+
+    /// Encodes a point as one record, overwriting the start of `bytes`.
+    ///
+    /// Four-byte fields lose precision.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is shorter than [RecordSchema::size].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Endianness, Point, RecordSchema};
+    ///
+    /// let schema = RecordSchema::standard();
+    /// let point = Point { time: 1., ..Default::default() };
+    /// let mut bytes = vec![0; schema.size()];
+    /// schema.encode(&point, Endianness::Little, &mut bytes);
+    /// assert_eq!(bytes, point.to_bytes());
+    /// ```
+    pub fn encode(&self, point: &Point, endianness: Endianness, bytes: &mut [u8]) {
+        let mut offset = 0;
+        for column in &self.columns {
+            let bytes = &mut bytes[offset..offset + column.size()];
+            match (*column, endianness) {
+                (Column::F64(field), Endianness::Little) => {
+                    LittleEndian::write_f64(bytes, point.get(field))
+                }
+                (Column::F64(field), Endianness::Big) => {
+                    BigEndian::write_f64(bytes, point.get(field))
+                }
+                (Column::F32(field), Endianness::Little) => {
+                    LittleEndian::write_f32(bytes, point.get(field) as f32)
+                }
+                (Column::F32(field), Endianness::Big) => {
+                    BigEndian::write_f32(bytes, point.get(field) as f32)
+                }
+                (Column::Skip(_), _) => bytes.fill(0),
+            }
+            offset += column.size();
+        }
+    }
+
+    /// Returns true if this is the standard SBET layout.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Column, RecordSchema};
+    ///
+    /// assert!(RecordSchema::standard().is_standard());
+    /// assert!(!RecordSchema::new(vec![Column::Skip(8)]).is_standard());
+    /// ```
+    pub fn is_standard(&self) -> bool {
+        self.columns.len() == Field::ALL.len()
+            && self
+                .columns
+                .iter()
+                .zip(Field::ALL)
+                .all(|(column, field)| *column == Column::F64(field))
+    }
+}
+
+impl Default for RecordSchema {
+    fn default() -> RecordSchema {
+        RecordSchema::standard()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Column, RecordSchema};
+    use crate::{Endianness, Field, Point};
+
+    #[test]
+    fn extra_columns() {
+        let mut columns: Vec<_> = Field::ALL.into_iter().map(Column::F64).collect();
+        columns.insert(4, Column::Skip(8));
+        let schema = RecordSchema::new(columns);
+
+        let point = Point {
+            time: 1.,
+            altitude: 2.,
+            x_velocity: 3.,
+            ..Default::default()
+        };
+        let mut bytes = vec![0xff; schema.size()];
+        schema.encode(&point, Endianness::Big, &mut bytes);
+        assert_eq!(bytes[32..40], [0; 8]);
+        assert_eq!(bytes[40..48], 3f64.to_be_bytes());
+        assert_eq!(schema.decode(&bytes, Endianness::Big), point);
+    }
+}
@@ -0,0 +1,103 @@
+use crate::{Field, Trajectory};
+use std::f64::consts::{PI, TAU};
+
+impl Trajectory {
+    /// Returns a new trajectory with these fields replaced by their centered moving average.
+    ///
+    /// Each value is averaged with the `window / 2` points on either side,
+    /// and fewer near the ends. Longitude and attitude angles are averaged
+    /// across their wrap-around, so a yaw near ±π doesn't average to zero.
+    /// Other fields are unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window` is even.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Field, Point, Trajectory};
+    ///
+    /// let trajectory: Trajectory = [0., 3., 0.]
+    ///     .into_iter()
+    ///     .map(|x_acceleration| Point { x_acceleration, ..Default::default() })
+    ///     .collect::<Vec<_>>()
+    ///     .into();
+    /// let smoothed = trajectory.smooth(&[Field::XAcceleration], 3);
+    /// assert_eq!(smoothed.points()[1].x_acceleration, 1.);
+    /// ```
+    pub fn smooth(&self, fields: &[Field], window: usize) -> Trajectory {
+        assert!(window % 2 == 1, "window must be odd");
+        let half = window / 2;
+        let points = self.points();
+        let mut smoothed = points.to_vec();
+        for (i, point) in smoothed.iter_mut().enumerate() {
+            let neighbors = &points[i.saturating_sub(half)..(i + half + 1).min(points.len())];
+            for &field in fields {
+                let center = points[i].get(field);
+                let sum: f64 = neighbors
+                    .iter()
+                    .map(|neighbor| {
+                        let offset = neighbor.get(field) - center;
+                        if wraps(field) {
+                            wrap(offset)
+                        } else {
+                            offset
+                        }
+                    })
+                    .sum();
+                let value = center + sum / neighbors.len() as f64;
+                point.set(field, if wraps(field) { wrap(value) } else { value });
+            }
+        }
+        smoothed.into()
+    }
+}
+
+/// Returns true if the field is an angle that wraps around at ±π.
+pub(crate) fn wraps(field: Field) -> bool {
+    matches!(
+        field,
+        Field::Longitude | Field::Roll | Field::Pitch | Field::Yaw | Field::WanderAngle
+    )
+}
+
+/// Wraps an angle into `[-π, π)`.
+pub(crate) fn wrap(angle: f64) -> f64 {
+    (angle + PI).rem_euclid(TAU) - PI
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Field, Point, Trajectory};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn yaw_wraps() {
+        let trajectory: Trajectory = [PI - 0.1, -PI + 0.1, PI - 0.1]
+            .into_iter()
+            .map(|yaw| Point {
+                yaw,
+                ..Default::default()
+            })
+            .collect::<Vec<Point>>()
+            .into();
+        let smoothed = trajectory.smooth(&[Field::Yaw], 3);
+        assert!((smoothed.points()[1].yaw - (PI - 0.1 / 3.)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn ends_use_fewer_points() {
+        let trajectory: Trajectory = [0., 3., 6.]
+            .into_iter()
+            .map(|altitude| Point {
+                altitude,
+                ..Default::default()
+            })
+            .collect::<Vec<Point>>()
+            .into();
+        let smoothed = trajectory.smooth(&[Field::Altitude], 3);
+        assert_eq!(smoothed.points()[0].altitude, 1.5);
+        assert_eq!(smoothed.points()[2].altitude, 4.5);
+    }
+}
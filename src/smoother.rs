@@ -0,0 +1,374 @@
+//! Kalman filtering and Rauch–Tung–Striebel smoothing of positions and velocities, behind the `smoother` feature.
+//!
+//! Each east, north, and up axis of a local tangent frame is modeled as
+//! constant velocity driven by white-noise acceleration. Positions and
+//! velocities are filtered forward in time, then smoothed backward, and
+//! short gaps are filled at the nominal sample rate. Other fields are
+//! unchanged, and are linearly interpolated in filled gaps.
+//!
+//! # Examples
+//!
+//! ```
+//! use sbet::smoother::Smoother;
+//! use sbet::Trajectory;
+//!
+//! let trajectory = Trajectory::from_path("data/2-points.sbet").unwrap();
+//! let points = Smoother::new().smooth(trajectory.points()).unwrap();
+//! assert_eq!(points.len(), 2);
+//! ```
+
+use crate::{
+    geodesy::{ecef_to_enu, enu_to_ecef, WGS84_A, WGS84_E2},
+    interpolator::lerp,
+    sample_rate::median_interval,
+    Error, Point, Result,
+};
+
+type Matrix = [[f64; 2]; 2];
+
+/// Smooths the positions and velocities of a slice of points.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Smoother {
+    position_noise: f64,
+    velocity_noise: f64,
+    acceleration_noise: f64,
+    max_gap: f64,
+}
+
+impl Default for Smoother {
+    fn default() -> Smoother {
+        Smoother::new()
+    }
+}
+
+impl Smoother {
+    /// Creates a smoother for five-centimeter positions, one-centimeter-per-second
+    /// velocities, and one-meter-per-second-squared accelerations, that fills
+    /// gaps up to one second.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::smoother::Smoother;
+    ///
+    /// let smoother = Smoother::new();
+    /// ```
+    pub fn new() -> Smoother {
+        Smoother {
+            position_noise: 0.05,
+            velocity_noise: 0.01,
+            acceleration_noise: 1.,
+            max_gap: 1.,
+        }
+    }
+
+    /// Sets the standard deviation of the measured positions, in meters.
+    pub fn position_noise(mut self, meters: f64) -> Smoother {
+        self.position_noise = meters;
+        self
+    }
+
+    /// Sets the standard deviation of the measured velocities, in meters per second.
+    pub fn velocity_noise(mut self, meters_per_second: f64) -> Smoother {
+        self.velocity_noise = meters_per_second;
+        self
+    }
+
+    /// Sets the spectral density of the unmodeled acceleration, in meters per second squared per root hertz.
+    ///
+    /// Larger values follow the measurements more closely.
+    pub fn acceleration_noise(mut self, acceleration_noise: f64) -> Smoother {
+        self.acceleration_noise = acceleration_noise;
+        self
+    }
+
+    /// Fills gaps up to this many seconds with points at the nominal sample rate.
+    ///
+    /// Use zero to never fill gaps.
+    pub fn max_gap(mut self, seconds: f64) -> Smoother {
+        self.max_gap = seconds;
+        self
+    }
+
+    /// Returns the smoothed points, including any points that fill gaps.
+    ///
+    /// The points must be sorted by time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are no points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::smoother::Smoother;
+    /// use sbet::Point;
+    ///
+    /// let points: Vec<Point> = [0., 1., 2., 4.]
+    ///     .into_iter()
+    ///     .map(|time| Point { time, ..Default::default() })
+    ///     .collect();
+    /// let smoothed = Smoother::new().max_gap(2.).smooth(&points).unwrap();
+    /// assert_eq!(smoothed.len(), 5);
+    /// assert_eq!(smoothed[3].time, 3.);
+    /// ```
+    pub fn smooth(&self, points: &[Point]) -> Result<Vec<Point>> {
+        let origin = *points.first().ok_or(Error::NoPoints)?;
+        let step = median_interval(points).unwrap_or(f64::INFINITY);
+        // Each epoch is a measured point, or a time between two points.
+        let mut epochs = Vec::with_capacity(points.len());
+        for (i, point) in points.iter().enumerate() {
+            epochs.push((point.time, i, true));
+            if let Some(next) = points.get(i + 1) {
+                if next.time - point.time <= self.max_gap {
+                    let mut time = point.time + step;
+                    while time < next.time - step / 2. {
+                        epochs.push((time, i, false));
+                        time += step;
+                    }
+                }
+            }
+        }
+        let enu: Vec<_> = points.iter().map(|point| point.to_enu(&origin)).collect();
+        let axes: [Vec<[f64; 2]>; 3] = [
+            enu.iter()
+                .map(|enu| [enu.east, enu.east_velocity])
+                .collect(),
+            enu.iter()
+                .map(|enu| [enu.north, enu.north_velocity])
+                .collect(),
+            enu.iter().map(|enu| [enu.up, enu.up_velocity]).collect(),
+        ];
+        let smoothed = axes.map(|measurements| {
+            self.smooth_axis(
+                &epochs
+                    .iter()
+                    .map(|&(time, i, measured)| (time, measured.then(|| measurements[i])))
+                    .collect::<Vec<_>>(),
+            )
+        });
+        let origin_ecef = origin.to_ecef();
+        Ok(epochs
+            .iter()
+            .enumerate()
+            .map(|(k, &(time, i, measured))| {
+                let mut point = if measured {
+                    points[i]
+                } else {
+                    lerp(&points[i], &points[i + 1], time)
+                };
+                let [x, y, z] = enu_to_ecef(
+                    [smoothed[0][k][0], smoothed[1][k][0], smoothed[2][k][0]],
+                    &origin,
+                );
+                let [latitude, longitude, altitude] =
+                    ecef_to_geodetic([origin_ecef.x + x, origin_ecef.y + y, origin_ecef.z + z]);
+                point.latitude = latitude;
+                point.longitude = longitude;
+                point.altitude = altitude;
+                let velocity = enu_to_ecef(
+                    [smoothed[0][k][1], smoothed[1][k][1], smoothed[2][k][1]],
+                    &origin,
+                );
+                let [east, north, up] = ecef_to_enu(velocity, &point);
+                let (sin_wander, cos_wander) = point.wander_angle.sin_cos();
+                point.x_velocity = north * cos_wander - east * sin_wander;
+                point.y_velocity = north * sin_wander + east * cos_wander;
+                point.z_velocity = -up;
+                point
+            })
+            .collect())
+    }
+
+    /// Filters and smooths one axis's position and velocity at each time, with optional measurements.
+    fn smooth_axis(&self, epochs: &[(f64, Option<[f64; 2]>)]) -> Vec<[f64; 2]> {
+        let r = [
+            [self.position_noise.powi(2), 0.],
+            [0., self.velocity_noise.powi(2)],
+        ];
+        let mut predicted: Vec<([f64; 2], Matrix)> = Vec::with_capacity(epochs.len());
+        let mut filtered: Vec<([f64; 2], Matrix)> = Vec::with_capacity(epochs.len());
+        for (k, &(time, measurement)) in epochs.iter().enumerate() {
+            let (x, p) = match filtered.last() {
+                None => (measurement.unwrap_or_default(), r),
+                Some(&(x, p)) => {
+                    let f = transition(time - epochs[k - 1].0);
+                    let q = self.process_noise(time - epochs[k - 1].0);
+                    (
+                        multiply_vector(&f, x),
+                        add(&multiply(&multiply(&f, &p), &transpose(&f)), &q),
+                    )
+                }
+            };
+            predicted.push((x, p));
+            let (x, p) = match measurement {
+                Some(z) if k > 0 => {
+                    // With the identity measurement model, the gain is P (P + R)⁻¹.
+                    let gain = multiply(&p, &invert(&add(&p, &r)));
+                    let innovation = [z[0] - x[0], z[1] - x[1]];
+                    let correction = multiply_vector(&gain, innovation);
+                    let identity_minus_gain = [
+                        [1. - gain[0][0], -gain[0][1]],
+                        [-gain[1][0], 1. - gain[1][1]],
+                    ];
+                    (
+                        [x[0] + correction[0], x[1] + correction[1]],
+                        multiply(&identity_minus_gain, &p),
+                    )
+                }
+                _ => (x, p),
+            };
+            filtered.push((x, p));
+        }
+        let mut smoothed: Vec<[f64; 2]> = filtered.iter().map(|&(x, _)| x).collect();
+        for k in (0..epochs.len().saturating_sub(1)).rev() {
+            let (x, p) = filtered[k];
+            let (x_predicted, p_predicted) = predicted[k + 1];
+            let f = transition(epochs[k + 1].0 - epochs[k].0);
+            let c = multiply(&multiply(&p, &transpose(&f)), &invert(&p_predicted));
+            let difference = [
+                smoothed[k + 1][0] - x_predicted[0],
+                smoothed[k + 1][1] - x_predicted[1],
+            ];
+            let correction = multiply_vector(&c, difference);
+            smoothed[k] = [x[0] + correction[0], x[1] + correction[1]];
+        }
+        smoothed
+    }
+
+    /// Returns the process noise covariance for white-noise acceleration over a time step.
+    fn process_noise(&self, dt: f64) -> Matrix {
+        let q = self.acceleration_noise.powi(2);
+        [
+            [q * dt.powi(3) / 3., q * dt.powi(2) / 2.],
+            [q * dt.powi(2) / 2., q * dt],
+        ]
+    }
+}
+
+/// Converts an ECEF position to latitude and longitude in radians, and ellipsoidal height.
+fn ecef_to_geodetic([x, y, z]: [f64; 3]) -> [f64; 3] {
+    let p = x.hypot(y);
+    let longitude = y.atan2(x);
+    let mut latitude = z.atan2(p * (1. - WGS84_E2));
+    let mut altitude = 0.;
+    for _ in 0..10 {
+        let sin_latitude = latitude.sin();
+        let n = WGS84_A / (1. - WGS84_E2 * sin_latitude * sin_latitude).sqrt();
+        altitude = p / latitude.cos() - n;
+        latitude = z.atan2(p * (1. - WGS84_E2 * n / (n + altitude)));
+    }
+    [latitude, longitude, altitude]
+}
+
+fn transition(dt: f64) -> Matrix {
+    [[1., dt], [0., 1.]]
+}
+
+fn add(a: &Matrix, b: &Matrix) -> Matrix {
+    [
+        [a[0][0] + b[0][0], a[0][1] + b[0][1]],
+        [a[1][0] + b[1][0], a[1][1] + b[1][1]],
+    ]
+}
+
+fn multiply(a: &Matrix, b: &Matrix) -> Matrix {
+    [
+        [
+            a[0][0] * b[0][0] + a[0][1] * b[1][0],
+            a[0][0] * b[0][1] + a[0][1] * b[1][1],
+        ],
+        [
+            a[1][0] * b[0][0] + a[1][1] * b[1][0],
+            a[1][0] * b[0][1] + a[1][1] * b[1][1],
+        ],
+    ]
+}
+
+fn multiply_vector(a: &Matrix, x: [f64; 2]) -> [f64; 2] {
+    [
+        a[0][0] * x[0] + a[0][1] * x[1],
+        a[1][0] * x[0] + a[1][1] * x[1],
+    ]
+}
+
+fn transpose(a: &Matrix) -> Matrix {
+    [[a[0][0], a[1][0]], [a[0][1], a[1][1]]]
+}
+
+fn invert(a: &Matrix) -> Matrix {
+    let determinant = a[0][0] * a[1][1] - a[0][1] * a[1][0];
+    [
+        [a[1][1] / determinant, -a[0][1] / determinant],
+        [-a[1][0] / determinant, a[0][0] / determinant],
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Smoother;
+    use crate::Point;
+
+    /// The meridional radius of curvature at latitude 0.5, plus the altitude, in meters.
+    const MERIDIAN_RADIUS: f64 = 6_351_090.;
+
+    /// Flying north at 50 m/s from latitude 0.5.
+    fn flight(times: &[f64]) -> Vec<Point> {
+        times
+            .iter()
+            .map(|&time| Point {
+                time,
+                latitude: 0.5 + 50. * time / MERIDIAN_RADIUS,
+                altitude: 1000.,
+                x_velocity: 50.,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ecef_round_trip() {
+        let point = Point {
+            latitude: 0.7,
+            longitude: -2.,
+            altitude: 1500.,
+            ..Default::default()
+        };
+        let ecef = point.to_ecef();
+        let [latitude, longitude, altitude] = super::ecef_to_geodetic([ecef.x, ecef.y, ecef.z]);
+        assert!((latitude - point.latitude).abs() < 1e-12);
+        assert!((longitude - point.longitude).abs() < 1e-12);
+        assert!((altitude - point.altitude).abs() < 1e-6);
+    }
+
+    #[test]
+    fn constant_velocity_is_unchanged() {
+        let points = flight(&[0., 0.1, 0.2, 0.3, 0.4]);
+        let smoothed = Smoother::new().smooth(&points).unwrap();
+        for (a, b) in points.iter().zip(&smoothed) {
+            assert!((a.altitude - b.altitude).abs() < 1e-3);
+            assert!((a.x_velocity - b.x_velocity).abs() < 1e-3);
+            assert!(b.y_velocity.abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn fills_gaps() {
+        let points = flight(&[0., 0.1, 0.2, 0.6, 0.7]);
+        let smoothed = Smoother::new().smooth(&points).unwrap();
+        assert_eq!(smoothed.len(), 8);
+        let filled = &smoothed[4];
+        assert!((filled.time - 0.4).abs() < 1e-9);
+        assert!((filled.x_velocity - 50.).abs() < 1e-2);
+        let smoothed = Smoother::new().max_gap(0.).smooth(&points).unwrap();
+        assert_eq!(smoothed.len(), 5);
+    }
+
+    #[test]
+    fn removes_noise() {
+        let mut points = flight(&(0..50).map(|i| f64::from(i) * 0.1).collect::<Vec<_>>());
+        points[25].altitude += 0.2;
+        let smoothed = Smoother::new().smooth(&points).unwrap();
+        assert!((smoothed[25].altitude - 1000.).abs() < 0.1);
+    }
+}
@@ -0,0 +1,393 @@
+//! Reading smrmsg accuracy files and filtering epochs by their accuracy.
+//!
+//! An smrmsg file holds the estimated RMS errors of an SBET's trajectory,
+//! usually at a lower rate than the SBET. Each record is ten little-endian
+//! `f64` values: the time, the north, east, and down position RMS in meters,
+//! the north, east, and down velocity RMS in meters per second, and the
+//! roll, pitch, and heading RMS in arc minutes.
+//!
+//! # Examples
+//!
+//! ```
+//! use sbet::smrmsg::{AccuracyFilter, Limits, Record};
+//! use sbet::Point;
+//!
+//! let records = [
+//!     Record { time: 0., ..Default::default() },
+//!     Record { time: 10., north_position: 4., ..Default::default() },
+//! ];
+//! let mut filter = AccuracyFilter::new(records.to_vec(), Limits::default());
+//! assert!(filter.accepts(&Point { time: 1., ..Default::default() }));
+//! assert!(!filter.accepts(&Point { time: 9., ..Default::default() }));
+//! ```
+
+use crate::{Error, Point, Result, TimeWindow};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::{
+    fs::File,
+    io::{BufReader, Read, Write},
+    path::Path,
+};
+
+/// The size of an smrmsg record, in bytes.
+pub const RECORD_SIZE: usize = 80;
+
+/// One smrmsg record: the estimated accuracy of the trajectory at a time.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Record {
+    /// The time, in the same convention as the SBET's times.
+    pub time: f64,
+
+    /// The north position RMS, in meters.
+    pub north_position: f64,
+
+    /// The east position RMS, in meters.
+    pub east_position: f64,
+
+    /// The down position RMS, in meters.
+    pub down_position: f64,
+
+    /// The north velocity RMS, in meters per second.
+    pub north_velocity: f64,
+
+    /// The east velocity RMS, in meters per second.
+    pub east_velocity: f64,
+
+    /// The down velocity RMS, in meters per second.
+    pub down_velocity: f64,
+
+    /// The roll RMS, in arc minutes.
+    pub roll: f64,
+
+    /// The pitch RMS, in arc minutes.
+    pub pitch: f64,
+
+    /// The heading RMS, in arc minutes.
+    pub heading: f64,
+}
+
+/// The largest RMS values an epoch may have and still be used.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::smrmsg::Limits;
+///
+/// let limits = Limits { heading: 5., ..Default::default() };
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Limits {
+    /// The largest horizontal position RMS, in meters.
+    pub horizontal_position: f64,
+
+    /// The largest down position RMS, in meters.
+    pub vertical_position: f64,
+
+    /// The largest north, east, or down velocity RMS, in meters per second.
+    pub velocity: f64,
+
+    /// The largest roll or pitch RMS, in arc minutes.
+    pub attitude: f64,
+
+    /// The largest heading RMS, in arc minutes.
+    pub heading: f64,
+}
+
+impl Default for Limits {
+    /// One meter of horizontal and vertical position RMS, and no other limits.
+    fn default() -> Limits {
+        Limits {
+            horizontal_position: 1.,
+            vertical_position: 1.,
+            velocity: f64::INFINITY,
+            attitude: f64::INFINITY,
+            heading: f64::INFINITY,
+        }
+    }
+}
+
+/// Decides whether each point is accurate enough, and keeps the time windows of those that aren't.
+///
+/// Each point's accuracy is interpolated from the smrmsg records on either
+/// side of its time. Points outside the records' times have unknown
+/// accuracy and are rejected. Consecutive rejected points are reported as
+/// one window, from the first rejected time to the last.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccuracyFilter {
+    records: Vec<Record>,
+    limits: Limits,
+    rejected: Vec<TimeWindow>,
+    rejecting: bool,
+}
+
+impl Record {
+    /// Returns the horizontal position RMS, in meters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::smrmsg::Record;
+    ///
+    /// let record = Record { north_position: 3., east_position: 4., ..Default::default() };
+    /// assert_eq!(record.horizontal_position(), 5.);
+    /// ```
+    pub fn horizontal_position(&self) -> f64 {
+        self.north_position.hypot(self.east_position)
+    }
+
+    /// Returns true if any of this record's RMS values exceed the limits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::smrmsg::{Limits, Record};
+    ///
+    /// let record = Record { down_position: 1.5, ..Default::default() };
+    /// assert!(record.exceeds(Limits::default()));
+    /// ```
+    pub fn exceeds(&self, limits: Limits) -> bool {
+        // NaN RMS values are unknown accuracy, so they exceed every limit.
+        let exceeds = |value: f64, limit: f64| value > limit || value.is_nan();
+        exceeds(self.horizontal_position(), limits.horizontal_position)
+            || exceeds(self.down_position, limits.vertical_position)
+            || exceeds(
+                self.north_velocity
+                    .max(self.east_velocity)
+                    .max(self.down_velocity),
+                limits.velocity,
+            )
+            || exceeds(self.roll.max(self.pitch), limits.attitude)
+            || exceeds(self.heading, limits.heading)
+    }
+
+    fn read_from<R: Read>(mut read: R) -> Result<Record> {
+        let mut values = [0.; RECORD_SIZE / 8];
+        read.read_f64_into::<LittleEndian>(&mut values)?;
+        Ok(Record::from_values(values))
+    }
+
+    fn from_values(values: [f64; RECORD_SIZE / 8]) -> Record {
+        let [time, north_position, east_position, down_position, north_velocity, east_velocity, down_velocity, roll, pitch, heading] =
+            values;
+        Record {
+            time,
+            north_position,
+            east_position,
+            down_position,
+            north_velocity,
+            east_velocity,
+            down_velocity,
+            roll,
+            pitch,
+            heading,
+        }
+    }
+
+    fn values(&self) -> [f64; RECORD_SIZE / 8] {
+        [
+            self.time,
+            self.north_position,
+            self.east_position,
+            self.down_position,
+            self.north_velocity,
+            self.east_velocity,
+            self.down_velocity,
+            self.roll,
+            self.pitch,
+            self.heading,
+        ]
+    }
+}
+
+impl AccuracyFilter {
+    /// Creates a filter from smrmsg records, in any order.
+    pub fn new(mut records: Vec<Record>, limits: Limits) -> AccuracyFilter {
+        records.sort_by(|a, b| a.time.total_cmp(&b.time));
+        AccuracyFilter {
+            records,
+            limits,
+            rejected: Vec::new(),
+            rejecting: false,
+        }
+    }
+
+    /// Returns the accuracy at a time, interpolated between the records on either side, or `None` if it's outside them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::smrmsg::{AccuracyFilter, Limits, Record};
+    ///
+    /// let records = vec![
+    ///     Record { time: 0., heading: 1., ..Default::default() },
+    ///     Record { time: 2., heading: 3., ..Default::default() },
+    /// ];
+    /// let filter = AccuracyFilter::new(records, Limits::default());
+    /// assert_eq!(filter.accuracy(1.).unwrap().heading, 2.);
+    /// assert!(filter.accuracy(3.).is_none());
+    /// ```
+    pub fn accuracy(&self, time: f64) -> Option<Record> {
+        let index = self.records.partition_point(|record| record.time <= time);
+        let before = self.records.get(index.checked_sub(1)?)?;
+        if before.time == time {
+            return Some(*before);
+        }
+        let after = self.records.get(index)?;
+        let fraction = (time - before.time) / (after.time - before.time);
+        let mut values = before.values();
+        for (value, other) in values.iter_mut().zip(after.values()) {
+            *value += (other - *value) * fraction;
+        }
+        Some(Record {
+            time,
+            ..Record::from_values(values)
+        })
+    }
+
+    /// Returns true if the point is accurate enough to use, and records it if it isn't.
+    ///
+    /// Feed points in time order so the rejected windows are accurate.
+    pub fn accepts(&mut self, point: &Point) -> bool {
+        let accepted = self
+            .accuracy(point.time)
+            .is_some_and(|accuracy| !accuracy.exceeds(self.limits));
+        if accepted {
+            self.rejecting = false;
+        } else {
+            match self.rejected.last_mut() {
+                Some(window) if self.rejecting => window.stop = point.time,
+                _ => self.rejected.push(TimeWindow::new(point.time, point.time)),
+            }
+            self.rejecting = true;
+        }
+        accepted
+    }
+
+    /// Returns the time windows of the rejected points so far.
+    pub fn rejected(&self) -> &[TimeWindow] {
+        &self.rejected
+    }
+}
+
+/// Reads every smrmsg record.
+///
+/// # Errors
+///
+/// Returns [Error::UnalignedLength] if the data isn't a whole number of records.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::smrmsg::Record;
+///
+/// let mut bytes = Vec::new();
+/// sbet::smrmsg::write(&[Record::default(); 2], &mut bytes).unwrap();
+/// assert_eq!(sbet::smrmsg::read(&bytes[..]).unwrap().len(), 2);
+/// ```
+pub fn read<R: Read>(mut read: R) -> Result<Vec<Record>> {
+    let mut bytes = Vec::new();
+    let _ = read.read_to_end(&mut bytes)?;
+    let remainder = bytes.len() % RECORD_SIZE;
+    if remainder != 0 {
+        return Err(Error::UnalignedLength {
+            len: bytes.len() as u64,
+            remainder: remainder as u64,
+        });
+    }
+    bytes
+        .chunks_exact(RECORD_SIZE)
+        .map(Record::read_from)
+        .collect()
+}
+
+/// Reads every record of the smrmsg file at a path.
+///
+/// # Examples
+///
+/// ```no_run
+/// let records = sbet::smrmsg::from_path("smrmsg.out").unwrap();
+/// ```
+pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Vec<Record>> {
+    read(BufReader::new(File::open(path)?))
+}
+
+/// Writes smrmsg records.
+pub fn write<W: Write>(records: &[Record], mut write: W) -> Result<()> {
+    for record in records {
+        for value in record.values() {
+            write.write_f64::<LittleEndian>(value)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AccuracyFilter, Limits, Record};
+    use crate::{Error, Point, TimeWindow};
+
+    #[test]
+    fn roundtrip() {
+        let record = Record {
+            time: 1.,
+            north_position: 2.,
+            heading: 10.,
+            ..Default::default()
+        };
+        let mut bytes = Vec::new();
+        super::write(&[record], &mut bytes).unwrap();
+        assert_eq!(bytes.len(), super::RECORD_SIZE);
+        assert_eq!(super::read(&bytes[..]).unwrap(), [record]);
+        assert!(matches!(
+            super::read(&bytes[..79]).unwrap_err(),
+            Error::UnalignedLength { remainder: 79, .. }
+        ));
+    }
+
+    #[test]
+    fn rejected_windows() {
+        let records = vec![
+            Record {
+                time: 0.,
+                ..Default::default()
+            },
+            Record {
+                time: 10.,
+                ..Default::default()
+            },
+            Record {
+                time: 20.,
+                east_position: 3.,
+                ..Default::default()
+            },
+            Record {
+                time: 30.,
+                ..Default::default()
+            },
+        ];
+        let mut filter = AccuracyFilter::new(records, Limits::default());
+        let accepted: Vec<f64> = (0..35)
+            .map(|time| Point {
+                time: f64::from(time),
+                ..Default::default()
+            })
+            .filter(|point| filter.accepts(point))
+            .map(|point| point.time)
+            .collect();
+        assert_eq!(accepted.len(), 18);
+        assert_eq!(accepted[13], 13.);
+        assert_eq!(
+            filter.rejected(),
+            [TimeWindow::new(14., 26.), TimeWindow::new(31., 34.)]
+        );
+    }
+
+    #[test]
+    fn nan_exceeds() {
+        let record = Record {
+            heading: f64::NAN,
+            ..Default::default()
+        };
+        assert!(record.exceeds(Limits::default()));
+    }
+}
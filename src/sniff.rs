@@ -0,0 +1,158 @@
+use crate::{time::TimeConvention, Endianness, Point, Reader, Result, RECORD_SIZE};
+use std::{f64::consts::FRAC_PI_2, f64::consts::TAU, fs::File, io::Read, path::Path};
+
+/// The number of records sampled by [sniff].
+const SAMPLE_RECORDS: usize = 64;
+
+/// A report on whether a file looks like an SBET.
+///
+/// Created by [sniff].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sniff {
+    /// The size of the file in bytes.
+    pub file_size: u64,
+
+    /// True if the file size is a whole number of records.
+    pub is_record_aligned: bool,
+
+    /// The number of records sampled from the start of the file.
+    pub sampled: usize,
+
+    /// The fraction of sampled records with latitude and longitude in radian range.
+    pub radian_positions: f64,
+
+    /// The fraction of consecutive sampled records whose time increases, counting GPS week rollovers.
+    pub monotonic_time: f64,
+
+    /// The detected byte order, if the sample was conclusive.
+    pub endianness: Option<Endianness>,
+
+    /// The detected time convention, if any.
+    pub time_convention: Option<TimeConvention>,
+
+    /// How much the file looks like an SBET, from zero to one.
+    ///
+    /// The mean of the record alignment, radian positions, and monotonic time scores.
+    pub confidence: f64,
+}
+
+impl Sniff {
+    /// Returns true if the file is very likely an SBET.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let sniff = sbet::sniff("data/2-points.sbet").unwrap();
+    /// assert!(sniff.is_sbet());
+    /// ```
+    pub fn is_sbet(&self) -> bool {
+        self.confidence >= 0.9
+    }
+}
+
+/// Samples the first records of a file and reports whether it looks like a valid SBET.
+///
+/// Checks that the file size is divisible by the record size, that
+/// latitudes and longitudes are in radians, and that time increases. The
+/// sample is decoded in the detected byte order, falling back to
+/// little-endian.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::{time::TimeConvention, Endianness};
+///
+/// let sniff = sbet::sniff("data/2-points.sbet").unwrap();
+/// assert_eq!(sniff.confidence, 1.);
+/// assert_eq!(sniff.endianness, Some(Endianness::Little));
+/// assert_eq!(sniff.time_convention, Some(TimeConvention::SecondsOfWeek));
+/// ```
+pub fn sniff<P: AsRef<Path>>(path: P) -> Result<Sniff> {
+    let file = File::open(path)?;
+    let file_size = file.metadata()?.len();
+    let mut bytes = Vec::new();
+    file.take((SAMPLE_RECORDS * RECORD_SIZE) as u64)
+        .read_to_end(&mut bytes)?;
+    let endianness = Endianness::detect(&bytes);
+    let whole = bytes.len() - bytes.len() % RECORD_SIZE;
+    let points = Reader::with_endianness(&bytes[..whole], endianness.unwrap_or_default())
+        .collect::<Result<Vec<_>>>()?;
+    Ok(assess(file_size, endianness, &points))
+}
+
+fn assess(file_size: u64, endianness: Option<Endianness>, points: &[Point]) -> Sniff {
+    let is_record_aligned = file_size > 0 && file_size.is_multiple_of(RECORD_SIZE as u64);
+    let radian_positions = fraction(
+        points
+            .iter()
+            .filter(|point| point.latitude.abs() <= FRAC_PI_2 && point.longitude.abs() <= TAU)
+            .count(),
+        points.len(),
+    );
+    let monotonic_time = if points.len() == 1 {
+        1.
+    } else {
+        fraction(
+            points
+                .windows(2)
+                .filter(|pair| {
+                    pair[1].time > pair[0].time
+                        || crate::time::is_rollover(pair[0].time, pair[1].time)
+                })
+                .count(),
+            points.len().saturating_sub(1),
+        )
+    };
+    let confidence = if points.is_empty() {
+        0.
+    } else {
+        (f64::from(u8::from(is_record_aligned)) + radian_positions + monotonic_time) / 3.
+    };
+    Sniff {
+        file_size,
+        is_record_aligned,
+        sampled: points.len(),
+        radian_positions,
+        monotonic_time,
+        endianness,
+        time_convention: TimeConvention::detect(points),
+        confidence,
+    }
+}
+
+fn fraction(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.
+    } else {
+        count as f64 / total as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Point;
+
+    #[test]
+    fn degrees_and_backwards_time() {
+        let points = [
+            Point {
+                time: 2.,
+                latitude: 45.,
+                longitude: -120.,
+                ..Default::default()
+            },
+            Point {
+                time: 1.,
+                latitude: 45.,
+                longitude: -120.,
+                ..Default::default()
+            },
+        ];
+        let sniff = super::assess(100, None, &points);
+        assert!(!sniff.is_record_aligned);
+        assert_eq!(sniff.radian_positions, 0.);
+        assert_eq!(sniff.monotonic_time, 0.);
+        assert_eq!(sniff.confidence, 0.);
+        assert!(!sniff.is_sbet());
+    }
+}
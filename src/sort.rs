@@ -0,0 +1,185 @@
+use crate::{Field, Point};
+use core::cmp::Ordering;
+
+/// A point that is totally ordered by time, for use in sorted collections.
+///
+/// Points are ordered by time, and then by each other field in
+/// [Field::ALL] order, all with [f64::total_cmp]. Two timed points are
+/// equal only if every field is identical, so points with the same time
+/// don't collide in a set.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::{Point, TimedPoint};
+/// use std::collections::BTreeSet;
+///
+/// let points: BTreeSet<TimedPoint> = [2., 1., 3.]
+///     .into_iter()
+///     .map(|time| TimedPoint(Point { time, ..Default::default() }))
+///     .collect();
+/// assert_eq!(points.first().unwrap().0.time, 1.);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct TimedPoint(pub Point);
+
+impl PartialEq for TimedPoint {
+    fn eq(&self, other: &TimedPoint) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for TimedPoint {}
+
+impl PartialOrd for TimedPoint {
+    fn partial_cmp(&self, other: &TimedPoint) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TimedPoint {
+    fn cmp(&self, other: &TimedPoint) -> Ordering {
+        Field::ALL
+            .into_iter()
+            .map(|field| self.0.get(field).total_cmp(&other.0.get(field)))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl From<Point> for TimedPoint {
+    fn from(point: Point) -> TimedPoint {
+        TimedPoint(point)
+    }
+}
+
+impl From<TimedPoint> for Point {
+    fn from(timed: TimedPoint) -> Point {
+        timed.0
+    }
+}
+
+/// Sorts points by time.
+///
+/// The sort is stable, so points with the same time keep their order. Times
+/// are compared with [f64::total_cmp], so NaN times don't cause a panic.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::Point;
+///
+/// let mut points = [
+///     Point { time: 2., ..Default::default() },
+///     Point { time: 1., ..Default::default() },
+/// ];
+/// sbet::sort_by_time(&mut points);
+/// assert_eq!(points[0].time, 1.);
+/// ```
+pub fn sort_by_time(points: &mut [Point]) {
+    points.sort_by(|a, b| a.time.total_cmp(&b.time));
+}
+
+/// Returns true if the points are sorted by time.
+///
+/// Consecutive points may have the same time. A NaN time is never sorted.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::Point;
+///
+/// let points = [
+///     Point { time: 1., ..Default::default() },
+///     Point { time: 1., ..Default::default() },
+///     Point { time: 2., ..Default::default() },
+/// ];
+/// assert!(sbet::is_sorted(&points));
+/// assert!(!sbet::is_sorted(&[points[2], points[0]]));
+/// ```
+pub fn is_sorted(points: &[Point]) -> bool {
+    unsorted_index(points).is_none()
+}
+
+/// Returns the index of the first point whose time is before the previous point's, or isn't comparable.
+pub(crate) fn unsorted_index(points: &[Point]) -> Option<usize> {
+    points
+        .windows(2)
+        .position(|pair| pair[0].time > pair[1].time || pair[1].time.is_nan())
+        .map(|index| index + 1)
+        .or_else(|| {
+            points
+                .first()
+                .filter(|point| point.time.is_nan())
+                .map(|_| 0)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Point;
+
+    fn points(times: &[f64]) -> Vec<Point> {
+        times
+            .iter()
+            .map(|&time| Point {
+                time,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sort_is_stable() {
+        let mut points: Vec<Point> = [(2., 0.), (1., 1.), (2., 2.), (1., 3.)]
+            .into_iter()
+            .map(|(time, altitude)| Point {
+                time,
+                altitude,
+                ..Default::default()
+            })
+            .collect();
+        super::sort_by_time(&mut points);
+        let altitudes: Vec<f64> = points.iter().map(|point| point.altitude).collect();
+        assert_eq!(altitudes, [1., 3., 0., 2.]);
+        assert!(super::is_sorted(&points));
+    }
+
+    #[test]
+    fn nan() {
+        assert!(!super::is_sorted(&points(&[f64::NAN])));
+        assert!(!super::is_sorted(&points(&[1., f64::NAN, 2.])));
+        let mut points = points(&[f64::NAN, 1.]);
+        super::sort_by_time(&mut points);
+        assert_eq!(points[0].time, 1.);
+    }
+
+    #[test]
+    fn timed_point_order() {
+        use super::TimedPoint;
+        let a = TimedPoint(Point {
+            time: 1.,
+            altitude: 2.,
+            ..Default::default()
+        });
+        let b = TimedPoint(Point {
+            time: 1.,
+            altitude: 1.,
+            ..Default::default()
+        });
+        let c = TimedPoint(Point {
+            time: f64::NAN,
+            ..Default::default()
+        });
+        assert!(b < a);
+        assert!(a < c);
+        assert_eq!(c, c);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn unsorted_index() {
+        assert_eq!(super::unsorted_index(&points(&[])), None);
+        assert_eq!(super::unsorted_index(&points(&[1., 3., 2.])), Some(2));
+    }
+}
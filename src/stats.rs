@@ -0,0 +1,337 @@
+use crate::{Derived, Field, Point, Quantity};
+
+/// Running summary statistics of every field and derived value.
+///
+/// Points are added one at a time, so a file doesn't need to fit in memory,
+/// unless percentiles are asked for with [Stats::with_percentiles].
+///
+/// # Examples
+///
+/// ```
+/// use sbet::{Field, Point, Stats};
+///
+/// let points = [
+///     Point { altitude: 1., ..Default::default() },
+///     Point { altitude: 3., ..Default::default() },
+/// ];
+/// let stats: Stats = points.iter().collect();
+/// let altitude = stats.get(Field::Altitude).unwrap();
+/// assert_eq!(altitude.mean, 2.);
+/// assert_eq!(altitude.max, 3.);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+    count: u64,
+    accumulators: Vec<(Quantity, Accumulator)>,
+}
+
+/// The 5th, 50th, and 95th percentiles of one field or derived value.
+///
+/// Percentiles are linearly interpolated between the nearest ranks.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Percentiles {
+    /// The 5th percentile.
+    pub p5: f64,
+
+    /// The 50th percentile, or median.
+    pub p50: f64,
+
+    /// The 95th percentile.
+    pub p95: f64,
+}
+
+/// Summary statistics of one field or derived value.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Summary {
+    /// The field or derived value.
+    pub quantity: Quantity,
+
+    /// The minimum value.
+    pub min: f64,
+
+    /// The maximum value.
+    pub max: f64,
+
+    /// The largest absolute value, for limits like "roll within ±5°".
+    pub max_abs: f64,
+
+    /// The mean value.
+    pub mean: f64,
+
+    /// The population standard deviation.
+    pub std_dev: f64,
+
+    /// The percentiles, if the statistics were created with [Stats::with_percentiles].
+    pub percentiles: Option<Percentiles>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Accumulator {
+    min: f64,
+    max: f64,
+    max_abs: f64,
+    mean: f64,
+    sum_of_squares: f64,
+    values: Option<Vec<f64>>,
+}
+
+impl Stats {
+    /// The quantities that acceptance limits are usually written for: roll,
+    /// pitch, the angular rates, and speed.
+    pub const DYNAMICS: [Quantity; 7] = [
+        Quantity::Field(Field::Roll),
+        Quantity::Field(Field::Pitch),
+        Quantity::Field(Field::XAngularRate),
+        Quantity::Field(Field::YAngularRate),
+        Quantity::Field(Field::ZAngularRate),
+        Quantity::Derived(Derived::AngularRateMagnitude),
+        Quantity::Derived(Derived::Speed),
+    ];
+
+    /// Creates empty statistics for every field, in [Field::ALL] order, then every derived value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Stats;
+    ///
+    /// let stats = Stats::new();
+    /// assert_eq!(stats.count(), 0);
+    /// ```
+    pub fn new() -> Stats {
+        Stats::with_values(None)
+    }
+
+    /// Creates empty statistics that also compute [Percentiles].
+    ///
+    /// Every value is kept until the summaries are computed, so memory grows
+    /// with the number of points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Field, Point, Stats};
+    ///
+    /// let mut stats = Stats::with_percentiles();
+    /// for i in 0..=100 {
+    ///     stats.add(&Point { altitude: f64::from(i), ..Default::default() });
+    /// }
+    /// let percentiles = stats.get(Field::Altitude).unwrap().percentiles.unwrap();
+    /// assert_eq!(percentiles.p5, 5.);
+    /// assert_eq!(percentiles.p50, 50.);
+    /// assert_eq!(percentiles.p95, 95.);
+    /// ```
+    pub fn with_percentiles() -> Stats {
+        Stats::with_values(Some(Vec::new()))
+    }
+
+    fn with_values(values: Option<Vec<f64>>) -> Stats {
+        let accumulator = Accumulator {
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            max_abs: 0.,
+            mean: 0.,
+            sum_of_squares: 0.,
+            values,
+        };
+        Stats {
+            count: 0,
+            accumulators: Field::ALL
+                .into_iter()
+                .map(Quantity::from)
+                .chain(Derived::ALL.into_iter().map(Quantity::from))
+                .map(|quantity| (quantity, accumulator.clone()))
+                .collect(),
+        }
+    }
+
+    /// Adds a point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Point, Stats};
+    ///
+    /// let mut stats = Stats::new();
+    /// stats.add(&Point::default());
+    /// assert_eq!(stats.count(), 1);
+    /// ```
+    pub fn add(&mut self, point: &Point) {
+        self.count += 1;
+        for (quantity, accumulator) in &mut self.accumulators {
+            // Welford's algorithm, which stays accurate for large values like GPS times.
+            let value = point.quantity(*quantity);
+            let delta = value - accumulator.mean;
+            accumulator.mean += delta / self.count as f64;
+            accumulator.sum_of_squares += delta * (value - accumulator.mean);
+            accumulator.min = accumulator.min.min(value);
+            accumulator.max = accumulator.max.max(value);
+            accumulator.max_abs = accumulator.max_abs.max(value.abs());
+            if let Some(values) = &mut accumulator.values {
+                values.push(value);
+            }
+        }
+    }
+
+    /// Returns the number of points added.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the summary of one field or derived value, or `None` if no points were added.
+    pub fn get<Q: Into<Quantity>>(&self, quantity: Q) -> Option<Summary> {
+        let quantity = quantity.into();
+        self.summaries()
+            .into_iter()
+            .find(|summary| summary.quantity == quantity)
+    }
+
+    /// Returns the summaries of [Stats::DYNAMICS], or nothing if no points were added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Field, Point, Stats};
+    ///
+    /// let points = [
+    ///     Point { roll: -0.2, ..Default::default() },
+    ///     Point { roll: 0.1, ..Default::default() },
+    /// ];
+    /// let stats: Stats = points.iter().collect();
+    /// let roll = stats.dynamics()[0];
+    /// assert_eq!(roll.quantity, Field::Roll.into());
+    /// assert_eq!(roll.max_abs, 0.2);
+    /// ```
+    pub fn dynamics(&self) -> Vec<Summary> {
+        let summaries = self.summaries();
+        Stats::DYNAMICS
+            .into_iter()
+            .filter_map(|quantity| {
+                summaries
+                    .iter()
+                    .find(|summary| summary.quantity == quantity)
+                    .copied()
+            })
+            .collect()
+    }
+
+    /// Returns the summary of every field and derived value, or nothing if no points were added.
+    ///
+    /// Percentiles are computed by sorting a copy of each quantity's values.
+    pub fn summaries(&self) -> Vec<Summary> {
+        if self.count == 0 {
+            return Vec::new();
+        }
+        self.accumulators
+            .iter()
+            .map(|(quantity, accumulator)| Summary {
+                quantity: *quantity,
+                min: accumulator.min,
+                max: accumulator.max,
+                max_abs: accumulator.max_abs,
+                mean: accumulator.mean,
+                std_dev: (accumulator.sum_of_squares / self.count as f64).sqrt(),
+                percentiles: accumulator.values.as_ref().map(|values| {
+                    let mut sorted = values.clone();
+                    sorted.sort_unstable_by(f64::total_cmp);
+                    Percentiles {
+                        p5: percentile(&sorted, 0.05),
+                        p50: percentile(&sorted, 0.5),
+                        p95: percentile(&sorted, 0.95),
+                    }
+                }),
+            })
+            .collect()
+    }
+}
+
+/// Returns a percentile of sorted, non-empty values, interpolating between the nearest ranks.
+fn percentile(sorted: &[f64], fraction: f64) -> f64 {
+    let rank = fraction * (sorted.len() - 1) as f64;
+    let low = rank.floor() as usize;
+    let high = rank.ceil() as usize;
+    sorted[low] + (sorted[high] - sorted[low]) * (rank - low as f64)
+}
+
+impl Default for Stats {
+    fn default() -> Stats {
+        Stats::new()
+    }
+}
+
+impl<'a> Extend<&'a Point> for Stats {
+    fn extend<I: IntoIterator<Item = &'a Point>>(&mut self, points: I) {
+        for point in points {
+            self.add(point);
+        }
+    }
+}
+
+impl<'a> FromIterator<&'a Point> for Stats {
+    fn from_iter<I: IntoIterator<Item = &'a Point>>(points: I) -> Stats {
+        let mut stats = Stats::new();
+        stats.extend(points);
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Stats;
+    use crate::{Derived, Field, Point};
+
+    #[test]
+    fn std_dev() {
+        let points: Vec<Point> = [2., 4., 4., 4., 5., 5., 7., 9.]
+            .into_iter()
+            .map(|time| Point {
+                time: 396000. + time,
+                ..Default::default()
+            })
+            .collect();
+        let stats: Stats = points.iter().collect();
+        let time = stats.get(Field::Time).unwrap();
+        assert_eq!(time.mean, 396005.);
+        assert_eq!(time.std_dev, 2.);
+    }
+
+    #[test]
+    fn magnitudes() {
+        let point = Point {
+            x_acceleration: 3.,
+            z_acceleration: 4.,
+            ..Default::default()
+        };
+        let stats: Stats = [point].iter().collect();
+        assert_eq!(stats.get(Derived::AccelerationMagnitude).unwrap().max, 5.);
+    }
+
+    #[test]
+    fn percentiles() {
+        let points: Vec<Point> = [4., 1., 3., 2.]
+            .into_iter()
+            .map(|speed| Point {
+                x_velocity: speed,
+                pitch: -speed,
+                ..Default::default()
+            })
+            .collect();
+        let mut stats = Stats::with_percentiles();
+        stats.extend(&points);
+        let percentiles = stats.get(Derived::Speed).unwrap().percentiles.unwrap();
+        assert!((percentiles.p5 - 1.15).abs() < 1e-12);
+        assert_eq!(percentiles.p50, 2.5);
+        assert!((percentiles.p95 - 3.85).abs() < 1e-12);
+        let pitch = stats.get(Field::Pitch).unwrap();
+        assert_eq!(pitch.max, -1.);
+        assert_eq!(pitch.max_abs, 4.);
+        assert!(Stats::new().get(Field::Pitch).is_none());
+        let stats: Stats = points.iter().collect();
+        assert!(stats.get(Field::Pitch).unwrap().percentiles.is_none());
+    }
+
+    #[test]
+    fn empty() {
+        assert!(Stats::new().summaries().is_empty());
+    }
+}
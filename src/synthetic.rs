@@ -0,0 +1,384 @@
+//! Synthetic trajectories, for testing pipelines and writing examples without fixture files.
+//!
+//! A [Generator] flies a sequence of straight legs and coordinated turns
+//! at a constant speed and altitude, optionally adding Gaussian noise to
+//! the positions and attitudes.
+//!
+//! # Examples
+//!
+//! ```
+//! use sbet::{synthetic::Generator, Point};
+//!
+//! let trajectory = Generator::new(Point::default())
+//!     .rate(10.)
+//!     .racetrack(2, 60., 20.)
+//!     .build();
+//! assert_eq!(trajectory.len(), 3201);
+//! ```
+
+use crate::{geodesy::WGS84_A, geodesy::WGS84_E2, smooth::wrap, Point, Trajectory};
+use std::f64::consts::{PI, TAU};
+
+/// Standard gravity, in meters per second squared, used to bank coordinated turns.
+const GRAVITY: f64 = 9.80665;
+
+/// Builds a synthetic trajectory from straight legs and turns.
+///
+/// The trajectory starts at the start point's position, time, and true
+/// heading, and flies each leg in order at a constant speed. Each leg's
+/// duration is rounded to a whole number of samples. Turns are coordinated,
+/// so the roll is the bank angle needed for the turn rate at the current
+/// speed. Velocities and accelerations are in the north, east, down frame,
+/// with a zero wander angle.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::{synthetic::Generator, Point};
+/// use std::f64::consts::FRAC_PI_2;
+///
+/// let trajectory = Generator::new(Point::default())
+///     .rate(1.)
+///     .speed(50.)
+///     .straight(10.)
+///     .turn(FRAC_PI_2, 10.)
+///     .build();
+/// assert_eq!(trajectory.len(), 21);
+/// assert!((trajectory.points()[20].true_heading() - FRAC_PI_2).abs() < 1e-9);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Generator {
+    start: Point,
+    rate: f64,
+    speed: f64,
+    legs: Vec<Leg>,
+    position_noise: f64,
+    attitude_noise: f64,
+    seed: u64,
+}
+
+/// One leg of a synthetic trajectory.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Leg {
+    /// The heading change, in radians, clockwise from above.
+    angle: f64,
+
+    /// The duration, in seconds.
+    duration: f64,
+}
+
+impl Generator {
+    /// Creates a generator that starts at a point.
+    ///
+    /// The default rate is 10 Hz and the default speed is 60 m/s.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{synthetic::Generator, Point};
+    ///
+    /// let start = Point::builder().latitude_degrees(40.).altitude(1000.).build();
+    /// let generator = Generator::new(start);
+    /// ```
+    pub fn new(start: Point) -> Generator {
+        Generator {
+            start,
+            rate: 10.,
+            speed: 60.,
+            legs: Vec::new(),
+            position_noise: 0.,
+            attitude_noise: 0.,
+            seed: 0,
+        }
+    }
+
+    /// Sets the sample rate, in hertz.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rate isn't positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{synthetic::Generator, Point};
+    ///
+    /// let trajectory = Generator::new(Point::default()).rate(200.).straight(1.).build();
+    /// assert_eq!(trajectory.len(), 201);
+    /// ```
+    pub fn rate(mut self, rate: f64) -> Generator {
+        assert!(rate > 0., "rate must be positive");
+        self.rate = rate;
+        self
+    }
+
+    /// Sets the ground speed, in meters per second.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{synthetic::Generator, Point};
+    ///
+    /// let trajectory = Generator::new(Point::default()).speed(40.).straight(1.).build();
+    /// assert_eq!(trajectory.points()[0].speed(), 40.);
+    /// ```
+    pub fn speed(mut self, speed: f64) -> Generator {
+        self.speed = speed;
+        self
+    }
+
+    /// Flies straight and level for a duration, in seconds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{synthetic::Generator, Point};
+    ///
+    /// let trajectory = Generator::new(Point::default()).straight(60.).build();
+    /// ```
+    pub fn straight(mut self, duration: f64) -> Generator {
+        self.legs.push(Leg {
+            angle: 0.,
+            duration,
+        });
+        self
+    }
+
+    /// Turns through an angle, in radians, over a duration, in seconds.
+    ///
+    /// Positive angles turn right, i.e. clockwise when seen from above.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{synthetic::Generator, Point};
+    /// use std::f64::consts::PI;
+    ///
+    /// let trajectory = Generator::new(Point::default()).turn(-PI, 30.).build();
+    /// assert!(trajectory.points()[1].roll < 0.);
+    /// ```
+    pub fn turn(mut self, angle: f64, duration: f64) -> Generator {
+        self.legs.push(Leg { angle, duration });
+        self
+    }
+
+    /// Flies laps of a racetrack: a line, a right half-turn, a line back, and another half-turn.
+    ///
+    /// Durations are in seconds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{synthetic::Generator, LineLimits, Point};
+    ///
+    /// let trajectory = Generator::new(Point::default()).racetrack(1, 60., 20.).build();
+    /// assert_eq!(trajectory.flight_lines(LineLimits::default()).len(), 2);
+    /// ```
+    pub fn racetrack(mut self, laps: usize, line_duration: f64, turn_duration: f64) -> Generator {
+        for _ in 0..2 * laps {
+            self = self.straight(line_duration).turn(PI, turn_duration);
+        }
+        self
+    }
+
+    /// Adds Gaussian noise with these standard deviations to every point.
+    ///
+    /// Position noise is in meters and applied in each of the north, east,
+    /// and down directions. Attitude noise is in radians and applied to the
+    /// roll, pitch, and yaw. The noise is the same for the same
+    /// [seed](Generator::seed).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{synthetic::Generator, Point};
+    ///
+    /// let trajectory = Generator::new(Point::default())
+    ///     .straight(10.)
+    ///     .noise(0.05, 0.001)
+    ///     .build();
+    /// ```
+    pub fn noise(mut self, position: f64, attitude: f64) -> Generator {
+        self.position_noise = position;
+        self.attitude_noise = attitude;
+        self
+    }
+
+    /// Sets the seed for the noise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{synthetic::Generator, Point};
+    ///
+    /// let generator = Generator::new(Point::default()).straight(1.).noise(1., 0.);
+    /// assert_ne!(generator.clone().seed(1).build(), generator.seed(2).build());
+    /// ```
+    pub fn seed(mut self, seed: u64) -> Generator {
+        self.seed = seed;
+        self
+    }
+
+    /// Builds the trajectory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{synthetic::Generator, Point};
+    ///
+    /// let trajectory = Generator::new(Point::default()).build();
+    /// assert_eq!(trajectory.len(), 1);
+    /// ```
+    pub fn build(&self) -> Trajectory {
+        let interval = 1. / self.rate;
+        let mut state = self.start;
+        let mut heading = self.start.true_heading();
+        let mut points = vec![self.point(&state, heading, 0.)];
+        let mut samples = 0;
+        for leg in &self.legs {
+            let steps = (leg.duration * self.rate).round() as usize;
+            let turn_rate = if steps > 0 {
+                leg.angle / (steps as f64 * interval)
+            } else {
+                0.
+            };
+            for _ in 0..steps {
+                let middle = heading + 0.5 * turn_rate * interval;
+                let (sin_latitude, cos_latitude) = state.latitude.sin_cos();
+                let w2 = 1. - WGS84_E2 * sin_latitude * sin_latitude;
+                let meridian = WGS84_A * (1. - WGS84_E2) / (w2 * w2.sqrt()) + state.altitude;
+                let normal = WGS84_A / w2.sqrt() + state.altitude;
+                state.latitude += self.speed * middle.cos() * interval / meridian;
+                state.longitude += self.speed * middle.sin() * interval / (normal * cos_latitude);
+                heading += turn_rate * interval;
+                samples += 1;
+                state.time = self.start.time + samples as f64 * interval;
+                points.push(self.point(&state, heading, turn_rate));
+            }
+        }
+        if self.position_noise > 0. || self.attitude_noise > 0. {
+            let mut random = Normal::new(self.seed);
+            for point in &mut points {
+                let (sin_latitude, cos_latitude) = point.latitude.sin_cos();
+                let normal = WGS84_A / (1. - WGS84_E2 * sin_latitude * sin_latitude).sqrt();
+                point.latitude += self.position_noise * random.next() / (normal + point.altitude);
+                point.longitude += self.position_noise * random.next()
+                    / ((normal + point.altitude) * cos_latitude);
+                point.altitude += self.position_noise * random.next();
+                point.roll += self.attitude_noise * random.next();
+                point.pitch += self.attitude_noise * random.next();
+                point.yaw = wrap(point.yaw + self.attitude_noise * random.next());
+            }
+        }
+        points.into()
+    }
+
+    fn point(&self, state: &Point, heading: f64, turn_rate: f64) -> Point {
+        let (sin_heading, cos_heading) = heading.sin_cos();
+        let roll = (self.speed * turn_rate / GRAVITY).atan();
+        let (sin_roll, cos_roll) = roll.sin_cos();
+        let centripetal = self.speed * turn_rate;
+        Point {
+            time: state.time,
+            latitude: state.latitude,
+            longitude: state.longitude,
+            altitude: state.altitude,
+            x_velocity: self.speed * cos_heading,
+            y_velocity: self.speed * sin_heading,
+            z_velocity: 0.,
+            roll,
+            pitch: 0.,
+            yaw: wrap(heading),
+            wander_angle: 0.,
+            x_acceleration: -centripetal * sin_heading,
+            y_acceleration: centripetal * cos_heading,
+            z_acceleration: 0.,
+            x_angular_rate: 0.,
+            y_angular_rate: turn_rate * sin_roll,
+            z_angular_rate: turn_rate * cos_roll,
+        }
+    }
+}
+
+/// Standard normal random numbers from a xorshift generator and the Box-Muller transform.
+struct Normal {
+    state: u64,
+    spare: Option<f64>,
+}
+
+impl Normal {
+    fn new(seed: u64) -> Normal {
+        Normal {
+            // Xorshift gets stuck at zero, so mix the seed with a nonzero constant.
+            state: seed ^ 0x9e37_79b9_7f4a_7c15,
+            spare: None,
+        }
+    }
+
+    fn uniform(&mut self) -> f64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        ((self.state >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+    }
+
+    fn next(&mut self) -> f64 {
+        if let Some(spare) = self.spare.take() {
+            return spare;
+        }
+        let radius = (-2. * self.uniform().ln()).sqrt();
+        let angle = TAU * self.uniform();
+        self.spare = Some(radius * angle.sin());
+        radius * angle.cos()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Generator;
+    use crate::Point;
+
+    #[test]
+    fn racetrack_closes() {
+        let start = Point::builder().latitude_degrees(45.).build();
+        let trajectory = Generator::new(start)
+            .rate(20.)
+            .racetrack(1, 30., 30.)
+            .build();
+        let last = &trajectory.points()[trajectory.len() - 1];
+        assert!(start.geodesic_distance(last) < 1.);
+        assert_eq!(last.time, 120.);
+        assert!(trajectory.points()[trajectory.len() / 2].roll > 0.);
+        // One lap is two half-turns, i.e. back to the start heading.
+        assert!((last.yaw - start.yaw).abs() < 1e-9);
+        let length: f64 = trajectory
+            .points()
+            .windows(2)
+            .map(|pair| pair[0].geodesic_distance(&pair[1]))
+            .sum();
+        assert!((length - 60. * 120.).abs() < 1., "{}", length);
+    }
+
+    #[test]
+    fn noise() {
+        let trajectory = Generator::new(Point::default())
+            .rate(100.)
+            .straight(100.)
+            .noise(2., 0.)
+            .build();
+        let altitudes: Vec<f64> = trajectory
+            .points()
+            .iter()
+            .map(|point| point.altitude)
+            .collect();
+        let mean = altitudes.iter().sum::<f64>() / altitudes.len() as f64;
+        let variance = altitudes
+            .iter()
+            .map(|altitude| (altitude - mean).powi(2))
+            .sum::<f64>()
+            / altitudes.len() as f64;
+        assert!(mean.abs() < 0.1, "{}", mean);
+        assert!((variance.sqrt() - 2.).abs() < 0.1, "{}", variance.sqrt());
+        assert_eq!(trajectory.points()[0].roll, 0.);
+    }
+}
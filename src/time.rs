@@ -0,0 +1,527 @@
+//! GPS time utilities.
+//!
+//! SBET times are usually GPS seconds-of-week, which are offset from UTC by
+//! the number of leap seconds since the GPS epoch (1980-01-06T00:00:00Z).
+//! Conversions to and from UTC datetimes require the `chrono` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "chrono")]
+//! # {
+//! use chrono::{DateTime, Utc};
+//!
+//! let datetime: DateTime<Utc> = "2023-06-01T14:00:00Z".parse().unwrap();
+//! assert_eq!(sbet::time::gps_week(datetime), (2264, 396018.));
+//! assert_eq!(sbet::time::to_utc(2264, 396018.).unwrap(), datetime);
+//! # }
+//! ```
+
+use crate::Point;
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// The number of seconds in a GPS week.
+pub const SECONDS_PER_WEEK: f64 = 604800.;
+
+/// The number of seconds in a day.
+pub const SECONDS_PER_DAY: f64 = 86400.;
+
+/// The offset between standard and adjusted standard GPS time.
+pub const ADJUSTED_STANDARD_OFFSET: f64 = 1e9;
+
+/// How the time values in an SBET (or LAS) file are defined.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::time::TimeConvention;
+///
+/// // Seconds-of-week in GPS week 2264, converted to LAS adjusted standard time
+/// let gps_seconds = TimeConvention::SecondsOfWeek.to_gps_seconds(396018., 2264. * 604800.);
+/// let adjusted = TimeConvention::AdjustedStandard.from_gps_seconds(gps_seconds);
+/// assert_eq!(adjusted, 369663218.);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TimeConvention {
+    /// Seconds since the start of the GPS week (Sunday 00:00:00 GPS time).
+    SecondsOfWeek,
+
+    /// Seconds since the start of the GPS day.
+    SecondsOfDay,
+
+    /// Standard GPS time, seconds since the GPS epoch.
+    Standard,
+
+    /// Adjusted standard GPS time, standard GPS time minus one billion, as used by LAS.
+    AdjustedStandard,
+}
+
+impl TimeConvention {
+    /// Detects the time convention of some points from the range of their times.
+    ///
+    /// Returns `None` if there are no points or if the times don't fit any
+    /// convention. Times that all fall within one day are reported as
+    /// [TimeConvention::SecondsOfDay], even though they could also be
+    /// seconds-of-week on a Sunday.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{time::TimeConvention, Trajectory};
+    ///
+    /// let trajectory = Trajectory::from_path("data/2-points.sbet").unwrap();
+    /// assert_eq!(
+    ///     TimeConvention::detect(trajectory.points()),
+    ///     Some(TimeConvention::SecondsOfWeek)
+    /// );
+    /// ```
+    pub fn detect(points: &[Point]) -> Option<TimeConvention> {
+        let (min, max) = points.iter().map(|point| point.time).fold(
+            None,
+            |bounds: Option<(f64, f64)>, time| {
+                if time.is_nan() {
+                    bounds
+                } else {
+                    Some(bounds.map_or((time, time), |(min, max)| (min.min(time), max.max(time))))
+                }
+            },
+        )?;
+        if min >= 0. && max <= SECONDS_PER_DAY {
+            Some(TimeConvention::SecondsOfDay)
+        } else if min >= 0. && max <= 2. * SECONDS_PER_WEEK {
+            // Trajectories that cross the end of the week keep counting past 604800
+            Some(TimeConvention::SecondsOfWeek)
+        } else if min >= ADJUSTED_STANDARD_OFFSET {
+            Some(TimeConvention::Standard)
+        } else if min > -ADJUSTED_STANDARD_OFFSET && max < ADJUSTED_STANDARD_OFFSET {
+            Some(TimeConvention::AdjustedStandard)
+        } else {
+            None
+        }
+    }
+
+    /// Converts a time in this convention to standard GPS time.
+    ///
+    /// `period_start` is the standard GPS time at the start of the week or
+    /// day, and is ignored for [TimeConvention::Standard] and
+    /// [TimeConvention::AdjustedStandard].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::time::TimeConvention;
+    ///
+    /// assert_eq!(TimeConvention::SecondsOfDay.to_gps_seconds(10., 86400.), 86410.);
+    /// assert_eq!(TimeConvention::AdjustedStandard.to_gps_seconds(10., 0.), 1e9 + 10.);
+    /// ```
+    pub fn to_gps_seconds(&self, time: f64, period_start: f64) -> f64 {
+        match self {
+            TimeConvention::SecondsOfWeek | TimeConvention::SecondsOfDay => period_start + time,
+            TimeConvention::Standard => time,
+            TimeConvention::AdjustedStandard => time + ADJUSTED_STANDARD_OFFSET,
+        }
+    }
+
+    /// Converts standard GPS time to a time in this convention.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::time::TimeConvention;
+    ///
+    /// assert_eq!(TimeConvention::SecondsOfWeek.from_gps_seconds(604810.), 10.);
+    /// assert_eq!(TimeConvention::SecondsOfDay.from_gps_seconds(86410.), 10.);
+    /// ```
+    pub fn from_gps_seconds(&self, gps_seconds: f64) -> f64 {
+        match self {
+            TimeConvention::SecondsOfWeek => gps_seconds.rem_euclid(SECONDS_PER_WEEK),
+            TimeConvention::SecondsOfDay => gps_seconds.rem_euclid(SECONDS_PER_DAY),
+            TimeConvention::Standard => gps_seconds,
+            TimeConvention::AdjustedStandard => gps_seconds - ADJUSTED_STANDARD_OFFSET,
+        }
+    }
+
+    /// Converts a time from this convention into another.
+    ///
+    /// `period_start` is as in [TimeConvention::to_gps_seconds].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::time::TimeConvention;
+    ///
+    /// let adjusted = TimeConvention::SecondsOfWeek.convert(
+    ///     396018.,
+    ///     2264. * 604800.,
+    ///     TimeConvention::AdjustedStandard,
+    /// );
+    /// assert_eq!(adjusted, 369663218.);
+    /// ```
+    pub fn convert(&self, time: f64, period_start: f64, to: TimeConvention) -> f64 {
+        to.from_gps_seconds(self.to_gps_seconds(time, period_start))
+    }
+}
+
+/// The GPS epoch, 1980-01-06T00:00:00Z, as a unix timestamp.
+#[cfg(feature = "chrono")]
+const GPS_EPOCH_UNIX_TIMESTAMP: i64 = 315964800;
+
+/// UTC unix timestamps at which each leap second took effect, and the
+/// resulting GPS - UTC offset.
+#[cfg(feature = "chrono")]
+const LEAP_SECONDS: [(i64, i64); 18] = [
+    (362793600, 1),
+    (394329600, 2),
+    (425865600, 3),
+    (489024000, 4),
+    (567993600, 5),
+    (631152000, 6),
+    (662688000, 7),
+    (709948800, 8),
+    (741484800, 9),
+    (773020800, 10),
+    (820454400, 11),
+    (867715200, 12),
+    (915148800, 13),
+    (1136073600, 14),
+    (1230768000, 15),
+    (1341100800, 16),
+    (1435708800, 17),
+    (1483228800, 18),
+];
+
+/// Returns the GPS - UTC offset, in seconds, at a UTC datetime.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{DateTime, Utc};
+///
+/// let datetime: DateTime<Utc> = "2023-06-01T14:00:00Z".parse().unwrap();
+/// assert_eq!(sbet::time::leap_seconds(datetime), 18);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn leap_seconds(datetime: DateTime<Utc>) -> i64 {
+    let timestamp = datetime.timestamp();
+    LEAP_SECONDS
+        .iter()
+        .rev()
+        .find(|(start, _)| timestamp >= *start)
+        .map(|(_, offset)| *offset)
+        .unwrap_or(0)
+}
+
+/// Converts a UTC datetime to seconds since the GPS epoch.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{DateTime, Utc};
+///
+/// let datetime: DateTime<Utc> = "1980-01-06T00:00:00Z".parse().unwrap();
+/// assert_eq!(sbet::time::gps_seconds(datetime), 0.);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn gps_seconds(datetime: DateTime<Utc>) -> f64 {
+    let seconds = datetime.timestamp() - GPS_EPOCH_UNIX_TIMESTAMP + leap_seconds(datetime);
+    seconds as f64 + f64::from(datetime.timestamp_subsec_nanos()) * 1e-9
+}
+
+/// Converts a UTC datetime to a GPS week number and seconds-of-week.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{DateTime, Utc};
+///
+/// let datetime: DateTime<Utc> = "2023-05-28T00:00:00Z".parse().unwrap();
+/// assert_eq!(sbet::time::gps_week(datetime), (2264, 18.));
+/// ```
+#[cfg(feature = "chrono")]
+pub fn gps_week(datetime: DateTime<Utc>) -> (u32, f64) {
+    let seconds = gps_seconds(datetime);
+    let week = (seconds / SECONDS_PER_WEEK).floor();
+    (week as u32, seconds - week * SECONDS_PER_WEEK)
+}
+
+/// Converts a UTC datetime to seconds relative to the start of the given GPS week.
+///
+/// The result may be negative or larger than [SECONDS_PER_WEEK] if the
+/// datetime falls outside of the week, which matches how SBET files that
+/// span a week boundary store their times.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{DateTime, Utc};
+///
+/// let datetime: DateTime<Utc> = "2023-06-04T00:00:00Z".parse().unwrap();
+/// assert_eq!(sbet::time::seconds_of_week(datetime, 2264), 604818.);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn seconds_of_week(datetime: DateTime<Utc>, week: u32) -> f64 {
+    gps_seconds(datetime) - f64::from(week) * SECONDS_PER_WEEK
+}
+
+/// Converts a GPS week number and seconds-of-week to a UTC datetime.
+///
+/// Seconds-of-week outside of `0..604800` are allowed, and refer to the
+/// preceding or following weeks. Returns `None` if the seconds-of-week
+/// aren't finite or the datetime is out of range.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{DateTime, Utc};
+///
+/// let datetime: DateTime<Utc> = "2023-05-28T00:00:00Z".parse().unwrap();
+/// assert_eq!(sbet::time::to_utc(2264, 18.).unwrap(), datetime);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn to_utc(week: u32, seconds_of_week: f64) -> Option<DateTime<Utc>> {
+    let gps_seconds = f64::from(week) * SECONDS_PER_WEEK + seconds_of_week;
+    let whole_seconds = gps_seconds.floor();
+    // `i64::MAX as f64` rounds up to 2^63, which doesn't fit.
+    if !whole_seconds.is_finite()
+        || whole_seconds < i64::MIN as f64
+        || whole_seconds >= i64::MAX as f64
+    {
+        return None;
+    }
+    let nanoseconds = ((gps_seconds - whole_seconds) * 1e9).round() as u32;
+    let mut timestamp = (whole_seconds as i64).checked_add(GPS_EPOCH_UNIX_TIMESTAMP)?;
+    let leap_seconds = LEAP_SECONDS
+        .iter()
+        .rev()
+        .find(|(start, offset)| timestamp >= *start + *offset)
+        .map(|(_, offset)| *offset)
+        .unwrap_or(0);
+    timestamp = timestamp.checked_sub(leap_seconds)?;
+    if nanoseconds >= 1_000_000_000 {
+        DateTime::from_timestamp(timestamp.checked_add(1)?, nanoseconds - 1_000_000_000)
+    } else {
+        DateTime::from_timestamp(timestamp, nanoseconds)
+    }
+}
+
+/// Converts seconds-of-week to a UTC datetime, using a date within the GPS week.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{DateTime, NaiveDate, Utc};
+///
+/// let date = NaiveDate::from_ymd_opt(2023, 6, 1).unwrap();
+/// let datetime: DateTime<Utc> = "2023-06-01T14:00:00Z".parse().unwrap();
+/// assert_eq!(sbet::time::to_utc_from_date(date, 396018.).unwrap(), datetime);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn to_utc_from_date(date: NaiveDate, seconds_of_week: f64) -> Option<DateTime<Utc>> {
+    to_utc(week_of_date(date), seconds_of_week)
+}
+
+/// Returns the GPS week containing a UTC date.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+///
+/// let date = NaiveDate::from_ymd_opt(2023, 6, 1).unwrap();
+/// assert_eq!(sbet::time::week_of_date(date), 2264);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn week_of_date(date: NaiveDate) -> u32 {
+    gps_week(date.and_hms_opt(12, 0, 0).unwrap().and_utc()).0
+}
+
+/// Returns the index of the first point after a GPS week rollover, if there is one.
+///
+/// A rollover is detected when seconds-of-week time jumps backwards by more
+/// than half a week, e.g. from ~604800 back to ~0.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::Point;
+///
+/// let points = [
+///     Point { time: 604799.5, ..Default::default() },
+///     Point { time: 0.5, ..Default::default() },
+/// ];
+/// assert_eq!(sbet::time::detect_week_rollover(&points), Some(1));
+/// ```
+pub fn detect_week_rollover(points: &[Point]) -> Option<usize> {
+    points
+        .windows(2)
+        .position(|pair| is_rollover(pair[0].time, pair[1].time))
+        .map(|index| index + 1)
+}
+
+/// Makes seconds-of-week times continuous across GPS week rollovers.
+///
+/// Points after each rollover have a week's worth of seconds added to their
+/// time, so a trajectory that crosses from Saturday into Sunday counts past
+/// 604800 instead of jumping back to zero. Returns the number of rollovers.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::Point;
+///
+/// let mut points = [
+///     Point { time: 604799.5, ..Default::default() },
+///     Point { time: 0.5, ..Default::default() },
+/// ];
+/// assert_eq!(sbet::time::unwrap_week_crossing(&mut points), 1);
+/// assert_eq!(points[1].time, 604800.5);
+/// ```
+pub fn unwrap_week_crossing(points: &mut [Point]) -> usize {
+    let mut rollovers = 0;
+    let mut previous: Option<f64> = None;
+    for point in points {
+        if previous.is_some_and(|previous| is_rollover(previous, point.time)) {
+            debug!(time = point.time, "found a GPS week rollover");
+            rollovers += 1;
+        }
+        previous = Some(point.time);
+        point.time += rollovers as f64 * SECONDS_PER_WEEK;
+    }
+    rollovers
+}
+
+pub(crate) fn is_rollover(previous: f64, current: f64) -> bool {
+    previous - current > SECONDS_PER_WEEK / 2.
+}
+
+/// Stitches consecutive weekly trajectories into one trajectory on an absolute time axis.
+///
+/// Each input is a GPS week number and that week's points, whose times are
+/// seconds-of-week. Output times are seconds since the GPS epoch. Times
+/// that roll over from the end of a week back to zero within an input are
+/// moved into the following week. Points are sorted by time, and points
+/// with the same absolute time (e.g. where consecutive files overlap at the
+/// seam) are only kept once.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::Point;
+///
+/// let first = vec![Point { time: 604799., ..Default::default() }];
+/// let second = vec![Point { time: 1., ..Default::default() }];
+/// let points = sbet::time::stitch([(2264, first), (2265, second)]);
+/// assert_eq!(points[0].time, 2264. * 604800. + 604799.);
+/// assert_eq!(points[1].time, 2265. * 604800. + 1.);
+/// ```
+pub fn stitch<I>(weeks: I) -> Vec<Point>
+where
+    I: IntoIterator<Item = (u32, Vec<Point>)>,
+{
+    let mut stitched = Vec::new();
+    for (week, mut points) in weeks {
+        unwrap_week_crossing(&mut points);
+        let offset = f64::from(week) * SECONDS_PER_WEEK;
+        stitched.extend(points.into_iter().map(|mut point| {
+            point.time += offset;
+            point
+        }));
+    }
+    crate::sort_by_time(&mut stitched);
+    stitched.dedup_by(|a, b| a.time == b.time);
+    stitched
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Point;
+    #[cfg(feature = "chrono")]
+    use chrono::{DateTime, Utc};
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn leap_seconds() {
+        let before: DateTime<Utc> = "2016-12-31T23:59:59Z".parse().unwrap();
+        let after: DateTime<Utc> = "2017-01-01T00:00:00Z".parse().unwrap();
+        assert_eq!(super::leap_seconds(before), 17);
+        assert_eq!(super::leap_seconds(after), 18);
+    }
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn subsecond() {
+        let datetime: DateTime<Utc> = "2023-06-01T14:00:00.5Z".parse().unwrap();
+        assert_eq!(super::gps_week(datetime), (2264, 396018.5));
+    }
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn to_utc_round_trip() {
+        for datetime in [
+            "1980-01-06T00:00:00Z",
+            "2016-12-31T23:59:59Z",
+            "2017-01-01T00:00:00Z",
+            "2023-06-01T14:00:00.125Z",
+        ] {
+            let datetime: DateTime<Utc> = datetime.parse().unwrap();
+            let (week, seconds) = super::gps_week(datetime);
+            assert_eq!(super::to_utc(week, seconds).unwrap(), datetime);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn to_utc_out_of_range() {
+        for seconds in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY, 1e300, -1e300] {
+            assert_eq!(super::to_utc(2264, seconds), None);
+        }
+    }
+
+    #[test]
+    fn detect_time_convention() {
+        use super::TimeConvention;
+
+        let points = |times: &[f64]| -> Vec<Point> {
+            times
+                .iter()
+                .map(|&time| Point {
+                    time,
+                    ..Default::default()
+                })
+                .collect()
+        };
+        assert_eq!(TimeConvention::detect(&[]), None);
+        assert_eq!(
+            TimeConvention::detect(&points(&[100., 200.])),
+            Some(TimeConvention::SecondsOfDay)
+        );
+        assert_eq!(
+            TimeConvention::detect(&points(&[604000., 605000.])),
+            Some(TimeConvention::SecondsOfWeek)
+        );
+        assert_eq!(
+            TimeConvention::detect(&points(&[1.37e9])),
+            Some(TimeConvention::Standard)
+        );
+        assert_eq!(
+            TimeConvention::detect(&points(&[3.7e8])),
+            Some(TimeConvention::AdjustedStandard)
+        );
+    }
+
+    #[test]
+    fn stitch_rollover_and_overlap() {
+        let point = |time| Point {
+            time,
+            ..Default::default()
+        };
+        let first = vec![point(604799.), point(0.), point(1.)];
+        let second = vec![point(1.), point(2.)];
+        let times: Vec<f64> = super::stitch([(1, first), (2, second)])
+            .into_iter()
+            .map(|point| point.time - 604800.)
+            .collect();
+        assert_eq!(times, [604799., 604800., 604801., 604802.]);
+    }
+}
@@ -0,0 +1,259 @@
+use crate::{Enu, Error, Ned, Point, Reader, Result};
+use std::{ops::ControlFlow, path::Path};
+
+const PROGRESS_CHUNK_SIZE: usize = 4096;
+
+/// An in-memory sequence of points.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::Trajectory;
+///
+/// let trajectory = Trajectory::from_path("data/2-points.sbet").unwrap();
+/// assert_eq!(trajectory.len(), 2);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Trajectory {
+    points: Vec<Point>,
+}
+
+impl Trajectory {
+    /// Creates a trajectory from points that are already sorted by time.
+    ///
+    /// Use [Trajectory::sorted] to sort the points instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns [Error::Unsorted] with the index of the first point that is out of time order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Point, Trajectory};
+    ///
+    /// let first = Point { time: 1., ..Default::default() };
+    /// let second = Point { time: 2., ..Default::default() };
+    /// assert!(Trajectory::new(vec![first, second]).is_ok());
+    /// assert!(Trajectory::new(vec![second, first]).is_err());
+    /// ```
+    pub fn new(points: Vec<Point>) -> Result<Trajectory> {
+        match crate::sort::unsorted_index(&points) {
+            Some(index) => Err(Error::Unsorted(index)),
+            None => Ok(Trajectory { points }),
+        }
+    }
+
+    /// Creates a trajectory from points, sorting them by time.
+    ///
+    /// See [sort_by_time](crate::sort_by_time).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Point, Trajectory};
+    ///
+    /// let trajectory = Trajectory::sorted(vec![
+    ///     Point { time: 2., ..Default::default() },
+    ///     Point { time: 1., ..Default::default() },
+    /// ]);
+    /// assert!(trajectory.is_sorted());
+    /// ```
+    pub fn sorted(mut points: Vec<Point>) -> Trajectory {
+        crate::sort_by_time(&mut points);
+        Trajectory { points }
+    }
+
+    /// Reads a trajectory from the SBET file at the path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Trajectory;
+    ///
+    /// let trajectory = Trajectory::from_path("data/2-points.sbet").unwrap();
+    /// ```
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Trajectory> {
+        let points = Reader::from_path(path)?.into_points()?;
+        Ok(Trajectory { points })
+    }
+
+    /// Reads a trajectory from the SBET file at the path, reporting progress as it goes.
+    ///
+    /// `progress` is called with the number of points read so far after
+    /// every chunk of points. Return [ControlFlow::Break] from it to stop
+    /// reading, which returns [Error::Cancelled].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Trajectory;
+    /// use std::ops::ControlFlow;
+    ///
+    /// let mut read = 0;
+    /// let trajectory = Trajectory::from_path_with_progress("data/2-points.sbet", |n| {
+    ///     read = n;
+    ///     ControlFlow::Continue(())
+    /// })
+    /// .unwrap();
+    /// assert_eq!(read, 2);
+    /// ```
+    pub fn from_path_with_progress<P, F>(path: P, mut progress: F) -> Result<Trajectory>
+    where
+        P: AsRef<Path>,
+        F: FnMut(u64) -> ControlFlow<()>,
+    {
+        let mut reader = Reader::from_path(path)?;
+        let mut points = Vec::new();
+        while reader.read_chunk(PROGRESS_CHUNK_SIZE, &mut points)? > 0 {
+            if progress(points.len() as u64).is_break() {
+                return Err(Error::Cancelled);
+            }
+        }
+        Ok(Trajectory { points })
+    }
+
+    /// Returns this trajectory's points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Trajectory;
+    ///
+    /// let trajectory = Trajectory::from_path("data/2-points.sbet").unwrap();
+    /// let first = trajectory.points()[0];
+    /// ```
+    pub fn points(&self) -> &[Point] {
+        &self.points
+    }
+
+    /// Returns the number of points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Trajectory;
+    ///
+    /// assert_eq!(Trajectory::default().len(), 0);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns true if there are no points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Trajectory;
+    ///
+    /// assert!(Trajectory::default().is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Returns true if the points are sorted by time.
+    ///
+    /// [Trajectory::from_path] and `From<Vec<Point>>` keep the points in
+    /// their original order, which might not be sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Trajectory;
+    ///
+    /// let trajectory = Trajectory::from_path("data/2-points.sbet").unwrap();
+    /// assert!(trajectory.is_sorted());
+    /// ```
+    pub fn is_sorted(&self) -> bool {
+        crate::is_sorted(&self.points)
+    }
+
+    /// Sorts the points by time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Point, Trajectory};
+    ///
+    /// let mut trajectory = Trajectory::from(vec![
+    ///     Point { time: 2., ..Default::default() },
+    ///     Point { time: 1., ..Default::default() },
+    /// ]);
+    /// trajectory.sort_by_time();
+    /// assert_eq!(trajectory.points()[0].time, 1.);
+    /// ```
+    pub fn sort_by_time(&mut self) {
+        crate::sort_by_time(&mut self.points);
+    }
+
+    /// Returns the length of the trajectory along the WGS84 ellipsoid, in meters.
+    ///
+    /// This is the sum of the [Point::geodesic_distance] between consecutive points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Trajectory;
+    ///
+    /// let trajectory = Trajectory::from_path("data/2-points.sbet").unwrap();
+    /// let length = trajectory.length_meters();
+    /// assert!(length > 0. && length < 1.);
+    /// ```
+    pub fn length_meters(&self) -> f64 {
+        self.points
+            .windows(2)
+            .map(|pair| pair[0].geodesic_distance(&pair[1]))
+            .sum()
+    }
+
+    /// Expresses every point in an east-north-up frame anchored at the origin.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Trajectory;
+    ///
+    /// let trajectory = Trajectory::from_path("data/2-points.sbet").unwrap();
+    /// let origin = trajectory.points()[0];
+    /// let enu = trajectory.to_enu(&origin);
+    /// assert_eq!(enu[0].east, 0.);
+    /// ```
+    pub fn to_enu(&self, origin: &Point) -> Vec<Enu> {
+        self.points
+            .iter()
+            .map(|point| point.to_enu(origin))
+            .collect()
+    }
+
+    /// Expresses every point in a north-east-down frame anchored at the origin.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Trajectory;
+    ///
+    /// let trajectory = Trajectory::from_path("data/2-points.sbet").unwrap();
+    /// let origin = trajectory.points()[0];
+    /// let ned = trajectory.to_ned(&origin);
+    /// ```
+    pub fn to_ned(&self, origin: &Point) -> Vec<Ned> {
+        self.points
+            .iter()
+            .map(|point| point.to_ned(origin))
+            .collect()
+    }
+}
+
+impl From<Vec<Point>> for Trajectory {
+    fn from(points: Vec<Point>) -> Trajectory {
+        Trajectory { points }
+    }
+}
+
+impl From<Trajectory> for Vec<Point> {
+    fn from(trajectory: Trajectory) -> Vec<Point> {
+        trajectory.points
+    }
+}
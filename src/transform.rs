@@ -0,0 +1,59 @@
+use crate::{Point, Result};
+use proj::Proj;
+
+/// Reprojects point positions from WGS84 into another coordinate reference system.
+///
+/// Uses [PROJ](https://proj.org), which must be installed on the system.
+/// Only horizontal coordinates are transformed; altitudes are passed through
+/// unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::{Reader, Transformer};
+///
+/// let point = Reader::from_path("data/2-points.sbet")
+///     .unwrap()
+///     .read_one()
+///     .unwrap()
+///     .unwrap();
+/// let transformer = Transformer::from_epsg(32611).unwrap();
+/// let (easting, northing, altitude) = transformer.transform(&point).unwrap();
+/// ```
+pub struct Transformer {
+    proj: Proj,
+}
+
+impl Transformer {
+    /// Creates a transformer from WGS84 (EPSG:4326) to the given EPSG code.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Transformer;
+    ///
+    /// let transformer = Transformer::from_epsg(3857).unwrap();
+    /// ```
+    pub fn from_epsg(epsg: u32) -> Result<Transformer> {
+        let proj = Proj::new_known_crs("EPSG:4326", &format!("EPSG:{}", epsg), None)?;
+        Ok(Transformer { proj })
+    }
+
+    /// Transforms a point's position, returning the target CRS's x and y and the altitude.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Point, Transformer};
+    ///
+    /// let transformer = Transformer::from_epsg(3857).unwrap();
+    /// let (x, y, _) = transformer.transform(&Point::default()).unwrap();
+    /// assert!(x.abs() < 1e-6 && y.abs() < 1e-6);
+    /// ```
+    pub fn transform(&self, point: &Point) -> Result<(f64, f64, f64)> {
+        let (x, y) = self
+            .proj
+            .convert((point.longitude.to_degrees(), point.latitude.to_degrees()))?;
+        Ok((x, y, point.altitude))
+    }
+}
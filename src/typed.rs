@@ -0,0 +1,167 @@
+//! A unit-aware view of a point, behind the `uom` feature.
+
+use crate::Point;
+use uom::si::{
+    acceleration::meter_per_second_squared,
+    angle::radian,
+    angular_velocity::radian_per_second,
+    f64::{Acceleration, Angle, AngularVelocity, Length, Time, Velocity},
+    length::meter,
+    time::second,
+    velocity::meter_per_second,
+};
+
+/// A [Point] whose fields carry their units, as [uom] quantities.
+///
+/// Converting to and from a point is lossless, so reading a value in the
+/// wrong units, like degrees instead of radians, is a type error instead of
+/// a silent mistake.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::Point;
+/// use uom::si::{angle::degree, length::foot};
+///
+/// let point = Point::builder().latitude_degrees(45.).altitude(100.).build();
+/// let typed = point.typed();
+/// assert!((typed.latitude.get::<degree>() - 45.).abs() < 1e-12);
+/// assert!((typed.altitude.get::<foot>() - 328.084).abs() < 1e-3);
+/// assert_eq!(Point::from(typed), point);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TypedPoint {
+    /// The GPS time of week.
+    pub time: Time,
+
+    /// The latitude.
+    pub latitude: Angle,
+
+    /// The longitude.
+    pub longitude: Angle,
+
+    /// The ellipsoidal altitude.
+    pub altitude: Length,
+
+    /// The velocity along the wander-azimuth x axis.
+    pub x_velocity: Velocity,
+
+    /// The velocity along the wander-azimuth y axis.
+    pub y_velocity: Velocity,
+
+    /// The velocity along the down axis.
+    pub z_velocity: Velocity,
+
+    /// The roll.
+    pub roll: Angle,
+
+    /// The pitch.
+    pub pitch: Angle,
+
+    /// The yaw, i.e. the platform heading plus the wander angle.
+    pub yaw: Angle,
+
+    /// The wander angle.
+    pub wander_angle: Angle,
+
+    /// The body x acceleration.
+    pub x_acceleration: Acceleration,
+
+    /// The body y acceleration.
+    pub y_acceleration: Acceleration,
+
+    /// The body z acceleration.
+    pub z_acceleration: Acceleration,
+
+    /// The body x angular rate.
+    pub x_angular_rate: AngularVelocity,
+
+    /// The body y angular rate.
+    pub y_angular_rate: AngularVelocity,
+
+    /// The body z angular rate.
+    pub z_angular_rate: AngularVelocity,
+}
+
+impl Point {
+    /// Returns this point with units attached to each field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Point;
+    /// use uom::si::angular_velocity::degree_per_second;
+    ///
+    /// let point = Point { z_angular_rate: 1f64.to_radians(), ..Default::default() };
+    /// let rate = point.typed().z_angular_rate.get::<degree_per_second>();
+    /// assert!((rate - 1.).abs() < 1e-12);
+    /// ```
+    pub fn typed(&self) -> TypedPoint {
+        (*self).into()
+    }
+}
+
+impl From<Point> for TypedPoint {
+    fn from(point: Point) -> TypedPoint {
+        TypedPoint {
+            time: Time::new::<second>(point.time),
+            latitude: Angle::new::<radian>(point.latitude),
+            longitude: Angle::new::<radian>(point.longitude),
+            altitude: Length::new::<meter>(point.altitude),
+            x_velocity: Velocity::new::<meter_per_second>(point.x_velocity),
+            y_velocity: Velocity::new::<meter_per_second>(point.y_velocity),
+            z_velocity: Velocity::new::<meter_per_second>(point.z_velocity),
+            roll: Angle::new::<radian>(point.roll),
+            pitch: Angle::new::<radian>(point.pitch),
+            yaw: Angle::new::<radian>(point.yaw),
+            wander_angle: Angle::new::<radian>(point.wander_angle),
+            x_acceleration: Acceleration::new::<meter_per_second_squared>(point.x_acceleration),
+            y_acceleration: Acceleration::new::<meter_per_second_squared>(point.y_acceleration),
+            z_acceleration: Acceleration::new::<meter_per_second_squared>(point.z_acceleration),
+            x_angular_rate: AngularVelocity::new::<radian_per_second>(point.x_angular_rate),
+            y_angular_rate: AngularVelocity::new::<radian_per_second>(point.y_angular_rate),
+            z_angular_rate: AngularVelocity::new::<radian_per_second>(point.z_angular_rate),
+        }
+    }
+}
+
+impl From<TypedPoint> for Point {
+    fn from(typed: TypedPoint) -> Point {
+        Point {
+            time: typed.time.get::<second>(),
+            latitude: typed.latitude.get::<radian>(),
+            longitude: typed.longitude.get::<radian>(),
+            altitude: typed.altitude.get::<meter>(),
+            x_velocity: typed.x_velocity.get::<meter_per_second>(),
+            y_velocity: typed.y_velocity.get::<meter_per_second>(),
+            z_velocity: typed.z_velocity.get::<meter_per_second>(),
+            roll: typed.roll.get::<radian>(),
+            pitch: typed.pitch.get::<radian>(),
+            yaw: typed.yaw.get::<radian>(),
+            wander_angle: typed.wander_angle.get::<radian>(),
+            x_acceleration: typed.x_acceleration.get::<meter_per_second_squared>(),
+            y_acceleration: typed.y_acceleration.get::<meter_per_second_squared>(),
+            z_acceleration: typed.z_acceleration.get::<meter_per_second_squared>(),
+            x_angular_rate: typed.x_angular_rate.get::<radian_per_second>(),
+            y_angular_rate: typed.y_angular_rate.get::<radian_per_second>(),
+            z_angular_rate: typed.z_angular_rate.get::<radian_per_second>(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Field, Point};
+    use uom::si::velocity::knot;
+
+    #[test]
+    fn roundtrip() {
+        let mut point = Point::default();
+        for (i, field) in Field::ALL.into_iter().enumerate() {
+            point.set(field, i as f64 + 0.25);
+        }
+        assert_eq!(Point::from(point.typed()), point);
+        let knots = point.typed().x_velocity.get::<knot>();
+        assert!((knots - 4.25 * 3600. / 1852.).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,115 @@
+use crate::Error;
+use alloc::string::ToString;
+use core::{fmt, str::FromStr};
+
+const KNOTS_PER_METER_PER_SECOND: f64 = 3600. / 1852.;
+const KILOMETERS_PER_HOUR_PER_METER_PER_SECOND: f64 = 3.6;
+
+/// Units used when reporting speeds.
+///
+/// SBET velocities are always stored in meters per second.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::SpeedUnit;
+///
+/// let unit: SpeedUnit = "knots".parse().unwrap();
+/// assert_eq!(unit, SpeedUnit::Knots);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SpeedUnit {
+    /// Meters per second.
+    #[default]
+    MetersPerSecond,
+
+    /// Nautical miles per hour.
+    Knots,
+
+    /// Kilometers per hour.
+    KilometersPerHour,
+}
+
+impl SpeedUnit {
+    /// Converts a speed in meters per second into this unit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::SpeedUnit;
+    ///
+    /// assert_eq!(SpeedUnit::KilometersPerHour.convert(10.), 36.);
+    /// ```
+    pub fn convert(&self, meters_per_second: f64) -> f64 {
+        match self {
+            SpeedUnit::MetersPerSecond => meters_per_second,
+            SpeedUnit::Knots => meters_per_second * KNOTS_PER_METER_PER_SECOND,
+            SpeedUnit::KilometersPerHour => {
+                meters_per_second * KILOMETERS_PER_HOUR_PER_METER_PER_SECOND
+            }
+        }
+    }
+
+    /// Returns the abbreviation for this unit, e.g. for column headers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::SpeedUnit;
+    ///
+    /// assert_eq!(SpeedUnit::Knots.abbreviation(), "kn");
+    /// ```
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            SpeedUnit::MetersPerSecond => "m/s",
+            SpeedUnit::Knots => "kn",
+            SpeedUnit::KilometersPerHour => "km/h",
+        }
+    }
+}
+
+impl FromStr for SpeedUnit {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<SpeedUnit, Error> {
+        match s {
+            "mps" => Ok(SpeedUnit::MetersPerSecond),
+            "knots" => Ok(SpeedUnit::Knots),
+            "kmh" => Ok(SpeedUnit::KilometersPerHour),
+            _ => Err(Error::UnknownSpeedUnit(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for SpeedUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpeedUnit::MetersPerSecond => write!(f, "mps"),
+            SpeedUnit::Knots => write!(f, "knots"),
+            SpeedUnit::KilometersPerHour => write!(f, "kmh"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SpeedUnit;
+
+    #[test]
+    fn knots() {
+        let knots = SpeedUnit::Knots.convert(1852. / 3600.);
+        assert!((knots - 1.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn round_trip_str() {
+        for unit in [
+            SpeedUnit::MetersPerSecond,
+            SpeedUnit::Knots,
+            SpeedUnit::KilometersPerHour,
+        ] {
+            assert_eq!(unit, unit.to_string().parse().unwrap());
+        }
+        assert!("furlongs".parse::<SpeedUnit>().is_err());
+    }
+}
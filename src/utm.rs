@@ -0,0 +1,95 @@
+use crate::Point;
+
+/// A position in Universal Transverse Mercator (UTM) coordinates on the WGS84 ellipsoid.
+///
+/// Created by [Point::to_utm] or [Point::to_utm_in_zone].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Utm {
+    /// The easting, in meters.
+    pub easting: f64,
+
+    /// The northing, in meters.
+    ///
+    /// Southern hemisphere northings include the 10,000,000 meter false northing.
+    pub northing: f64,
+
+    /// The altitude, in meters, unchanged from the source point.
+    pub altitude: f64,
+
+    /// The UTM zone number, from 1 to 60.
+    pub zone: u8,
+
+    /// The UTM latitude band letter, if the latitude is between 80°S and 84°N.
+    pub band: Option<char>,
+}
+
+impl Point {
+    /// Converts this point's position to UTM, detecting the zone automatically.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Reader;
+    ///
+    /// let point = Reader::from_path("data/2-points.sbet")
+    ///     .unwrap()
+    ///     .read_one()
+    ///     .unwrap()
+    ///     .unwrap();
+    /// let utm = point.to_utm();
+    /// assert_eq!(utm.zone, 11);
+    /// assert_eq!(utm.band, Some('S'));
+    /// ```
+    pub fn to_utm(&self) -> Utm {
+        let zone =
+            ::utm::lat_lon_to_zone_number(self.latitude.to_degrees(), self.longitude.to_degrees());
+        self.to_utm_in_zone(zone)
+    }
+
+    /// Converts this point's position to UTM in the given zone.
+    ///
+    /// Use this to keep a whole trajectory in one zone, even if it crosses a
+    /// zone boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Reader;
+    ///
+    /// let point = Reader::from_path("data/2-points.sbet")
+    ///     .unwrap()
+    ///     .read_one()
+    ///     .unwrap()
+    ///     .unwrap();
+    /// let utm = point.to_utm_in_zone(12);
+    /// assert_eq!(utm.zone, 12);
+    /// ```
+    pub fn to_utm_in_zone(&self, zone: u8) -> Utm {
+        let (northing, easting, _) =
+            ::utm::radians_to_utm_wgs84(self.latitude, self.longitude, zone);
+        Utm {
+            easting,
+            northing,
+            altitude: self.altitude,
+            zone,
+            band: ::utm::lat_to_zone_letter(self.latitude.to_degrees()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Point;
+
+    #[test]
+    fn central_meridian() {
+        let point = Point {
+            latitude: 0.,
+            longitude: 3f64.to_radians(),
+            ..Default::default()
+        };
+        let utm = point.to_utm();
+        assert_eq!(utm.zone, 31);
+        assert!((utm.easting - 500000.).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,79 @@
+//! JavaScript bindings for WebAssembly, behind the `wasm` feature.
+//!
+//! Build a `cdylib` for `wasm32-unknown-unknown` and generate JavaScript
+//! glue with [wasm-bindgen](https://rustwasm.github.io/wasm-bindgen/):
+//!
+//! ```shell
+//! cargo rustc --lib --release --target wasm32-unknown-unknown --features wasm --crate-type cdylib
+//! wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/sbet.wasm
+//! ```
+//!
+//! Then, in JavaScript:
+//!
+//! ```js
+//! import init, { parse } from "./pkg/sbet.js";
+//!
+//! await init();
+//! const bytes = new Uint8Array(await (await fetch("trajectory.sbet")).arrayBuffer());
+//! const trajectory = parse(bytes);
+//! const latitudes = trajectory.field("latitude"); // Float64Array, in radians
+//! ```
+
+use crate::{Field, Interpolator, Point, Reader};
+use wasm_bindgen::prelude::*;
+
+/// Parses SBET records from a byte buffer.
+///
+/// In JavaScript, `bytes` is a `Uint8Array`.
+#[wasm_bindgen]
+pub fn parse(bytes: &[u8]) -> Result<Trajectory, JsError> {
+    let points = Reader::new(bytes).into_points()?;
+    Ok(Trajectory { points })
+}
+
+/// A parsed trajectory.
+#[wasm_bindgen]
+pub struct Trajectory {
+    points: Vec<Point>,
+}
+
+#[wasm_bindgen]
+impl Trajectory {
+    /// The number of points.
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.points.len()
+    }
+
+    /// The names of the fields that can be passed to [Trajectory::field].
+    #[wasm_bindgen(js_name = fieldNames)]
+    pub fn field_names() -> Vec<String> {
+        Field::ALL
+            .iter()
+            .map(|field| field.name().to_string())
+            .collect()
+    }
+
+    /// Returns one field's values for every point, as a `Float64Array`.
+    pub fn field(&self, name: &str) -> Result<Vec<f64>, JsError> {
+        let field: Field = name.parse()?;
+        Ok(self.points.iter().map(|point| point.get(field)).collect())
+    }
+
+    /// Returns one point's values as a `Float64Array`, in [Trajectory::field_names] order.
+    pub fn point(&self, index: usize) -> Option<Vec<f64>> {
+        self.points.get(index).map(values)
+    }
+
+    /// Interpolates a point at a time, returning its values as a `Float64Array`.
+    ///
+    /// The points must be sorted by time.
+    pub fn interpolate(&self, time: f64) -> Result<Vec<f64>, JsError> {
+        let point = Interpolator::new(&self.points)?.interpolate(time)?;
+        Ok(values(&point))
+    }
+}
+
+fn values(point: &Point) -> Vec<f64> {
+    Field::ALL.iter().map(|&field| point.get(field)).collect()
+}
@@ -0,0 +1,94 @@
+use crate::Error;
+use alloc::string::ToString;
+use core::{fmt, str::FromStr};
+
+/// An inclusive range of times.
+///
+/// Parses from and displays as `start:stop`.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::TimeWindow;
+///
+/// let window: TimeWindow = "10.5:20".parse().unwrap();
+/// assert!(window.contains(15.));
+/// assert_eq!(window.to_string(), "10.5:20");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TimeWindow {
+    /// The first time in the window.
+    pub start: f64,
+
+    /// The last time in the window.
+    pub stop: f64,
+}
+
+impl TimeWindow {
+    /// Creates a window from its start and stop times.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::TimeWindow;
+    ///
+    /// let window = TimeWindow::new(1., 2.);
+    /// ```
+    pub fn new(start: f64, stop: f64) -> TimeWindow {
+        TimeWindow { start, stop }
+    }
+
+    /// Returns true if the time is within this window, including its ends.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::TimeWindow;
+    ///
+    /// let window = TimeWindow::new(1., 2.);
+    /// assert!(window.contains(2.));
+    /// assert!(!window.contains(2.5));
+    /// ```
+    pub fn contains(&self, time: f64) -> bool {
+        time >= self.start && time <= self.stop
+    }
+
+    /// Returns the length of this window, in seconds.
+    pub fn duration(&self) -> f64 {
+        self.stop - self.start
+    }
+}
+
+impl fmt::Display for TimeWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.start, self.stop)
+    }
+}
+
+impl FromStr for TimeWindow {
+    type Err = Error;
+
+    /// Parses `start:stop`, where the stop isn't before the start.
+    fn from_str(s: &str) -> Result<TimeWindow, Error> {
+        s.split_once(':')
+            .and_then(|(start, stop)| Some((start.parse().ok()?, stop.parse().ok()?)))
+            .filter(|(start, stop)| start <= stop)
+            .map(|(start, stop)| TimeWindow { start, stop })
+            .ok_or_else(|| Error::InvalidTimeWindow(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TimeWindow;
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            "-1:inf".parse::<TimeWindow>().unwrap(),
+            TimeWindow::new(-1., f64::INFINITY)
+        );
+        assert!("2:1".parse::<TimeWindow>().is_err());
+        assert!("1".parse::<TimeWindow>().is_err());
+    }
+}
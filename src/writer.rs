@@ -0,0 +1,481 @@
+use crate::{Compression, Endianness, Error, Output, Point, Reader, RecordSchema, Result};
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+/// Use this structure to write sbet data.
+///
+/// The underlying writer is flushed when the writer is dropped, but any
+/// error is lost; call [Writer::finish] to check for one.
+#[derive(Debug)]
+pub struct Writer<W: Write> {
+    write: Option<W>,
+    endianness: Endianness,
+    schema: RecordSchema,
+    standard: bool,
+    buffer: Vec<u8>,
+    records: u64,
+    last_time: Option<f64>,
+    stats: WriterStats,
+}
+
+/// What a [Writer] has written.
+///
+/// # Examples
+///
+/// ```
+/// use sbet::{Point, Writer};
+///
+/// let mut writer = Writer::new(Vec::new());
+/// writer.write_one(Point { time: 1., ..Default::default() }).unwrap();
+/// writer.write_one(Point { time: 2., ..Default::default() }).unwrap();
+/// let stats = writer.stats();
+/// assert_eq!(stats.points, 2);
+/// assert_eq!(stats.bytes, 2 * sbet::RECORD_SIZE as u64);
+/// assert_eq!(stats.first_time, Some(1.));
+/// assert_eq!(stats.last_time, Some(2.));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct WriterStats {
+    /// The number of points written.
+    pub points: u64,
+
+    /// The number of bytes written.
+    pub bytes: u64,
+
+    /// The time of the first point written.
+    pub first_time: Option<f64>,
+
+    /// The time of the last point written.
+    pub last_time: Option<f64>,
+}
+
+impl WriterStats {
+    fn add(&mut self, points: &[Point], size: usize) {
+        if let (Some(first), Some(last)) = (points.first(), points.last()) {
+            self.points += points.len() as u64;
+            self.bytes += (points.len() * size) as u64;
+            self.first_time.get_or_insert(first.time);
+            self.last_time = Some(last.time);
+        }
+    }
+}
+
+impl<W: Write> Writer<W> {
+    /// Creates a writer for little-endian records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::Writer;
+    ///
+    /// let writer = Writer::new(Vec::new());
+    /// ```
+    pub fn new(write: W) -> Writer<W> {
+        Writer::with_endianness(write, Endianness::Little)
+    }
+
+    /// Creates a writer for records in the given byte order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Endianness, Point, Writer};
+    ///
+    /// let mut writer = Writer::with_endianness(Vec::new(), Endianness::Big);
+    /// writer.write_one(Point { time: 1., ..Default::default() }).unwrap();
+    /// assert_eq!(writer.into_inner()[..8], 1f64.to_be_bytes());
+    /// ```
+    pub fn with_endianness(write: W, endianness: Endianness) -> Writer<W> {
+        Writer {
+            write: Some(write),
+            endianness,
+            schema: RecordSchema::standard(),
+            standard: true,
+            buffer: Vec::new(),
+            records: 0,
+            last_time: None,
+            stats: WriterStats::default(),
+        }
+    }
+
+    /// Creates a writer for little-endian records with a nonstandard layout.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Column, Field, Point, RecordSchema, Writer};
+    ///
+    /// let schema = RecordSchema::new(vec![Column::F64(Field::Time), Column::Skip(8)]);
+    /// let mut writer = Writer::with_schema(Vec::new(), schema);
+    /// writer.write_one(Point::default()).unwrap();
+    /// assert_eq!(writer.into_inner().len(), 16);
+    /// ```
+    pub fn with_schema(write: W, schema: RecordSchema) -> Writer<W> {
+        let mut writer = Writer::new(write);
+        writer.set_schema(schema);
+        writer
+    }
+
+    /// Returns this writer's byte order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Endianness, Writer};
+    ///
+    /// let writer = Writer::new(Vec::new());
+    /// assert_eq!(writer.endianness(), Endianness::Little);
+    /// ```
+    pub fn endianness(&self) -> Endianness {
+        self.endianness
+    }
+
+    /// Sets this writer's byte order for subsequent writes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Endianness, Writer};
+    ///
+    /// let mut writer = Writer::new(Vec::new());
+    /// writer.set_endianness(Endianness::Big);
+    /// ```
+    pub fn set_endianness(&mut self, endianness: Endianness) {
+        self.endianness = endianness;
+    }
+
+    /// Returns this writer's record layout.
+    pub fn schema(&self) -> &RecordSchema {
+        &self.schema
+    }
+
+    /// Sets this writer's record layout for subsequent writes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{RecordSchema, Writer};
+    ///
+    /// let mut writer = Writer::new(Vec::new());
+    /// writer.set_schema(RecordSchema::standard());
+    /// ```
+    pub fn set_schema(&mut self, schema: RecordSchema) {
+        self.standard = schema.is_standard();
+        self.schema = schema;
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        self.write.as_ref().expect("writer is not finished")
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        self.write.as_mut().expect("writer is not finished")
+    }
+
+    /// Consumes this writer, returning the underlying writer without flushing it.
+    pub fn into_inner(mut self) -> W {
+        self.write.take().expect("writer is not finished")
+    }
+
+    /// Returns the number of points and bytes written by this writer, and the times of the first and last points.
+    ///
+    /// The times are in the order written, so they're only the time range
+    /// if the points were written sorted. Records that were already in a
+    /// file opened with [Writer::append_to_path] aren't counted.
+    pub fn stats(&self) -> WriterStats {
+        self.stats
+    }
+
+    /// Writes one point to the writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Writer, Point};
+    ///
+    /// let mut writer = Writer::new(std::io::stdout());
+    /// writer.write_one(Point::default());
+    /// ```
+    pub fn write_one(&mut self, point: Point) -> Result<()> {
+        self.check_times(std::slice::from_ref(&point))?;
+        if self.standard {
+            let bytes = self.endianness.encode(&point);
+            self.get_mut().write_all(&bytes)?;
+        } else {
+            self.buffer.resize(self.schema.size(), 0);
+            self.schema
+                .encode(&point, self.endianness, &mut self.buffer);
+            self.write
+                .as_mut()
+                .expect("writer is not finished")
+                .write_all(&self.buffer)?;
+        }
+        self.records += 1;
+        self.stats
+            .add(std::slice::from_ref(&point), self.schema.size());
+        Ok(())
+    }
+
+    /// Writes many points with a single write to the underlying writer.
+    ///
+    /// The points are encoded into an internal buffer first, which is much
+    /// faster than calling [Writer::write_one] for each point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Point, Writer};
+    ///
+    /// let mut writer = Writer::new(Vec::new());
+    /// writer.write_all(&[Point::default(); 3]).unwrap();
+    /// assert_eq!(writer.into_inner().len(), 3 * sbet::RECORD_SIZE);
+    /// ```
+    pub fn write_all(&mut self, points: &[Point]) -> Result<()> {
+        self.check_times(points)?;
+        let size = self.schema.size();
+        self.buffer.clear();
+        self.buffer.resize(points.len() * size, 0);
+        for (point, bytes) in points.iter().zip(self.buffer.chunks_exact_mut(size)) {
+            if self.standard {
+                bytes.copy_from_slice(&self.endianness.encode(point));
+            } else {
+                self.schema.encode(point, self.endianness, bytes);
+            }
+        }
+        self.write
+            .as_mut()
+            .expect("writer is not finished")
+            .write_all(&self.buffer)?;
+        self.records += points.len() as u64;
+        self.stats.add(points, size);
+        Ok(())
+    }
+
+    /// Checks that the points are after the last written time, if times are being checked.
+    fn check_times(&mut self, points: &[Point]) -> Result<()> {
+        if let Some(mut last_time) = self.last_time {
+            for (i, point) in points.iter().enumerate() {
+                if point.time <= last_time || point.time.is_nan() {
+                    return Err(Error::NonMonotonicTime {
+                        index: self.records + i as u64,
+                        previous: last_time,
+                        current: point.time,
+                    });
+                }
+                last_time = point.time;
+            }
+            self.last_time = Some(last_time);
+        }
+        Ok(())
+    }
+
+    /// Flushes the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sbet::{Point, Writer};
+    ///
+    /// let mut writer = Writer::new(std::io::stdout());
+    /// writer.write_one(Point::default()).unwrap();
+    /// writer.flush().unwrap();
+    /// ```
+    pub fn flush(&mut self) -> Result<()> {
+        self.get_mut().flush()?;
+        Ok(())
+    }
+
+    /// Flushes and consumes this writer, returning the underlying writer.
+    ///
+    /// Unlike dropping the writer, this reports errors from the final flush.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use sbet::{Point, Writer};
+    ///
+    /// let mut writer = Writer::from_path("outfile.sbet").unwrap();
+    /// writer.write_all(&[Point::default(); 2]).unwrap();
+    /// let file = writer.finish().unwrap();
+    /// ```
+    pub fn finish(mut self) -> Result<W> {
+        self.flush()?;
+        Ok(self.into_inner())
+    }
+}
+
+impl<W: Write> Drop for Writer<W> {
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    fn drop(&mut self) {
+        if let Some(write) = self.write.as_mut() {
+            if let Err(err) = write.flush() {
+                debug!(%err, "failed to flush the writer on drop");
+            }
+        }
+    }
+}
+
+impl Writer<BufWriter<File>> {
+    /// Creates a writer for the file at the path.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use sbet::Writer;
+    ///
+    /// let writer = Writer::from_path("outfile.sbet").unwrap();
+    /// ```
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Writer<BufWriter<File>>> {
+        File::create(path)
+            .map(|f| Writer::new(BufWriter::new(f)))
+            .map_err(|e| e.into())
+    }
+
+    /// Creates a writer that appends little-endian records to the file at the path.
+    ///
+    /// The file is created if it doesn't exist. If `check_times` is true,
+    /// every appended point must be later than the one before it, starting
+    /// with the file's last record; a point that isn't is rejected with
+    /// [Error::NonMonotonicTime] and nothing is written.
+    ///
+    /// # Errors
+    ///
+    /// Returns [Error::UnalignedLength] if the file's length isn't a whole
+    /// number of records, e.g. because a previous write was cut short.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use sbet::{Point, Writer};
+    ///
+    /// let mut writer = Writer::append_to_path("log.sbet", true).unwrap();
+    /// writer.write_one(Point { time: 151700., ..Default::default() }).unwrap();
+    /// writer.finish().unwrap();
+    /// ```
+    pub fn append_to_path<P: AsRef<Path>>(
+        path: P,
+        check_times: bool,
+    ) -> Result<Writer<BufWriter<File>>> {
+        let path = path.as_ref();
+        let file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(path)?;
+        let records = crate::exact_number_of_points(path)?;
+        let mut writer = Writer::new(BufWriter::new(file.try_clone()?));
+        writer.records = records;
+        if check_times {
+            let last = Reader::new(file).read_last()?;
+            writer.last_time = Some(last.map_or(f64::NEG_INFINITY, |point| point.time));
+        }
+        Ok(writer)
+    }
+}
+
+impl Writer<Output> {
+    /// Creates a writer for the file at the path, compressing the records.
+    ///
+    /// Call [Output::finish] on the output returned by [Writer::finish] to
+    /// check that the compressed stream was finished.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "zstd")] {
+    /// use sbet::{Compression, Point, Writer};
+    ///
+    /// let mut writer = Writer::from_path_compressed("outfile.sbet.zst", Compression::Zstd).unwrap();
+    /// writer.write_one(Point::default()).unwrap();
+    /// writer.finish().unwrap().finish().unwrap();
+    /// # }
+    /// ```
+    pub fn from_path_compressed<P: AsRef<Path>>(
+        path: P,
+        compression: Compression,
+    ) -> Result<Writer<Output>> {
+        Output::create(path, compression)
+            .map(Writer::new)
+            .map_err(|e| e.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Writer;
+    use crate::{Error, Point, Reader};
+
+    fn point(time: f64) -> Point {
+        Point {
+            time,
+            ..Default::default()
+        }
+    }
+
+    struct Flag<'a>(&'a mut bool);
+
+    impl std::io::Write for Flag<'_> {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            *self.0 = true;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn flush_on_drop() {
+        let mut flushed = false;
+        drop(Writer::new(Flag(&mut flushed)));
+        assert!(flushed);
+
+        let mut flushed = false;
+        let _ = Writer::new(Flag(&mut flushed)).into_inner();
+        assert!(!flushed);
+    }
+
+    #[test]
+    fn append() {
+        let path = std::env::temp_dir().join(format!("sbet-append-{}.sbet", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut writer = Writer::append_to_path(&path, true).unwrap();
+        writer.write_all(&[point(1.), point(2.)]).unwrap();
+        writer.finish().unwrap();
+
+        let mut writer = Writer::append_to_path(&path, true).unwrap();
+        assert!(matches!(
+            writer.write_all(&[point(3.), point(2.5)]).unwrap_err(),
+            Error::NonMonotonicTime {
+                index: 3,
+                previous: 3.,
+                current: 2.5
+            }
+        ));
+        writer.write_one(point(3.)).unwrap();
+        assert!(writer.write_one(point(3.)).is_err());
+        assert_eq!(writer.stats().points, 1);
+        writer.finish().unwrap();
+        let times: Vec<f64> = Reader::from_path(&path)
+            .unwrap()
+            .map(|result| result.unwrap().time)
+            .collect();
+        assert_eq!(times, [1., 2., 3.]);
+
+        std::fs::write(&path, [0; 10]).unwrap();
+        assert!(matches!(
+            Writer::append_to_path(&path, false).unwrap_err(),
+            Error::UnalignedLength {
+                len: 10,
+                remainder: 10
+            }
+        ));
+        std::fs::remove_file(path).unwrap();
+    }
+}